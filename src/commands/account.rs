@@ -0,0 +1,385 @@
+use base64::Engine;
+use serde_json::{json, Value};
+use solana_account_decoder_client_types::{UiAccount, UiAccountData, UiAccountEncoding};
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_request::RpcRequest;
+use solana_client::rpc_response::Response;
+use solana_sdk::pubkey::Pubkey;
+use std::error::Error;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::commands::export::{to_csv_or_header, write_output};
+use crate::commands::node_health::format_sol;
+use crate::commands::rpc;
+use crate::commands::OutputFormat;
+use crate::types::{AccountDetails, AccountField};
+
+const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+
+/// Programs shown by name instead of by address.
+const KNOWN_PROGRAMS: [(&str, &str); 14] = [
+    (SYSTEM_PROGRAM, "System Program"),
+    ("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA", "Token Program"),
+    ("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb", "Token-2022 Program"),
+    ("Stake11111111111111111111111111111111111111", "Stake Program"),
+    ("Vote111111111111111111111111111111111111111", "Vote Program"),
+    ("BPFLoaderUpgradeab1e11111111111111111111111", "BPF Upgradeable Loader"),
+    ("BPFLoader2111111111111111111111111111111111", "BPF Loader"),
+    ("BPFLoader1111111111111111111111111111111111", "BPF Loader (deprecated)"),
+    ("LoaderV411111111111111111111111111111111111", "Loader v4"),
+    ("Config1111111111111111111111111111111111111", "Config Program"),
+    ("Sysvar1111111111111111111111111111111111111", "Sysvar"),
+    ("NativeLoader1111111111111111111111111111111", "Native Loader"),
+    ("AddressLookupTab1e1111111111111111111111111", "Address Lookup Table Program"),
+    ("ComputeBudget111111111111111111111111111111", "Compute Budget Program"),
+];
+
+const TOKEN_FIELDS: &[(&str, &str)] = &[
+    // Mint
+    ("Supply", "/info/supply"),
+    ("Decimals", "/info/decimals"),
+    ("Mint authority", "/info/mintAuthority"),
+    ("Freeze authority", "/info/freezeAuthority"),
+    // Token account
+    ("Mint", "/info/mint"),
+    ("Token owner", "/info/owner"),
+    ("Token balance", "/info/tokenAmount/uiAmountString"),
+    ("State", "/info/state"),
+    ("Delegate", "/info/delegate"),
+    ("Delegated amount", "/info/delegatedAmount/uiAmountString"),
+    ("Close authority", "/info/closeAuthority"),
+    ("Native", "/info/isNative"),
+    ("Extensions", "/info/extensions"),
+];
+
+/// Fields shown for the accounts the node decodes, by parser, as
+/// (label, JSON pointer into the parsed data). Pointers missing from an
+/// account, e.g. the delegation of an undelegated stake account, are skipped.
+const DECODED_FIELDS: [(&str, &[(&str, &str)]); 6] = [
+    ("spl-token", TOKEN_FIELDS),
+    ("spl-token-2022", TOKEN_FIELDS),
+    (
+        "stake",
+        &[
+            ("Staker", "/info/meta/authorized/staker"),
+            ("Withdrawer", "/info/meta/authorized/withdrawer"),
+            ("Rent-exempt reserve", "/info/meta/rentExemptReserve"),
+            ("Lockup epoch", "/info/meta/lockup/epoch"),
+            ("Lockup custodian", "/info/meta/lockup/custodian"),
+            ("Voter", "/info/stake/delegation/voter"),
+            ("Delegated stake", "/info/stake/delegation/stake"),
+            ("Activation epoch", "/info/stake/delegation/activationEpoch"),
+            ("Deactivation epoch", "/info/stake/delegation/deactivationEpoch"),
+            ("Credits observed", "/info/stake/creditsObserved"),
+        ],
+    ),
+    (
+        "vote",
+        &[
+            ("Validator identity", "/info/nodePubkey"),
+            ("Withdraw authority", "/info/authorizedWithdrawer"),
+            ("Commission", "/info/commission"),
+            ("Root slot", "/info/rootSlot"),
+            ("Last timestamp slot", "/info/lastTimestamp/slot"),
+            ("Votes", "/info/votes"),
+            ("Epoch credits", "/info/epochCredits"),
+        ],
+    ),
+    (
+        "bpf-upgradeable-loader",
+        &[
+            ("Program data", "/info/programData"),
+            ("Deployed slot", "/info/slot"),
+            ("Upgrade authority", "/info/authority"),
+        ],
+    ),
+    (
+        "nonce",
+        &[
+            ("Nonce authority", "/info/authority"),
+            ("Durable nonce", "/info/blockhash"),
+            ("Lamports per signature", "/info/feeCalculator/lamportsPerSignature"),
+        ],
+    ),
+];
+
+/// Bytes of undecoded data shown as hex.
+const DATA_PREVIEW: usize = 32;
+
+pub fn program_name(program_id: &str) -> Option<&'static str> {
+    KNOWN_PROGRAMS
+        .iter()
+        .find(|(id, _)| *id == program_id)
+        .map(|(_, name)| *name)
+}
+
+/// Reads an account with `jsonParsed` encoding, so the node decodes the data
+/// of the programs it knows (system, token, token-2022, stake, vote, loader,
+/// nonce, sysvars) and returns the rest as base64.
+pub fn fetch_account(client: &RpcClient, address: &str) -> Result<AccountDetails, Box<dyn Error>> {
+    let pubkey = Pubkey::from_str(address).map_err(|e| format!("Invalid account {}: {}", address, e))?;
+    let response: Response<Option<UiAccount>> = client.send(
+        RpcRequest::GetAccountInfo,
+        json!([pubkey.to_string(), { "encoding": "jsonParsed", "commitment": "confirmed" }]),
+    )?;
+    let account = response
+        .value
+        .ok_or_else(|| format!("Account {} does not exist", pubkey))?;
+
+    let mut details = AccountDetails {
+        address: pubkey.to_string(),
+        slot: response.context.slot,
+        lamports: account.lamports,
+        owner_name: program_name(&account.owner).map(str::to_string),
+        owner: account.owner,
+        executable: account.executable,
+        ..AccountDetails::default()
+    };
+
+    let data_len = match account.data {
+        UiAccountData::Json(parsed) => {
+            let account_type = parsed.parsed.get("type").and_then(Value::as_str).unwrap_or("unknown");
+            details.kind = Some(format!("{}/{}", parsed.program, account_type));
+            details.fields = decoded_fields(&parsed.program, &parsed.parsed);
+            details.parsed = Some(parsed.parsed);
+            parsed.space
+        }
+        UiAccountData::Binary(data, UiAccountEncoding::Base64) => {
+            let bytes = base64::engine::general_purpose::STANDARD.decode(&data).unwrap_or_default();
+            if !bytes.is_empty() {
+                let preview: String = bytes.iter().take(DATA_PREVIEW).map(|byte| format!("{:02x}", byte)).collect();
+                let more = if bytes.len() > DATA_PREVIEW { "..." } else { "" };
+                details.fields.push(field("Data", format!("{}{}", preview, more)));
+            }
+            bytes.len() as u64
+        }
+        _ => 0,
+    };
+    details.data_len = account.space.unwrap_or(data_len);
+    if details.kind.is_none() && details.owner == SYSTEM_PROGRAM && details.data_len == 0 {
+        details.kind = Some("system/wallet".to_string());
+    }
+
+    details.rent_exempt_minimum = client.get_minimum_balance_for_rent_exemption(details.data_len as usize)?;
+    details.rent_exempt = details.lamports >= details.rent_exempt_minimum;
+    Ok(details)
+}
+
+fn field(name: &str, value: String) -> AccountField {
+    AccountField {
+        name: name.to_string(),
+        value,
+    }
+}
+
+/// Picks the known fields for `program`, or every scalar in `info` for
+/// parsers without a field list (sysvars, config, lookup tables, ...).
+fn decoded_fields(program: &str, parsed: &Value) -> Vec<AccountField> {
+    match DECODED_FIELDS.iter().find(|(parser, _)| *parser == program) {
+        Some((_, fields)) => fields
+            .iter()
+            .filter_map(|(name, pointer)| parsed.pointer(pointer).map(|value| field(name, format_value(value))))
+            .collect(),
+        None => parsed
+            .get("info")
+            .and_then(Value::as_object)
+            .map(|info| {
+                info.iter()
+                    .filter(|(_, value)| !value.is_array() && !value.is_object())
+                    .map(|(name, value)| field(name, format_value(value)))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+fn format_value(value: &Value) -> String {
+    match value {
+        Value::Null => "none".to_string(),
+        Value::String(value) => value.clone(),
+        Value::Bool(value) => if *value { "yes" } else { "no" }.to_string(),
+        Value::Array(items) if items.iter().all(|item| item.get("extension").is_some()) && !items.is_empty() => items
+            .iter()
+            .filter_map(|item| item.get("extension").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join(", "),
+        Value::Array(items) => format!("{} entries", items.len()),
+        value => value.to_string(),
+    }
+}
+
+/// Summary and decoded fields in display order, as shown by the table
+/// output and the TUI.
+pub fn account_fields(details: &AccountDetails) -> Vec<AccountField> {
+    let owner = match &details.owner_name {
+        Some(name) => format!("{} ({})", name, details.owner),
+        None => details.owner.clone(),
+    };
+    let rent = if details.rent_exempt {
+        format!("yes (minimum {})", format_sol(details.rent_exempt_minimum))
+    } else {
+        format!(
+            "no, minimum {}, {} lamports short",
+            format_sol(details.rent_exempt_minimum),
+            details.rent_exempt_minimum - details.lamports
+        )
+    };
+
+    let mut fields = vec![
+        field("Address", details.address.clone()),
+        field("Balance", format_sol(details.lamports)),
+        field("Owner", owner),
+        field("Executable", if details.executable { "yes" } else { "no" }.to_string()),
+        field("Data size", format!("{} bytes", details.data_len)),
+        field("Rent exempt", rent),
+    ];
+    if let Some(kind) = &details.kind {
+        fields.push(field("Type", kind.clone()));
+    }
+    fields.extend(details.fields.iter().cloned());
+    fields
+}
+
+pub fn run_account(url: &str, address: &str, format: OutputFormat, output: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    let client = rpc::client(url)?;
+    let details = fetch_account(&client, address)?;
+
+    let rendered = match format {
+        OutputFormat::Table => format_table(&details),
+        OutputFormat::Csv => to_csv_or_header(&account_fields(&details))?,
+        OutputFormat::Json => serde_json::to_string_pretty(&details)?,
+    };
+
+    write_output(&rendered, output)?;
+
+    Ok(())
+}
+
+fn format_table(details: &AccountDetails) -> String {
+    let mut out = format!("Account at slot {}\n\n", details.slot);
+    for field in account_fields(details) {
+        out.push_str(&format!("{:<24} {}\n", field.name, field.value));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names_and_values(fields: &[AccountField]) -> Vec<(&str, &str)> {
+        fields.iter().map(|field| (field.name.as_str(), field.value.as_str())).collect()
+    }
+
+    #[test]
+    fn format_value_renders_scalars_extensions_and_arrays() {
+        assert_eq!(format_value(&Value::Null), "none");
+        assert_eq!(format_value(&json!("abc")), "abc");
+        assert_eq!(format_value(&json!(true)), "yes");
+        assert_eq!(format_value(&json!(false)), "no");
+        assert_eq!(format_value(&json!(42)), "42");
+        assert_eq!(
+            format_value(&json!([
+                { "extension": "transferFeeConfig", "state": {} },
+                { "extension": "metadataPointer", "state": {} },
+            ])),
+            "transferFeeConfig, metadataPointer"
+        );
+        assert_eq!(format_value(&json!([{ "slot": 1 }, { "slot": 2 }, { "slot": 3 }])), "3 entries");
+        assert_eq!(format_value(&json!([])), "0 entries");
+    }
+
+    #[test]
+    fn decoded_fields_picks_token_fields() {
+        let parsed = json!({
+            "type": "mint",
+            "info": {
+                "supply": "1000000",
+                "decimals": 6,
+                "mintAuthority": null,
+                "freezeAuthority": "Freeze1111111111111111111111111111111111111",
+                "isInitialized": true,
+                "extensions": [{ "extension": "mintCloseAuthority", "state": {} }],
+            },
+        });
+
+        assert_eq!(
+            names_and_values(&decoded_fields("spl-token-2022", &parsed)),
+            [
+                ("Supply", "1000000"),
+                ("Decimals", "6"),
+                ("Mint authority", "none"),
+                ("Freeze authority", "Freeze1111111111111111111111111111111111111"),
+                ("Extensions", "mintCloseAuthority"),
+            ]
+        );
+    }
+
+    #[test]
+    fn decoded_fields_skips_missing_stake_delegation() {
+        let parsed = json!({
+            "type": "initialized",
+            "info": {
+                "meta": {
+                    "authorized": { "staker": "staker", "withdrawer": "withdrawer" },
+                    "lockup": { "custodian": "custodian", "epoch": 0, "unixTimestamp": 0 },
+                    "rentExemptReserve": "2282880",
+                },
+                "stake": null,
+            },
+        });
+
+        assert_eq!(
+            names_and_values(&decoded_fields("stake", &parsed)),
+            [
+                ("Staker", "staker"),
+                ("Withdrawer", "withdrawer"),
+                ("Rent-exempt reserve", "2282880"),
+                ("Lockup epoch", "0"),
+                ("Lockup custodian", "custodian"),
+            ]
+        );
+    }
+
+    #[test]
+    fn decoded_fields_falls_back_to_scalar_info_fields() {
+        let parsed = json!({
+            "type": "rent",
+            "info": {
+                "burnPercent": 50,
+                "exemptionThreshold": 2.0,
+                "lamportsPerByteYear": "3480",
+                "nested": { "ignored": true },
+            },
+        });
+
+        assert_eq!(
+            names_and_values(&decoded_fields("sysvar", &parsed)),
+            [("burnPercent", "50"), ("exemptionThreshold", "2.0"), ("lamportsPerByteYear", "3480")]
+        );
+        assert!(decoded_fields("sysvar", &json!({ "type": "unknown" })).is_empty());
+    }
+
+    #[test]
+    fn account_fields_names_the_owner_and_reports_rent_shortfall() {
+        let details = AccountDetails {
+            address: "address".to_string(),
+            lamports: 500_000,
+            owner: SYSTEM_PROGRAM.to_string(),
+            owner_name: Some("System Program".to_string()),
+            data_len: 0,
+            rent_exempt_minimum: 890_880,
+            rent_exempt: false,
+            kind: Some("system/wallet".to_string()),
+            ..AccountDetails::default()
+        };
+
+        let fields = account_fields(&details);
+        let fields = names_and_values(&fields);
+        assert!(fields.contains(&("Balance", "0.0005 SOL")));
+        assert!(fields.contains(&("Owner", "System Program (11111111111111111111111111111111)")));
+        assert!(fields.contains(&("Rent exempt", "no, minimum 0.00089088 SOL, 390880 lamports short")));
+        assert_eq!(fields.last(), Some(&("Type", "system/wallet")));
+    }
+}
@@ -0,0 +1,342 @@
+use chrono::{DateTime, Local};
+use serde_json::json;
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::commands::probe::ProbeSample;
+use crate::commands::rpc::redact_url;
+use crate::types::{AlertEvent, AlertState};
+use crate::utils::config::{AlertCondition, AlertConfig, AlertRule, NotifierConfig};
+
+/// Time allowed for a single notification, HTTP or command, to be delivered.
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Current state of a single alert rule.
+pub struct RuleStatus {
+    pub rule: AlertRule,
+    /// Latest value of the rule's metric, if the probe produced one.
+    pub value: Option<f64>,
+    pub firing: bool,
+    pub since: Option<DateTime<Local>>,
+    /// Whether the current firing period was announced; resolutions are only
+    /// sent for announced alerts.
+    notified: bool,
+    last_notified: Option<Instant>,
+}
+
+/// Evaluates alert rules against probe samples and delivers state changes.
+///
+/// A rule notifies once when it starts firing and once when it resolves; it
+/// is not repeated while it keeps firing. A rule that starts firing again
+/// within the cooldown of its last notification is announced once the
+/// cooldown has elapsed, if it is still firing by then.
+pub struct AlertManager {
+    endpoint: String,
+    cooldown: Duration,
+    notifiers: Vec<NotifierConfig>,
+    rules: Vec<RuleStatus>,
+    http: reqwest::Client,
+    last_error: Arc<Mutex<Option<String>>>,
+}
+
+impl AlertManager {
+    pub fn new(endpoint: &str, config: &AlertConfig) -> Self {
+        AlertManager {
+            endpoint: redact_url(endpoint),
+            cooldown: Duration::from_secs(config.cooldown_secs),
+            notifiers: config.notifiers.clone(),
+            rules: config
+                .rules
+                .iter()
+                .map(|rule| RuleStatus {
+                    rule: rule.clone(),
+                    value: None,
+                    firing: false,
+                    since: None,
+                    notified: false,
+                    last_notified: None,
+                })
+                .collect(),
+            http: reqwest::Client::builder()
+                .timeout(NOTIFY_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            last_error: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn rules(&self) -> &[RuleStatus] {
+        &self.rules
+    }
+
+    /// Takes the most recent notification delivery failure, if any.
+    pub fn take_error(&self) -> Option<String> {
+        self.last_error.lock().ok().and_then(|mut error| error.take())
+    }
+
+    /// Updates every rule from the latest samples and returns the events that
+    /// should be delivered. Rules whose metric has no value keep their state.
+    pub fn evaluate(&mut self, samples: &[ProbeSample]) -> Vec<AlertEvent> {
+        self.evaluate_at(samples, Instant::now())
+    }
+
+    fn evaluate_at(&mut self, samples: &[ProbeSample], now: Instant) -> Vec<AlertEvent> {
+        let mut events = Vec::new();
+
+        for status in &mut self.rules {
+            let value = samples
+                .iter()
+                .find(|sample| sample.name == status.rule.metric)
+                .and_then(|sample| sample.value);
+            let Some(value) = value else {
+                continue;
+            };
+            status.value = Some(value);
+
+            let breached = match status.rule.condition {
+                AlertCondition::Above => value > status.rule.threshold,
+                AlertCondition::Below => value < status.rule.threshold,
+            };
+
+            if breached && !status.firing {
+                status.firing = true;
+                status.since = Some(Local::now());
+                status.notified = false;
+            } else if !breached && status.firing {
+                status.firing = false;
+                status.since = None;
+                if status.notified {
+                    status.notified = false;
+                    events.push(alert_event(status, AlertState::Resolved, value, &self.endpoint));
+                }
+            }
+
+            let cooled_down = status.last_notified.is_none_or(|last| now.duration_since(last) >= self.cooldown);
+            if status.firing && !status.notified && cooled_down {
+                status.notified = true;
+                status.last_notified = Some(now);
+                events.push(alert_event(status, AlertState::Firing, value, &self.endpoint));
+            }
+        }
+
+        events
+    }
+
+    /// Sends each event to every configured notifier in the background.
+    /// Must be called from within a Tokio runtime.
+    pub fn notify(&self, events: &[AlertEvent]) {
+        for event in events {
+            for notifier in &self.notifiers {
+                let http = self.http.clone();
+                let notifier = notifier.clone();
+                let event = event.clone();
+                let last_error = self.last_error.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = deliver(&http, &notifier, &event).await {
+                        if let Ok(mut last_error) = last_error.lock() {
+                            *last_error = Some(format!("{} ({}): {}", event.rule, notifier_kind(&notifier), e));
+                        }
+                    }
+                });
+            }
+        }
+    }
+}
+
+fn alert_event(status: &RuleStatus, state: AlertState, value: f64, endpoint: &str) -> AlertEvent {
+    let rule = &status.rule;
+    let condition = match rule.condition {
+        AlertCondition::Above => "above",
+        AlertCondition::Below => "below",
+    };
+    let label = match state {
+        AlertState::Firing => "FIRING",
+        AlertState::Resolved => "RESOLVED",
+    };
+
+    AlertEvent {
+        rule: rule.name.clone(),
+        state,
+        severity: rule.severity,
+        metric: rule.metric.clone(),
+        value,
+        threshold: rule.threshold,
+        endpoint: endpoint.to_string(),
+        summary: format!(
+            "[{}] {}: {} is {:.2} (threshold {} {}) on {}",
+            label, rule.name, rule.metric, value, condition, rule.threshold, endpoint
+        ),
+        timestamp: Local::now().to_rfc3339(),
+    }
+}
+
+/// Names a notifier in errors without its URL, which may carry a secret.
+fn notifier_kind(notifier: &NotifierConfig) -> &'static str {
+    match notifier {
+        NotifierConfig::Webhook { .. } => "webhook",
+        NotifierConfig::Slack { .. } => "slack",
+        NotifierConfig::Discord { .. } => "discord",
+        NotifierConfig::PagerDuty { .. } => "pagerduty",
+        NotifierConfig::Command { .. } => "command",
+    }
+}
+
+async fn deliver(
+    http: &reqwest::Client,
+    notifier: &NotifierConfig,
+    event: &AlertEvent,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (url, body) = match notifier {
+        NotifierConfig::Webhook { url } => (url, serde_json::to_value(event)?),
+        NotifierConfig::Slack { url } => (url, json!({ "text": event.summary })),
+        NotifierConfig::Discord { url } => (url, json!({ "content": event.summary })),
+        NotifierConfig::PagerDuty { routing_key, url } => {
+            let action = match event.state {
+                AlertState::Firing => "trigger",
+                AlertState::Resolved => "resolve",
+            };
+            let body = json!({
+                "routing_key": routing_key,
+                "event_action": action,
+                "dedup_key": format!("solprobe-{}", event.rule),
+                "payload": {
+                    "summary": event.summary,
+                    "source": event.endpoint,
+                    "severity": event.severity,
+                    "timestamp": event.timestamp,
+                    "custom_details": event,
+                },
+            });
+            (url, body)
+        }
+        NotifierConfig::Command { command, args } => {
+            let status = tokio::process::Command::new(command)
+                .args(args)
+                .kill_on_drop(true)
+                .env("SOLPROBE_ALERT_RULE", &event.rule)
+                .env("SOLPROBE_ALERT_STATE", serde_json::to_value(event.state)?.as_str().unwrap_or_default())
+                .env("SOLPROBE_ALERT_SEVERITY", serde_json::to_value(event.severity)?.as_str().unwrap_or_default())
+                .env("SOLPROBE_ALERT_METRIC", &event.metric)
+                .env("SOLPROBE_ALERT_VALUE", event.value.to_string())
+                .env("SOLPROBE_ALERT_THRESHOLD", event.threshold.to_string())
+                .env("SOLPROBE_ALERT_SUMMARY", &event.summary)
+                .env("SOLPROBE_ALERT_JSON", serde_json::to_string(event)?)
+                .status();
+            let status = tokio::time::timeout(NOTIFY_TIMEOUT, status)
+                .await
+                .map_err(|_| format!("{} timed out after {}s", command, NOTIFY_TIMEOUT.as_secs()))??;
+            if !status.success() {
+                return Err(format!("{} exited with {}", command, status).into());
+            }
+            return Ok(());
+        }
+    };
+
+    // Webhook URLs carry their credentials, so keep them out of the error.
+    http.post(url)
+        .json(&body)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| e.without_url())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::config::AlertSeverity;
+
+    fn manager(cooldown_secs: u64) -> AlertManager {
+        let config = AlertConfig {
+            cooldown_secs,
+            rules: vec![AlertRule {
+                name: "high skip rate".to_string(),
+                metric: "skip_rate_percent".to_string(),
+                condition: AlertCondition::Above,
+                threshold: 10.0,
+                severity: AlertSeverity::Critical,
+            }],
+            notifiers: Vec::new(),
+        };
+        AlertManager::new("https://rpc.example.com/?api-key=SECRET", &config)
+    }
+
+    fn sample(value: f64) -> Vec<ProbeSample> {
+        vec![ProbeSample { name: "skip_rate_percent", help: "", value: Some(value) }]
+    }
+
+    fn states(events: &[AlertEvent]) -> Vec<AlertState> {
+        events.iter().map(|event| event.state).collect()
+    }
+
+    #[test]
+    fn fires_once_and_resolves() {
+        let mut alerts = manager(300);
+        let start = Instant::now();
+
+        let events = alerts.evaluate_at(&sample(20.0), start);
+        assert_eq!(states(&events), vec![AlertState::Firing]);
+        assert_eq!(events[0].endpoint, "https://rpc.example.com/?api-key=***");
+        assert!(alerts.rules()[0].firing);
+
+        assert!(alerts.evaluate_at(&sample(25.0), start + Duration::from_secs(5)).is_empty());
+
+        let events = alerts.evaluate_at(&sample(5.0), start + Duration::from_secs(10));
+        assert_eq!(states(&events), vec![AlertState::Resolved]);
+        assert!(!alerts.rules()[0].firing);
+    }
+
+    #[test]
+    fn missing_values_keep_the_state() {
+        let mut alerts = manager(300);
+        let start = Instant::now();
+        alerts.evaluate_at(&sample(20.0), start);
+
+        let missing = vec![ProbeSample { name: "skip_rate_percent", help: "", value: None }];
+        assert!(alerts.evaluate_at(&missing, start + Duration::from_secs(5)).is_empty());
+        assert!(alerts.rules()[0].firing);
+    }
+
+    #[test]
+    fn refiring_within_the_cooldown_is_silent() {
+        let mut alerts = manager(300);
+        let start = Instant::now();
+        alerts.evaluate_at(&sample(20.0), start);
+        alerts.evaluate_at(&sample(5.0), start + Duration::from_secs(10));
+
+        assert!(alerts.evaluate_at(&sample(20.0), start + Duration::from_secs(20)).is_empty());
+        assert!(alerts.rules()[0].firing);
+        // Never announced, so its resolution is not sent either.
+        assert!(alerts.evaluate_at(&sample(5.0), start + Duration::from_secs(30)).is_empty());
+    }
+
+    #[test]
+    fn outage_after_a_flap_is_announced_once_the_cooldown_elapses() {
+        let mut alerts = manager(300);
+        let start = Instant::now();
+        alerts.evaluate_at(&sample(20.0), start);
+        alerts.evaluate_at(&sample(5.0), start + Duration::from_secs(10));
+        assert!(alerts.evaluate_at(&sample(20.0), start + Duration::from_secs(20)).is_empty());
+        assert!(alerts.evaluate_at(&sample(20.0), start + Duration::from_secs(200)).is_empty());
+
+        let events = alerts.evaluate_at(&sample(20.0), start + Duration::from_secs(300));
+        assert_eq!(states(&events), vec![AlertState::Firing]);
+        assert!(alerts.evaluate_at(&sample(20.0), start + Duration::from_secs(400)).is_empty());
+
+        let events = alerts.evaluate_at(&sample(5.0), start + Duration::from_secs(500));
+        assert_eq!(states(&events), vec![AlertState::Resolved]);
+    }
+
+    #[test]
+    fn below_rules_fire_under_the_threshold() {
+        let mut alerts = manager(0);
+        alerts.rules[0].rule.condition = AlertCondition::Below;
+        let start = Instant::now();
+
+        assert!(alerts.evaluate_at(&sample(20.0), start).is_empty());
+        assert_eq!(states(&alerts.evaluate_at(&sample(5.0), start)), vec![AlertState::Firing]);
+        assert_eq!(states(&alerts.evaluate_at(&sample(20.0), start)), vec![AlertState::Resolved]);
+    }
+}
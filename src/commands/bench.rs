@@ -0,0 +1,483 @@
+use futures::future::join_all;
+use log::{info, warn};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::time::{interval, Duration, Instant, MissedTickBehavior};
+
+use crate::commands::export::{to_csv_or_header, write_output};
+use crate::commands::rpc::{http_client_builder, redact_url};
+use crate::commands::rpc_stats::format_counts;
+use crate::commands::OutputFormat;
+use crate::types::{nearest_rank, BenchResult};
+
+/// Account read by `getAccountInfo` unless `--account` is given.
+const DEFAULT_ACCOUNT: &str = "SysvarC1ock11111111111111111111111111111111";
+/// Program scanned by `getProgramAccounts` unless `--program` is given; it
+/// owns only a handful of accounts on every cluster.
+const DEFAULT_PROGRAM: &str = "Config1111111111111111111111111111111111111";
+/// How far behind the finalized slot to look for a block for `getBlock`.
+const BLOCK_LOOKBACK: u64 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BenchMethod {
+    Slot,
+    LatestBlockhash,
+    AccountInfo,
+    ProgramAccounts,
+    Block,
+}
+
+impl BenchMethod {
+    const ALL: [BenchMethod; 5] = [
+        BenchMethod::Slot,
+        BenchMethod::LatestBlockhash,
+        BenchMethod::AccountInfo,
+        BenchMethod::ProgramAccounts,
+        BenchMethod::Block,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BenchMethod::Slot => "getSlot",
+            BenchMethod::LatestBlockhash => "getLatestBlockhash",
+            BenchMethod::AccountInfo => "getAccountInfo",
+            BenchMethod::ProgramAccounts => "getProgramAccounts",
+            BenchMethod::Block => "getBlock",
+        }
+    }
+}
+
+/// Parses `method` or `method=weight`, e.g. `getSlot=5`.
+pub fn parse_method_weight(value: &str) -> Result<(BenchMethod, u32), String> {
+    let (name, weight) = match value.split_once('=') {
+        Some((name, weight)) => {
+            let weight = weight.parse().map_err(|_| format!("invalid weight '{}'", weight))?;
+            (name, weight)
+        }
+        None => (value, 1),
+    };
+    let method = BenchMethod::ALL
+        .into_iter()
+        .find(|method| method.name().eq_ignore_ascii_case(name))
+        .ok_or_else(|| {
+            let names: Vec<&str> = BenchMethod::ALL.iter().map(|method| method.name()).collect();
+            format!("unknown method '{}' (expected one of {})", name, names.join(", "))
+        })?;
+    if weight == 0 {
+        return Err(format!("weight of {} must be at least 1", method.name()));
+    }
+    Ok((method, weight))
+}
+
+/// Mix used when no `--method` is given. `getProgramAccounts` is left out
+/// because many providers disable or heavily rate limit it.
+fn default_mix() -> Vec<(BenchMethod, u32)> {
+    vec![
+        (BenchMethod::Slot, 4),
+        (BenchMethod::LatestBlockhash, 3),
+        (BenchMethod::AccountInfo, 2),
+        (BenchMethod::Block, 1),
+    ]
+}
+
+/// Folds repeated methods into one entry with the summed weight, keeping the
+/// order in which each method first appears, so `getSlot getSlot=2` is
+/// benchmarked and reported as `getSlot=3`.
+fn merge_methods(methods: &[(BenchMethod, u32)]) -> Vec<(BenchMethod, u32)> {
+    let mut mix: Vec<(BenchMethod, u32)> = Vec::with_capacity(methods.len());
+    for (method, weight) in methods {
+        match mix.iter_mut().find(|(merged, _)| merged == method) {
+            Some((_, total)) => *total = total.saturating_add(*weight),
+            None => mix.push((*method, *weight)),
+        }
+    }
+    mix
+}
+
+pub struct BenchOptions {
+    /// Methods and their relative weights; the default mix when empty.
+    pub methods: Vec<(BenchMethod, u32)>,
+    pub concurrency: usize,
+    /// Target requests per second per endpoint; unlimited when `None`.
+    pub rate: Option<f64>,
+    pub duration: Duration,
+    pub timeout: Duration,
+    pub account: Option<String>,
+    pub program: Option<String>,
+}
+
+#[derive(Default)]
+struct MethodStats {
+    requests: u64,
+    /// Latencies of successful requests.
+    latencies_ms: Vec<f64>,
+    errors: BTreeMap<String, u64>,
+}
+
+/// Spreads the weighted methods evenly over one cycle (smooth weighted
+/// round-robin), so a 3:1 mix issues `a a b a` rather than `a a a b`.
+fn schedule(mix: &[(BenchMethod, u32)]) -> Vec<BenchMethod> {
+    let total: i64 = mix.iter().map(|(_, weight)| *weight as i64).sum();
+    let mut current = vec![0i64; mix.len()];
+    let mut order = Vec::with_capacity(total as usize);
+    for _ in 0..total {
+        for (index, (_, weight)) in mix.iter().enumerate() {
+            current[index] += *weight as i64;
+        }
+        let index = (0..current.len()).fold(0, |best, index| if current[index] > current[best] { index } else { best });
+        current[index] -= total;
+        order.push(mix[index].0);
+    }
+    order
+}
+
+/// Sends one JSON-RPC request and returns its result. Errors are reported as
+/// their type so they can be counted: `timeout`, `connect`, `http_<status>`,
+/// `rpc_<code>`, `decode` or `http`.
+async fn send(http: &reqwest::Client, url: &str, method: &str, params: &Value) -> Result<Value, String> {
+    let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+    let response = http.post(url).json(&body).send().await.map_err(|e| request_error_type(&e))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("http_{}", status.as_u16()));
+    }
+    let mut body: Value = response.json().await.map_err(|e| request_error_type(&e))?;
+    if let Some(error) = body.get("error") {
+        return Err(format!("rpc_{}", error.get("code").and_then(Value::as_i64).unwrap_or_default()));
+    }
+    Ok(body["result"].take())
+}
+
+fn request_error_type(error: &reqwest::Error) -> String {
+    if error.is_timeout() {
+        "timeout"
+    } else if error.is_connect() {
+        "connect"
+    } else if error.is_decode() {
+        "decode"
+    } else {
+        "http"
+    }
+    .to_string()
+}
+
+/// A recent confirmed block, so `getBlock` is not measured against skipped
+/// slots. Falls back to the finalized slot when the lookup fails.
+async fn recent_block_slot(http: &reqwest::Client, url: &str) -> Option<u64> {
+    let finalized = send(http, url, "getSlot", &json!([{ "commitment": "finalized" }])).await.ok()?.as_u64()?;
+    let start = finalized.saturating_sub(BLOCK_LOOKBACK);
+    let blocks = send(http, url, "getBlocksWithLimit", &json!([start, 1])).await.ok();
+    blocks
+        .and_then(|blocks| blocks.get(0).and_then(Value::as_u64))
+        .or(Some(finalized))
+}
+
+fn params(method: BenchMethod, options: &BenchOptions, block_slot: u64) -> Value {
+    let account = options.account.as_deref().unwrap_or(DEFAULT_ACCOUNT);
+    let program = options.program.as_deref().unwrap_or(DEFAULT_PROGRAM);
+    match method {
+        BenchMethod::Slot => json!([]),
+        BenchMethod::LatestBlockhash => json!([{ "commitment": "confirmed" }]),
+        BenchMethod::AccountInfo => json!([account, { "encoding": "base64" }]),
+        BenchMethod::ProgramAccounts => json!([program, { "encoding": "base64" }]),
+        BenchMethod::Block => json!([block_slot, {
+            "encoding": "json",
+            "transactionDetails": "signatures",
+            "rewards": false,
+            "maxSupportedTransactionVersion": 0,
+        }]),
+    }
+}
+
+/// Issues the method mix against one endpoint from `concurrency` workers for
+/// the configured duration, optionally paced to a shared request rate.
+pub async fn bench_endpoint(url: &str, options: &BenchOptions) -> Result<Vec<BenchResult>, Box<dyn Error>> {
+    let (request_url, http) = http_client_builder(url, options.timeout)?;
+    let http = http.pool_max_idle_per_host(options.concurrency).build()?;
+
+    let mut mix = if options.methods.is_empty() {
+        default_mix()
+    } else {
+        merge_methods(&options.methods)
+    };
+    let mut block_slot = 0;
+    if mix.iter().any(|(method, _)| *method == BenchMethod::Block) {
+        match recent_block_slot(&http, &request_url).await {
+            Some(slot) => block_slot = slot,
+            None => {
+                warn!("Skipping getBlock on {}: could not find a recent block", redact_url(url));
+                mix.retain(|(method, _)| *method != BenchMethod::Block);
+            }
+        }
+    }
+    if mix.is_empty() {
+        return Err(format!("No methods left to benchmark on {}", redact_url(url)).into());
+    }
+    let calls: Arc<Vec<(BenchMethod, Value)>> = Arc::new(
+        schedule(&mix)
+            .into_iter()
+            .map(|method| (method, params(method, options, block_slot)))
+            .collect(),
+    );
+
+    let pacer = options.rate.filter(|rate| *rate > 0.0).map(|rate| {
+        let mut pacer = interval(Duration::from_secs_f64(1.0 / rate));
+        pacer.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        Arc::new(Mutex::new(pacer))
+    });
+    let next = Arc::new(AtomicUsize::new(0));
+    let started = Instant::now();
+    let deadline = started + options.duration;
+
+    let workers = (0..options.concurrency.max(1)).map(|_| {
+        let http = http.clone();
+        let url = request_url.clone();
+        let calls = calls.clone();
+        let pacer = pacer.clone();
+        let next = next.clone();
+        tokio::spawn(async move {
+            let mut stats: BTreeMap<BenchMethod, MethodStats> = BTreeMap::new();
+            loop {
+                if let Some(pacer) = &pacer {
+                    pacer.lock().await.tick().await;
+                }
+                if Instant::now() >= deadline {
+                    break;
+                }
+                let (method, params) = &calls[next.fetch_add(1, Ordering::Relaxed) % calls.len()];
+                let start = Instant::now();
+                let outcome = send(&http, &url, method.name(), params).await;
+                let entry = stats.entry(*method).or_default();
+                entry.requests += 1;
+                match outcome {
+                    Ok(_) => entry.latencies_ms.push(start.elapsed().as_secs_f64() * 1000.0),
+                    Err(error_type) => *entry.errors.entry(error_type).or_default() += 1,
+                }
+            }
+            stats
+        })
+    });
+
+    let mut totals: BTreeMap<BenchMethod, MethodStats> = BTreeMap::new();
+    for stats in join_all(workers).await {
+        for (method, stats) in stats? {
+            let total = totals.entry(method).or_default();
+            total.requests += stats.requests;
+            total.latencies_ms.extend(stats.latencies_ms);
+            for (error_type, count) in stats.errors {
+                *total.errors.entry(error_type).or_default() += count;
+            }
+        }
+    }
+    let elapsed = started.elapsed().as_secs_f64();
+
+    Ok(mix
+        .iter()
+        .map(|(method, _)| {
+            let stats = totals.remove(method).unwrap_or_default();
+            bench_result(&redact_url(url), *method, stats, elapsed)
+        })
+        .collect())
+}
+
+fn bench_result(endpoint: &str, method: BenchMethod, mut stats: MethodStats, elapsed: f64) -> BenchResult {
+    stats.latencies_ms.sort_unstable_by(f64::total_cmp);
+    let samples = &stats.latencies_ms;
+    let rank = |percentile| nearest_rank(samples, percentile).unwrap_or_default();
+    let errors: u64 = stats.errors.values().sum();
+
+    BenchResult {
+        endpoint: endpoint.to_string(),
+        method: method.name().to_string(),
+        requests: stats.requests,
+        errors,
+        error_rate: if stats.requests == 0 { 0.0 } else { errors as f64 / stats.requests as f64 * 100.0 },
+        throughput: if elapsed > 0.0 { samples.len() as f64 / elapsed } else { 0.0 },
+        mean_ms: if samples.is_empty() { 0.0 } else { samples.iter().sum::<f64>() / samples.len() as f64 },
+        p50_ms: rank(50.0),
+        p90_ms: rank(90.0),
+        p99_ms: rank(99.0),
+        max_ms: samples.last().copied().unwrap_or_default(),
+        errors_by_type: stats.errors,
+    }
+}
+
+/// [`BenchResult`] flattened into CSV cells, with the error counters written
+/// as a `type xcount` list.
+#[derive(Debug, Default, Serialize)]
+struct BenchRow {
+    endpoint: String,
+    method: String,
+    requests: u64,
+    errors: u64,
+    error_rate: f64,
+    throughput: f64,
+    mean_ms: f64,
+    p50_ms: f64,
+    p90_ms: f64,
+    p99_ms: f64,
+    max_ms: f64,
+    errors_by_type: String,
+}
+
+fn csv_rows(results: &[BenchResult]) -> Vec<BenchRow> {
+    results
+        .iter()
+        .map(|result| BenchRow {
+            endpoint: result.endpoint.clone(),
+            method: result.method.clone(),
+            requests: result.requests,
+            errors: result.errors,
+            error_rate: result.error_rate,
+            throughput: result.throughput,
+            mean_ms: result.mean_ms,
+            p50_ms: result.p50_ms,
+            p90_ms: result.p90_ms,
+            p99_ms: result.p99_ms,
+            max_ms: result.max_ms,
+            errors_by_type: format_counts(&result.errors_by_type),
+        })
+        .collect()
+}
+
+/// Benchmarks each endpoint in turn, so they do not compete for local
+/// bandwidth, and prints the per-method results.
+pub async fn run_bench(
+    urls: &[String],
+    options: &BenchOptions,
+    format: OutputFormat,
+    output: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    let mut results = Vec::new();
+    for url in urls {
+        info!("Benchmarking {} for {:?}", redact_url(url), options.duration);
+        results.extend(bench_endpoint(url, options).await?);
+    }
+
+    let rendered = match format {
+        OutputFormat::Table => format_table(&results, options),
+        OutputFormat::Csv => to_csv_or_header(&csv_rows(&results))?,
+        OutputFormat::Json => serde_json::to_string_pretty(&results)?,
+    };
+
+    write_output(&rendered, output)?;
+
+    Ok(())
+}
+
+fn format_table(results: &[BenchResult], options: &BenchOptions) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "RPC Benchmark: {:.0}s, {} workers, {}\n",
+        options.duration.as_secs_f64(),
+        options.concurrency,
+        match options.rate {
+            Some(rate) => format!("{} req/s target", rate),
+            None => "unpaced".to_string(),
+        }
+    ));
+
+    let mut endpoint = None;
+    for result in results {
+        if endpoint != Some(&result.endpoint) {
+            endpoint = Some(&result.endpoint);
+            out.push_str(&format!("\nEndpoint: {}\n", result.endpoint));
+            out.push_str(&format!(
+                "{:<20} {:>9} {:>7} {:>9} {:>9} {:>9} {:>9} {:>9} {:>9}\n",
+                "Method", "Requests", "Err %", "Req/s", "Mean ms", "p50 ms", "p90 ms", "p99 ms", "Max ms"
+            ));
+        }
+        out.push_str(&format!(
+            "{:<20} {:>9} {:>6.2}% {:>9.1} {:>9.1} {:>9.1} {:>9.1} {:>9.1} {:>9.1}\n",
+            result.method,
+            result.requests,
+            result.error_rate,
+            result.throughput,
+            result.mean_ms,
+            result.p50_ms,
+            result.p90_ms,
+            result.p99_ms,
+            result.max_ms
+        ));
+        if !result.errors_by_type.is_empty() {
+            out.push_str(&format!("  errors: {}\n", format_counts(&result.errors_by_type)));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_method_weight_accepts_names_and_weights() {
+        assert_eq!(parse_method_weight("getSlot"), Ok((BenchMethod::Slot, 1)));
+        assert_eq!(parse_method_weight("getblock=3"), Ok((BenchMethod::Block, 3)));
+        assert!(parse_method_weight("getSlot=0").is_err());
+        assert!(parse_method_weight("getSlot=x").is_err());
+        assert!(parse_method_weight("getBalance").is_err());
+    }
+
+    #[test]
+    fn merge_methods_sums_duplicate_weights() {
+        let merged = merge_methods(&[
+            (BenchMethod::Slot, 1),
+            (BenchMethod::Block, 1),
+            (BenchMethod::Slot, 2),
+        ]);
+        assert_eq!(merged, vec![(BenchMethod::Slot, 3), (BenchMethod::Block, 1)]);
+    }
+
+    #[test]
+    fn schedule_interleaves_methods_by_weight() {
+        let order = schedule(&[(BenchMethod::Slot, 3), (BenchMethod::Block, 1)]);
+        assert_eq!(
+            order,
+            vec![BenchMethod::Slot, BenchMethod::Slot, BenchMethod::Block, BenchMethod::Slot]
+        );
+
+        let order = schedule(&default_mix());
+        assert_eq!(order.len(), 10);
+        for (method, weight) in default_mix() {
+            assert_eq!(order.iter().filter(|scheduled| **scheduled == method).count(), weight as usize);
+        }
+    }
+
+    #[test]
+    fn bench_result_ranks_percentiles() {
+        let stats = MethodStats {
+            requests: 110,
+            latencies_ms: (1..=100).rev().map(f64::from).collect(),
+            errors: BTreeMap::from([("timeout".to_string(), 10)]),
+        };
+        let result = bench_result("http://localhost", BenchMethod::Slot, stats, 10.0);
+
+        assert_eq!(result.method, "getSlot");
+        assert_eq!(result.errors, 10);
+        assert!((result.error_rate - 1000.0 / 110.0).abs() < 1e-9);
+        assert_eq!(result.throughput, 10.0);
+        assert_eq!(result.mean_ms, 50.5);
+        assert_eq!((result.p50_ms, result.p90_ms, result.p99_ms, result.max_ms), (50.0, 90.0, 99.0, 100.0));
+    }
+
+    #[test]
+    fn bench_result_without_successes_reports_zeros() {
+        let stats = MethodStats {
+            requests: 3,
+            latencies_ms: Vec::new(),
+            errors: BTreeMap::from([("connect".to_string(), 3)]),
+        };
+        let result = bench_result("http://localhost", BenchMethod::Block, stats, 0.0);
+
+        assert_eq!(result.error_rate, 100.0);
+        assert_eq!(result.throughput, 0.0);
+        assert_eq!((result.mean_ms, result.p50_ms, result.max_ms), (0.0, 0.0, 0.0));
+    }
+}
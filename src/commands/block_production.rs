@@ -0,0 +1,289 @@
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcBlockProductionConfig, RpcBlockProductionConfigRange};
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+
+use crate::commands::export::{to_csv_or_header, write_output};
+use crate::commands::leader_schedule::EpochLeaderSchedule;
+use crate::commands::rpc;
+use crate::commands::OutputFormat;
+use crate::types::{BlockProductionReport, SkipRateSort, ValidatorSkipRate};
+
+/// Builds a skip rate report from `getBlockProduction`. Without a range the
+/// node reports on the current epoch so far, and the epoch's leader schedule is
+/// used to fill in how many slots each validator is scheduled for in total.
+/// `schedule` is used instead of `getLeaderSchedule` when it covers that epoch.
+pub fn fetch_block_production(
+    client: &RpcClient,
+    range: Option<(u64, Option<u64>)>,
+    schedule: Option<&EpochLeaderSchedule>,
+) -> Result<BlockProductionReport, Box<dyn Error>> {
+    let config = RpcBlockProductionConfig {
+        range: range.map(|(first_slot, last_slot)| RpcBlockProductionConfigRange { first_slot, last_slot }),
+        ..RpcBlockProductionConfig::default()
+    };
+    let production = client.get_block_production_with_config(config)?.value;
+
+    let cached = schedule.filter(|schedule| schedule.first_slot == production.range.first_slot);
+    let scheduled: HashMap<String, u64> = if range.is_some() {
+        HashMap::new()
+    } else if let Some(schedule) = cached {
+        schedule.slot_counts()
+    } else {
+        client
+            .get_leader_schedule(Some(production.range.first_slot))?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(identity, slots)| (identity, slots.len() as u64))
+            .collect()
+    };
+
+    let mut report = BlockProductionReport {
+        first_slot: production.range.first_slot,
+        last_slot: production.range.last_slot,
+        ..BlockProductionReport::default()
+    };
+
+    for (identity, (leader_slots, blocks_produced)) in production.by_identity {
+        let leader_slots = leader_slots as u64;
+        let blocks_produced = blocks_produced as u64;
+        let skipped_slots = leader_slots.saturating_sub(blocks_produced);
+
+        report.leader_slots += leader_slots;
+        report.blocks_produced += blocks_produced;
+        report.validators.push(ValidatorSkipRate {
+            scheduled_slots: scheduled.get(&identity).copied(),
+            identity,
+            leader_slots,
+            blocks_produced,
+            skipped_slots,
+            skip_rate: skip_rate(leader_slots, skipped_slots),
+        });
+    }
+
+    report.skipped_slots = report.leader_slots.saturating_sub(report.blocks_produced);
+    report.skip_rate = skip_rate(report.leader_slots, report.skipped_slots);
+    report.sort_by(SkipRateSort::default());
+
+    Ok(report)
+}
+
+pub fn get_block_production(
+    url: &str,
+    range: Option<(u64, Option<u64>)>,
+) -> Result<BlockProductionReport, Box<dyn Error>> {
+    let client = rpc::client(url)?;
+    fetch_block_production(&client, range, None)
+}
+
+pub fn run_block_production(
+    url: &str,
+    range: Option<(u64, Option<u64>)>,
+    sort: SkipRateSort,
+    format: OutputFormat,
+    output: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    let mut report = get_block_production(url, range)?;
+    report.sort_by(sort);
+
+    let rendered = match format {
+        OutputFormat::Table => format_table(&report),
+        OutputFormat::Csv => to_csv_or_header(&report.validators)?,
+        OutputFormat::Json => serde_json::to_string_pretty(&report)?,
+    };
+
+    write_output(&rendered, output)?;
+
+    Ok(())
+}
+
+fn skip_rate(leader_slots: u64, skipped_slots: u64) -> f64 {
+    if leader_slots == 0 {
+        0.0
+    } else {
+        skipped_slots as f64 / leader_slots as f64 * 100.0
+    }
+}
+
+fn format_table(report: &BlockProductionReport) -> String {
+    let mut out = String::new();
+    out.push_str("Block Production:\n");
+    out.push_str(&format!("Slot Range: {} - {}\n", report.first_slot, report.last_slot));
+    out.push_str(&format!("Leader Slots: {}\n", report.leader_slots));
+    out.push_str(&format!("Blocks Produced: {}\n", report.blocks_produced));
+    out.push_str(&format!("Skipped Slots: {}\n", report.skipped_slots));
+    out.push_str(&format!("Cluster Skip Rate: {:.2}%\n\n", report.skip_rate));
+    out.push_str(&format!(
+        "{:<44} {:>12} {:>12} {:>10} {:>10}\n",
+        "Identity", "Leader Slots", "Produced", "Skipped", "Skip Rate"
+    ));
+    for validator in &report.validators {
+        out.push_str(&format!(
+            "{:<44} {:>12} {:>12} {:>10} {:>9.2}%\n",
+            validator.identity,
+            validator.leader_slots,
+            validator.blocks_produced,
+            validator.skipped_slots,
+            validator.skip_rate
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use serde_json::{json, Value};
+    use solana_client::client_error::{ClientErrorKind, Result as ClientResult};
+    use solana_client::rpc_client::RpcClientConfig;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_client::rpc_sender::{RpcSender, RpcTransportStats};
+    use solana_sdk::commitment_config::CommitmentConfig;
+    use solana_sdk::epoch_info::EpochInfo;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use crate::commands::leader_schedule::fetch_leader_schedule;
+
+    const FIRST_SLOT: u64 = 1_000;
+
+    /// Fake endpoint for an epoch starting at `FIRST_SLOT`, counting
+    /// `getLeaderSchedule` calls.
+    struct ProductionSender {
+        schedule_calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl RpcSender for ProductionSender {
+        async fn send(&self, request: RpcRequest, _params: Value) -> ClientResult<Value> {
+            match request {
+                RpcRequest::GetBlockProduction => Ok(json!({
+                    "context": { "slot": FIRST_SLOT + 9 },
+                    "value": {
+                        "byIdentity": {
+                            "alpha": [4, 3],
+                            "beta": [4, 4],
+                            "gamma": [2, 0],
+                        },
+                        "range": { "firstSlot": FIRST_SLOT, "lastSlot": FIRST_SLOT + 9 },
+                    },
+                })),
+                RpcRequest::GetLeaderSchedule => {
+                    self.schedule_calls.fetch_add(1, Ordering::SeqCst);
+                    Ok(json!({
+                        "alpha": [0, 1, 2, 3, 12, 13, 14, 15],
+                        "beta": [4, 5, 6, 7],
+                        "gamma": [8, 9, 10, 11],
+                    }))
+                },
+                _ => Err(ClientErrorKind::Custom("unsupported".to_string()).into()),
+            }
+        }
+
+        fn get_transport_stats(&self) -> RpcTransportStats {
+            RpcTransportStats::default()
+        }
+
+        fn url(&self) -> String {
+            "production".to_string()
+        }
+    }
+
+    fn client() -> (RpcClient, Arc<AtomicUsize>) {
+        let schedule_calls = Arc::new(AtomicUsize::new(0));
+        let sender = ProductionSender { schedule_calls: schedule_calls.clone() };
+        let config = RpcClientConfig::with_commitment(CommitmentConfig::confirmed());
+        (RpcClient::new_sender(sender, config), schedule_calls)
+    }
+
+    fn epoch_info(epoch: u64, first_slot: u64) -> EpochInfo {
+        EpochInfo {
+            epoch,
+            slot_index: 9,
+            slots_in_epoch: 16,
+            absolute_slot: first_slot + 9,
+            block_height: 0,
+            transaction_count: None,
+        }
+    }
+
+    fn validator<'a>(report: &'a BlockProductionReport, identity: &str) -> &'a ValidatorSkipRate {
+        report.validators.iter().find(|v| v.identity == identity).unwrap()
+    }
+
+    #[test]
+    fn skip_rate_is_a_percentage_of_leader_slots() {
+        assert_eq!(skip_rate(0, 0), 0.0);
+        assert_eq!(skip_rate(4, 1), 25.0);
+        assert_eq!(skip_rate(2, 2), 100.0);
+    }
+
+    #[test]
+    fn fetch_block_production_computes_validator_and_cluster_skip_rates() {
+        let (client, schedule_calls) = client();
+        let report = fetch_block_production(&client, None, None).unwrap();
+
+        assert_eq!((report.first_slot, report.last_slot), (FIRST_SLOT, FIRST_SLOT + 9));
+        assert_eq!(report.leader_slots, 10);
+        assert_eq!(report.blocks_produced, 7);
+        assert_eq!(report.skipped_slots, 3);
+        assert_eq!(report.skip_rate, 30.0);
+
+        let alpha = validator(&report, "alpha");
+        assert_eq!(alpha.skipped_slots, 1);
+        assert_eq!(alpha.skip_rate, 25.0);
+        assert_eq!(alpha.scheduled_slots, Some(8));
+        assert_eq!(validator(&report, "gamma").skip_rate, 100.0);
+        assert_eq!(schedule_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn fetch_block_production_with_range_skips_the_leader_schedule() {
+        let (client, schedule_calls) = client();
+        let report = fetch_block_production(&client, Some((FIRST_SLOT, None)), None).unwrap();
+
+        assert!(report.validators.iter().all(|v| v.scheduled_slots.is_none()));
+        assert_eq!(schedule_calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn fetch_block_production_reuses_a_schedule_for_the_same_epoch() {
+        let (client, schedule_calls) = client();
+        let current = fetch_leader_schedule(&client, &epoch_info(5, FIRST_SLOT)).unwrap();
+        let previous = fetch_leader_schedule(&client, &epoch_info(4, FIRST_SLOT - 16)).unwrap();
+        schedule_calls.store(0, Ordering::SeqCst);
+
+        let report = fetch_block_production(&client, None, Some(&current)).unwrap();
+        assert_eq!(validator(&report, "alpha").scheduled_slots, Some(8));
+        assert_eq!(validator(&report, "beta").scheduled_slots, Some(4));
+        assert_eq!(schedule_calls.load(Ordering::SeqCst), 0);
+
+        fetch_block_production(&client, None, Some(&previous)).unwrap();
+        assert_eq!(schedule_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn sort_by_orders_validators() {
+        let (client, _) = client();
+        let mut report = fetch_block_production(&client, Some((FIRST_SLOT, None)), None).unwrap();
+        let identities = |report: &BlockProductionReport| {
+            report.validators.iter().map(|v| v.identity.clone()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(identities(&report), ["gamma", "alpha", "beta"]);
+
+        report.sort_by(SkipRateSort::BlocksProduced);
+        assert_eq!(identities(&report), ["beta", "alpha", "gamma"]);
+
+        report.sort_by(SkipRateSort::Identity);
+        assert_eq!(identities(&report), ["alpha", "beta", "gamma"]);
+
+        report.sort_by(SkipRateSort::LeaderSlots);
+        assert_eq!(&identities(&report)[2..], ["gamma"]);
+
+        report.sort_by(SkipRateSort::SkipRate);
+        assert_eq!(identities(&report), ["gamma", "alpha", "beta"]);
+    }
+}
@@ -0,0 +1,42 @@
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcBlockConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::transaction::VersionedTransaction;
+use solana_sdk_ids::vote;
+use solana_transaction_status_client_types::{TransactionDetails, UiConfirmedBlock, UiTransactionEncoding};
+use std::error::Error;
+
+/// Fetches up to `count` of the most recent confirmed blocks with full,
+/// base64-encoded transactions. Blocks that fail to load are skipped.
+pub fn sample_recent_blocks(client: &RpcClient, count: usize) -> Result<Vec<(u64, UiConfirmedBlock)>, Box<dyn Error>> {
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+
+    // Look back twice as far as needed so skipped slots don't shrink the sample.
+    let latest = client.get_slot()?;
+    let slots = client.get_blocks_with_limit(latest.saturating_sub(count as u64 * 2), count * 2)?;
+    let config = RpcBlockConfig {
+        encoding: Some(UiTransactionEncoding::Base64),
+        transaction_details: Some(TransactionDetails::Full),
+        rewards: Some(false),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    };
+
+    Ok(slots
+        .into_iter()
+        .rev()
+        .take(count)
+        .filter_map(|slot| client.get_block_with_config(slot, config).ok().map(|block| (slot, block)))
+        .collect())
+}
+
+pub fn is_vote_transaction(transaction: &VersionedTransaction) -> bool {
+    let keys = transaction.message.static_account_keys();
+    transaction
+        .message
+        .instructions()
+        .iter()
+        .any(|ix| keys.get(ix.program_id_index as usize) == Some(&vote::id()))
+}
@@ -0,0 +1,226 @@
+use solana_client::rpc_client::RpcClient;
+use solana_transaction_status_client_types::{UiConfirmedBlock, UiLoadedAddresses};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::error::Error;
+
+use crate::commands::blocks::{is_vote_transaction, sample_recent_blocks};
+use crate::types::{AccountHotspot, BlockUtilization, CongestionReport};
+
+/// Maximum compute units per block (60M since SIMD-0256).
+pub const BLOCK_CU_LIMIT: u64 = 60_000_000;
+
+/// Blocks above this share of the CU limit are counted as full.
+const FULL_BLOCK_UTILIZATION: f64 = 90.0;
+
+const MAX_HOTSPOTS: usize = 10;
+
+/// Measures how close sampled blocks came to the compute limit, which
+/// writable accounts consumed the most compute, and how many non-vote
+/// transactions failed, then folds that into a single congestion score.
+/// `None` without blocks, since an empty sample says nothing about load.
+pub fn analyze_blocks(blocks: &[(u64, UiConfirmedBlock)]) -> Option<CongestionReport> {
+    if blocks.is_empty() {
+        return None;
+    }
+    let mut report = CongestionReport {
+        block_cu_limit: BLOCK_CU_LIMIT,
+        ..CongestionReport::default()
+    };
+    let mut hotspots: HashMap<String, AccountHotspot> = HashMap::new();
+    let mut non_vote_transactions = 0u64;
+    let mut failed_transactions = 0u64;
+
+    for (slot, block) in blocks {
+        let mut utilization = BlockUtilization {
+            slot: *slot,
+            ..BlockUtilization::default()
+        };
+
+        for encoded in block.transactions.iter().flatten() {
+            let (Some(transaction), Some(meta)) = (encoded.transaction.decode(), &encoded.meta) else {
+                continue;
+            };
+            let compute_units: Option<u64> = meta.compute_units_consumed.clone().into();
+            let compute_units = compute_units.unwrap_or(0);
+            utilization.compute_units += compute_units;
+            utilization.transactions += 1;
+
+            if is_vote_transaction(&transaction) {
+                continue;
+            }
+            non_vote_transactions += 1;
+            if meta.err.is_some() {
+                utilization.failed_transactions += 1;
+                failed_transactions += 1;
+            }
+
+            // Signers are skipped: fee payers are writable but rarely contended.
+            let message = &transaction.message;
+            let signers = message.header().num_required_signatures as usize;
+            let static_writable = message
+                .static_account_keys()
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| *index >= signers && message.is_maybe_writable(*index, None))
+                .map(|(_, key)| key.to_string());
+            let loaded: Option<UiLoadedAddresses> = meta.loaded_addresses.clone().into();
+            let loaded_writable = loaded.map(|loaded| loaded.writable).unwrap_or_default();
+
+            for account in static_writable.chain(loaded_writable) {
+                let hotspot = hotspots.entry(account.clone()).or_insert_with(|| AccountHotspot {
+                    account,
+                    ..AccountHotspot::default()
+                });
+                hotspot.compute_units += compute_units;
+                hotspot.transactions += 1;
+            }
+        }
+
+        utilization.utilization = utilization.compute_units as f64 / BLOCK_CU_LIMIT as f64 * 100.0;
+        report.blocks.push(utilization);
+    }
+
+    report.avg_utilization =
+        report.blocks.iter().map(|block| block.utilization).sum::<f64>() / report.blocks.len() as f64;
+    report.max_utilization = report.blocks.iter().map(|block| block.utilization).fold(0.0, f64::max);
+    if non_vote_transactions > 0 {
+        report.failed_tx_ratio = failed_transactions as f64 / non_vote_transactions as f64;
+    }
+
+    let mut hotspots: Vec<AccountHotspot> = hotspots.into_values().collect();
+    hotspots.sort_by_key(|hotspot| Reverse(hotspot.compute_units));
+    hotspots.truncate(MAX_HOTSPOTS);
+    report.hotspots = hotspots;
+
+    let score = report.avg_utilization * 0.7 + report.failed_tx_ratio * 100.0 * 0.3;
+    report.score = score.round().clamp(0.0, 100.0) as u8;
+    report.evidence = evidence(&report);
+
+    Some(report)
+}
+
+fn evidence(report: &CongestionReport) -> Vec<String> {
+    let mut evidence = vec![format!(
+        "Average block used {:.1}% of the {}M CU limit (max {:.1}%)",
+        report.avg_utilization,
+        report.block_cu_limit / 1_000_000,
+        report.max_utilization
+    )];
+
+    let full_blocks = report
+        .blocks
+        .iter()
+        .filter(|block| block.utilization >= FULL_BLOCK_UTILIZATION)
+        .count();
+    if full_blocks > 0 {
+        evidence.push(format!(
+            "{} of {} sampled blocks were above {:.0}% of the CU limit",
+            full_blocks,
+            report.blocks.len(),
+            FULL_BLOCK_UTILIZATION
+        ));
+    }
+
+    evidence.push(format!("{:.1}% of non-vote transactions failed", report.failed_tx_ratio * 100.0));
+
+    if let Some(hottest) = report.hotspots.first() {
+        evidence.push(format!(
+            "Hottest writable account {} was locked by {} transactions consuming {} CU",
+            hottest.account, hottest.transactions, hottest.compute_units
+        ));
+    }
+
+    evidence
+}
+
+pub fn fetch_congestion(client: &RpcClient, sample_blocks: usize) -> Result<CongestionReport, Box<dyn Error>> {
+    let blocks = sample_recent_blocks(client, sample_blocks)?;
+    analyze_blocks(&blocks).ok_or_else(|| "No blocks could be sampled".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::prelude::{Engine, BASE64_STANDARD};
+    use serde_json::{json, Value};
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+    use solana_sdk::message::Message;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk_ids::vote;
+
+    /// A base64 wire transaction calling `program` with one writable account,
+    /// plus its status meta.
+    fn transaction(program: Pubkey, writable: Pubkey, compute_units: u64, failed: bool) -> Value {
+        let payer = Pubkey::new_unique();
+        let instruction = Instruction::new_with_bytes(
+            program,
+            &[],
+            vec![AccountMeta::new(payer, true), AccountMeta::new(writable, false)],
+        );
+        // One zeroed signature followed by the legacy message.
+        let mut wire = vec![1u8];
+        wire.extend([0u8; 64]);
+        wire.extend(Message::new(&[instruction], Some(&payer)).serialize());
+
+        let error = json!({ "InstructionError": [0, { "Custom": 1 }] });
+        json!({
+            "transaction": [BASE64_STANDARD.encode(wire), "base64"],
+            "meta": {
+                "err": if failed { error.clone() } else { Value::Null },
+                "status": if failed { json!({ "Err": error }) } else { json!({ "Ok": null }) },
+                "fee": 5000,
+                "preBalances": [],
+                "postBalances": [],
+                "computeUnitsConsumed": compute_units,
+            },
+        })
+    }
+
+    fn block(slot: u64, transactions: Vec<Value>) -> (u64, UiConfirmedBlock) {
+        let block = json!({
+            "previousBlockhash": "11111111111111111111111111111111",
+            "blockhash": "11111111111111111111111111111111",
+            "parentSlot": slot - 1,
+            "transactions": transactions,
+            "blockTime": null,
+            "blockHeight": null,
+        });
+        (slot, serde_json::from_value(block).unwrap())
+    }
+
+    #[test]
+    fn analyze_blocks_without_blocks_is_unknown() {
+        assert!(analyze_blocks(&[]).is_none());
+    }
+
+    #[test]
+    fn analyze_blocks_scores_utilization_and_failures() {
+        let program = Pubkey::new_unique();
+        let hot = Pubkey::new_unique();
+        let blocks = vec![
+            block(10, vec![transaction(program, hot, 30_000_000, false)]),
+            block(
+                11,
+                vec![
+                    transaction(program, hot, 6_000_000, true),
+                    transaction(vote::id(), Pubkey::new_unique(), 0, true),
+                ],
+            ),
+        ];
+
+        let report = analyze_blocks(&blocks).unwrap();
+
+        assert_eq!(report.blocks.len(), 2);
+        assert_eq!(report.blocks[0].utilization, 50.0);
+        assert_eq!((report.blocks[1].transactions, report.blocks[1].failed_transactions), (2, 1));
+        assert_eq!(report.avg_utilization, 30.0);
+        assert_eq!(report.max_utilization, 50.0);
+        // The failed vote is not counted against non-vote transactions.
+        assert_eq!(report.failed_tx_ratio, 0.5);
+        assert_eq!(report.score, 36);
+        assert_eq!(report.hotspots[0].account, hot.to_string());
+        assert_eq!((report.hotspots[0].transactions, report.hotspots[0].compute_units), (2, 36_000_000));
+        assert!(report.evidence[0].starts_with("Average block used 30.0% of the 60M CU limit"));
+    }
+}
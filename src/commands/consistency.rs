@@ -0,0 +1,615 @@
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcBlockConfig};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::hash::hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status_client_types::TransactionDetails;
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::path::Path;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use crate::commands::export::{to_csv_or_header, write_output};
+use crate::commands::rpc;
+use crate::commands::OutputFormat;
+use crate::types::{AccountView, BlockView, ConsistencyReport, Finding, FindingSeverity};
+
+/// Accounts compared unless `--account` is given: sysvars that only change
+/// with a feature activation, so any difference points at the endpoint.
+const DEFAULT_ACCOUNTS: [&str; 2] = [
+    "SysvarRent111111111111111111111111111111111",
+    "SysvarEpochSchedu1e111111111111111111111111",
+];
+/// How far behind the common finalized slot to look for a block that was not skipped.
+const BLOCK_LOOKBACK: u64 = 100;
+/// Reads of the accounts before giving up on the endpoints answering at the
+/// same context slot.
+const ACCOUNT_ATTEMPTS: usize = 3;
+const ACCOUNT_RETRY_DELAY: Duration = Duration::from_millis(400);
+
+/// Reads one compared field of a block view.
+type BlockField = fn(&BlockView) -> Option<String>;
+
+struct Endpoint {
+    name: String,
+    client: RpcClient,
+}
+
+/// Sends the same request to every endpoint at once, so the answers are as
+/// close in time as possible. Results are in endpoint order.
+fn query_all<T: Send>(endpoints: &[Endpoint], request: impl Fn(&RpcClient) -> Result<T, String> + Sync) -> Vec<Result<T, String>> {
+    let request = &request;
+    thread::scope(|scope| {
+        let handles: Vec<_> = endpoints
+            .iter()
+            .map(|endpoint| scope.spawn(move || request(&endpoint.client)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap_or_else(|_| Err("request panicked".to_string())))
+            .collect()
+    })
+}
+
+/// Most common value and the number of endpoints reporting it; ties go to
+/// the value reported first.
+fn majority<'a>(values: impl Iterator<Item = &'a str>) -> Option<(&'a str, usize)> {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for value in values {
+        match counts.iter_mut().find(|(seen, _)| *seen == value) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((value, 1)),
+        }
+    }
+    counts.into_iter().fold(None, |best, (value, count)| match best {
+        Some((_, best_count)) if best_count >= count => best,
+        _ => Some((value, count)),
+    })
+}
+
+/// Queries every endpoint for the newest finalized slot they all have, the
+/// block at that slot and the given accounts, and reports where they differ.
+pub fn check_consistency(urls: &[String], accounts: &[String], max_lag: u64) -> Result<ConsistencyReport, Box<dyn Error>> {
+    if urls.len() < 2 {
+        return Err("consistency needs at least two --url endpoints to compare".into());
+    }
+    let accounts: Vec<String> = if accounts.is_empty() {
+        DEFAULT_ACCOUNTS.iter().map(|account| account.to_string()).collect()
+    } else {
+        accounts.to_vec()
+    };
+    let pubkeys = accounts
+        .iter()
+        .map(|account| Pubkey::from_str(account).map_err(|e| format!("Invalid account {}: {}", account, e)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let endpoints = urls
+        .iter()
+        .map(|url| {
+            Ok(Endpoint {
+                name: rpc::redact_url(url),
+                client: rpc::client(url)?,
+            })
+        })
+        .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+
+    let mut report = ConsistencyReport::default();
+    let finalized = query_all(&endpoints, |client| {
+        client.get_slot_with_commitment(CommitmentConfig::finalized()).map_err(|e| e.to_string())
+    });
+    let newest = finalized.iter().filter_map(|slot| slot.as_ref().ok()).max().copied();
+    report.blocks = endpoints
+        .iter()
+        .zip(finalized)
+        .map(|(endpoint, slot)| BlockView {
+            endpoint: endpoint.name.clone(),
+            finalized_slot: slot.as_ref().ok().copied(),
+            finalized_lag: slot.as_ref().ok().zip(newest).map(|(slot, newest)| newest - slot),
+            error: slot.err(),
+            ..BlockView::default()
+        })
+        .collect();
+
+    // Endpoints that are far behind would drag the comparison back to a slot
+    // the others may already have pruned; they are reported as stale instead.
+    let in_sync: Vec<usize> = (0..endpoints.len())
+        .filter(|index| report.blocks[*index].finalized_lag.is_some_and(|lag| lag <= max_lag))
+        .collect();
+    let Some(common) = in_sync.iter().filter_map(|index| report.blocks[*index].finalized_slot).min() else {
+        report.findings = block_findings(&report, max_lag);
+        return Ok(report);
+    };
+    let slot = endpoints[in_sync[0]]
+        .client
+        .get_blocks_with_commitment(common.saturating_sub(BLOCK_LOOKBACK), Some(common), CommitmentConfig::finalized())
+        .ok()
+        .and_then(|slots| slots.last().copied())
+        .unwrap_or(common);
+    report.slot = Some(slot);
+
+    let config = RpcBlockConfig {
+        encoding: None,
+        transaction_details: Some(TransactionDetails::None),
+        rewards: Some(false),
+        commitment: Some(CommitmentConfig::finalized()),
+        max_supported_transaction_version: Some(0),
+    };
+    let blocks = query_all(&endpoints, |client| client.get_block_with_config(slot, config).map_err(|e| e.to_string()));
+    for (view, block) in report.blocks.iter_mut().zip(blocks) {
+        match block {
+            Ok(block) => {
+                view.blockhash = Some(block.blockhash);
+                view.parent_slot = Some(block.parent_slot);
+                view.block_time = block.block_time;
+            }
+            Err(error) => view.error = view.error.take().or(Some(error)),
+        }
+    }
+    report.findings = block_findings(&report, max_lag);
+
+    report.accounts = read_accounts(&endpoints, &accounts, &pubkeys, slot);
+    report.findings.extend(account_findings(&report.accounts, &accounts));
+
+    Ok(report)
+}
+
+/// Reads the accounts from every endpoint, retrying while the endpoints
+/// answer at different context slots so their data can be compared.
+fn read_accounts(endpoints: &[Endpoint], accounts: &[String], pubkeys: &[Pubkey], min_slot: u64) -> Vec<AccountView> {
+    let config = RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        commitment: Some(CommitmentConfig::finalized()),
+        min_context_slot: Some(min_slot),
+        ..RpcAccountInfoConfig::default()
+    };
+
+    let mut views = Vec::new();
+    for attempt in 0..ACCOUNT_ATTEMPTS {
+        if attempt > 0 {
+            thread::sleep(ACCOUNT_RETRY_DELAY);
+        }
+        let responses = query_all(endpoints, |client| {
+            client
+                .get_multiple_accounts_with_config(pubkeys, config.clone())
+                .map_err(|e| e.to_string())
+        });
+        let context_slots: BTreeSet<u64> = responses
+            .iter()
+            .filter_map(|response| response.as_ref().ok().map(|response| response.context.slot))
+            .collect();
+
+        views.clear();
+        for (endpoint, response) in endpoints.iter().zip(responses) {
+            for (index, account) in accounts.iter().enumerate() {
+                let mut view = AccountView {
+                    endpoint: endpoint.name.clone(),
+                    account: account.clone(),
+                    ..AccountView::default()
+                };
+                match &response {
+                    Ok(response) => {
+                        view.context_slot = Some(response.context.slot);
+                        if let Some(Some(data)) = response.value.get(index) {
+                            view.lamports = Some(data.lamports);
+                            view.owner = Some(data.owner.to_string());
+                            view.data_len = Some(data.data.len());
+                            view.data_hash = Some(hash(&data.data).to_string());
+                        }
+                    }
+                    Err(error) => view.error = Some(error.clone()),
+                }
+                views.push(view);
+            }
+        }
+
+        if context_slots.len() <= 1 {
+            break;
+        }
+    }
+    views
+}
+
+fn block_findings(report: &ConsistencyReport, max_lag: u64) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let newest = report.blocks.iter().filter_map(|view| view.finalized_slot).max();
+
+    for view in &report.blocks {
+        match (view.finalized_slot, view.finalized_lag) {
+            (None, _) => findings.push(Finding {
+                severity: FindingSeverity::Critical,
+                check: "Connection".to_string(),
+                detail: format!(
+                    "{} did not answer getSlot: {}",
+                    view.endpoint,
+                    view.error.as_deref().unwrap_or("unknown error")
+                ),
+                remediation: Some("Check that the endpoint is reachable and its credentials are valid.".to_string()),
+            }),
+            (Some(slot), Some(lag)) if lag > max_lag => findings.push(Finding {
+                severity: FindingSeverity::Critical,
+                check: "Finalized slot".to_string(),
+                detail: format!(
+                    "{} has finalized slot {}, {} slots behind {}",
+                    view.endpoint,
+                    slot,
+                    lag,
+                    newest.unwrap_or_default()
+                ),
+                remediation: Some(
+                    "The node is stuck or catching up and serves stale state; check `solana catchup` on it and stop \
+                     routing traffic to it until it has caught up."
+                        .to_string(),
+                ),
+            }),
+            _ => {}
+        }
+    }
+
+    let Some(slot) = report.slot else {
+        return findings;
+    };
+    let answered: Vec<&BlockView> = report.blocks.iter().filter(|view| view.blockhash.is_some()).collect();
+    for view in &report.blocks {
+        if view.finalized_slot.is_some() && view.blockhash.is_none() {
+            findings.push(Finding {
+                severity: FindingSeverity::Warning,
+                check: "Finalized block".to_string(),
+                detail: format!(
+                    "{} could not return block {}: {}",
+                    view.endpoint,
+                    slot,
+                    view.error.as_deref().unwrap_or("unknown error")
+                ),
+                remediation: Some(
+                    "A node that reports a slot skipped while others return a block may be on a minority fork; one \
+                     with a short ledger history may have pruned it."
+                        .to_string(),
+                ),
+            });
+        }
+    }
+
+    let mut diverged = false;
+    let fields: [(&str, FindingSeverity, BlockField); 3] = [
+        ("Blockhash", FindingSeverity::Critical, |view| view.blockhash.clone()),
+        ("Parent slot", FindingSeverity::Critical, |view| view.parent_slot.map(|slot| slot.to_string())),
+        ("Block time", FindingSeverity::Warning, |view| view.block_time.map(|time| time.to_string())),
+    ];
+    for (check, severity, field) in fields {
+        let values: Vec<(&str, String)> = answered
+            .iter()
+            .filter_map(|view| field(view).map(|value| (view.endpoint.as_str(), value)))
+            .collect();
+        let Some((expected, count)) = majority(values.iter().map(|(_, value)| value.as_str())) else {
+            continue;
+        };
+        for (endpoint, value) in &values {
+            if value != expected {
+                diverged = true;
+                findings.push(Finding {
+                    severity,
+                    check: check.to_string(),
+                    detail: format!(
+                        "{} reports {} for slot {}, {} of {} endpoints report {}",
+                        endpoint,
+                        value,
+                        slot,
+                        count,
+                        values.len(),
+                        expected
+                    ),
+                    remediation: Some(
+                        "The endpoints disagree on finalized history, so at least one is on a minority fork or serving \
+                         corrupt ledger data; compare with a trusted endpoint and restart the odd one out from a fresh \
+                         snapshot."
+                            .to_string(),
+                    ),
+                });
+            }
+        }
+    }
+
+    if !diverged && answered.len() > 1 {
+        findings.push(Finding::ok(
+            "Finalized block",
+            &format!(
+                "{} endpoints agree on blockhash {} for slot {}",
+                answered.len(),
+                answered[0].blockhash.as_deref().unwrap_or_default(),
+                slot
+            ),
+        ));
+    }
+    findings
+}
+
+/// Compares each account between the endpoints that answered at the same
+/// context slot; data read at different slots may legitimately differ.
+fn account_findings(views: &[AccountView], accounts: &[String]) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let mut matched = 0;
+
+    for account in accounts {
+        let mut by_slot: BTreeMap<u64, Vec<&AccountView>> = BTreeMap::new();
+        for view in views.iter().filter(|view| &view.account == account) {
+            if let Some(slot) = view.context_slot {
+                by_slot.entry(slot).or_default().push(view);
+            }
+        }
+
+        let mut compared = false;
+        let mut diverged = false;
+        for (slot, group) in by_slot.iter().filter(|(_, group)| group.len() > 1) {
+            compared = true;
+            let fingerprints: Vec<String> = group.iter().map(|view| fingerprint(view)).collect();
+            let Some((expected, count)) = majority(fingerprints.iter().map(String::as_str)) else {
+                continue;
+            };
+            for (view, fingerprint) in group.iter().zip(&fingerprints) {
+                if fingerprint != expected {
+                    diverged = true;
+                    findings.push(Finding {
+                        severity: FindingSeverity::Critical,
+                        check: "Account data".to_string(),
+                        detail: format!(
+                            "{} returns {} for {} at context slot {}, {} of {} endpoints return {}",
+                            view.endpoint,
+                            fingerprint,
+                            account,
+                            slot,
+                            count,
+                            group.len(),
+                            expected
+                        ),
+                        remediation: Some(
+                            "The node serves different state for the same slot; it is likely on a minority fork or \
+                             has a corrupt accounts database and should be restarted from a fresh snapshot."
+                                .to_string(),
+                        ),
+                    });
+                }
+            }
+        }
+
+        if !compared && by_slot.len() > 1 {
+            let slots: Vec<String> = by_slot.keys().map(|slot| slot.to_string()).collect();
+            findings.push(Finding {
+                severity: FindingSeverity::Warning,
+                check: "Account data".to_string(),
+                detail: format!(
+                    "Could not compare {}: endpoints kept answering at different context slots ({})",
+                    account,
+                    slots.join(", ")
+                ),
+                remediation: None,
+            });
+        } else if compared && !diverged {
+            matched += 1;
+        }
+    }
+
+    if matched > 0 {
+        findings.push(Finding::ok(
+            "Account data",
+            &format!("{} of {} accounts match across endpoints at the same context slot", matched, accounts.len()),
+        ));
+    }
+    findings
+}
+
+fn fingerprint(view: &AccountView) -> String {
+    match (&view.data_hash, view.lamports, &view.owner) {
+        (Some(data_hash), Some(lamports), Some(owner)) => format!("{} lamports, owner {}, data {}", lamports, owner, data_hash),
+        _ => "no account".to_string(),
+    }
+}
+
+pub fn run_consistency(
+    urls: &[String],
+    accounts: &[String],
+    max_lag: u64,
+    format: OutputFormat,
+    output: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    let report = check_consistency(urls, accounts, max_lag)?;
+
+    let rendered = match format {
+        OutputFormat::Table => format_table(&report),
+        OutputFormat::Csv => format_csv(&report)?,
+        OutputFormat::Json => serde_json::to_string_pretty(&report)?,
+    };
+
+    write_output(&rendered, output)?;
+
+    Ok(())
+}
+
+/// The block, account and finding tables as CSV sections separated by a blank
+/// line, each with its own header row.
+fn format_csv(report: &ConsistencyReport) -> Result<String, Box<dyn Error>> {
+    let sections = [
+        to_csv_or_header(&report.blocks)?,
+        to_csv_or_header(&report.accounts)?,
+        to_csv_or_header(&report.findings)?,
+    ];
+    Ok(sections.join("\n"))
+}
+
+fn format_table(report: &ConsistencyReport) -> String {
+    let mut out = match report.slot {
+        Some(slot) => format!("Consistency at finalized slot {}\n\n", slot),
+        None => "Consistency: no common finalized slot\n\n".to_string(),
+    };
+
+    out.push_str(&format!(
+        "{:<40} {:>12} {:>6} {:<44} {:>12} {:>12}\n",
+        "Endpoint", "Finalized", "Lag", "Blockhash", "Parent", "Block Time"
+    ));
+    for view in &report.blocks {
+        let optional = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+        out.push_str(&format!(
+            "{:<40} {:>12} {:>6} {:<44} {:>12} {:>12}\n",
+            view.endpoint,
+            optional(view.finalized_slot.map(|slot| slot.to_string())),
+            optional(view.finalized_lag.map(|lag| lag.to_string())),
+            optional(view.blockhash.clone()),
+            optional(view.parent_slot.map(|slot| slot.to_string())),
+            optional(view.block_time.map(|time| time.to_string()))
+        ));
+    }
+
+    if !report.accounts.is_empty() {
+        out.push_str(&format!(
+            "\n{:<44} {:<40} {:>12} {:>16} {:>8} {:<44}\n",
+            "Account", "Endpoint", "Context", "Lamports", "Bytes", "Data Hash"
+        ));
+        for view in &report.accounts {
+            let optional = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+            out.push_str(&format!(
+                "{:<44} {:<40} {:>12} {:>16} {:>8} {:<44}\n",
+                view.account,
+                view.endpoint,
+                optional(view.context_slot.map(|slot| slot.to_string())),
+                optional(view.lamports.map(|lamports| lamports.to_string())),
+                optional(view.data_len.map(|len| len.to_string())),
+                optional(view.data_hash.clone().or_else(|| view.error.clone()))
+            ));
+        }
+    }
+
+    out.push_str("\nFindings:\n");
+    for finding in &report.findings {
+        out.push_str(&format!("[{}] {}: {}\n", finding.severity.label(), finding.check, finding.detail));
+        if let Some(remediation) = &finding.remediation {
+            out.push_str(&format!("  -> {}\n", remediation));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(endpoint: &str, finalized_slot: u64, lag: u64, blockhash: &str) -> BlockView {
+        BlockView {
+            endpoint: endpoint.to_string(),
+            finalized_slot: Some(finalized_slot),
+            finalized_lag: Some(lag),
+            blockhash: Some(blockhash.to_string()),
+            parent_slot: Some(99),
+            block_time: Some(1_700_000_000),
+            error: None,
+        }
+    }
+
+    fn account(endpoint: &str, context_slot: u64, lamports: u64) -> AccountView {
+        AccountView {
+            endpoint: endpoint.to_string(),
+            account: DEFAULT_ACCOUNTS[0].to_string(),
+            context_slot: Some(context_slot),
+            lamports: Some(lamports),
+            owner: Some("Sysvar1111111111111111111111111111111111111".to_string()),
+            data_len: Some(17),
+            data_hash: Some("hash".to_string()),
+            error: None,
+        }
+    }
+
+    fn checks(findings: &[Finding]) -> Vec<(FindingSeverity, &str)> {
+        findings.iter().map(|finding| (finding.severity, finding.check.as_str())).collect()
+    }
+
+    #[test]
+    fn majority_prefers_the_most_common_then_the_first_value() {
+        assert_eq!(majority(["a", "b", "b"].into_iter()), Some(("b", 2)));
+        assert_eq!(majority(["a", "b"].into_iter()), Some(("a", 1)));
+        assert_eq!(majority(std::iter::empty()), None);
+    }
+
+    #[test]
+    fn block_findings_agree_on_a_common_blockhash() {
+        let report = ConsistencyReport {
+            slot: Some(100),
+            blocks: vec![block("one", 110, 0, "hashA"), block("two", 109, 1, "hashA")],
+            ..ConsistencyReport::default()
+        };
+
+        let findings = block_findings(&report, 150);
+        assert_eq!(checks(&findings), [(FindingSeverity::Ok, "Finalized block")]);
+        assert!(findings[0].detail.contains("2 endpoints agree on blockhash hashA for slot 100"));
+    }
+
+    #[test]
+    fn block_findings_flag_the_minority_blockhash() {
+        let report = ConsistencyReport {
+            slot: Some(100),
+            blocks: vec![block("one", 110, 0, "hashA"), block("two", 110, 0, "hashB"), block("three", 110, 0, "hashA")],
+            ..ConsistencyReport::default()
+        };
+
+        let findings = block_findings(&report, 150);
+        assert_eq!(checks(&findings), [(FindingSeverity::Critical, "Blockhash")]);
+        assert_eq!(findings[0].detail, "two reports hashB for slot 100, 2 of 3 endpoints report hashA");
+    }
+
+    #[test]
+    fn block_findings_report_lagging_and_unreachable_endpoints() {
+        let unreachable = BlockView {
+            endpoint: "down".to_string(),
+            error: Some("connection refused".to_string()),
+            ..BlockView::default()
+        };
+        let report = ConsistencyReport {
+            slot: None,
+            blocks: vec![block("one", 1_000, 0, "hashA"), block("behind", 700, 300, "hashA"), unreachable],
+            ..ConsistencyReport::default()
+        };
+
+        let findings = block_findings(&report, 150);
+        assert_eq!(
+            checks(&findings),
+            [(FindingSeverity::Critical, "Finalized slot"), (FindingSeverity::Critical, "Connection")]
+        );
+        assert_eq!(findings[0].detail, "behind has finalized slot 700, 300 slots behind 1000");
+        assert!(findings[1].detail.contains("connection refused"));
+    }
+
+    #[test]
+    fn account_findings_compare_only_matching_context_slots() {
+        let accounts = [DEFAULT_ACCOUNTS[0].to_string()];
+
+        let matching = [account("one", 200, 5), account("two", 200, 5)];
+        assert_eq!(checks(&account_findings(&matching, &accounts)), [(FindingSeverity::Ok, "Account data")]);
+
+        let diverged = [account("one", 200, 5), account("two", 200, 6), account("three", 200, 5)];
+        let findings = account_findings(&diverged, &accounts);
+        assert_eq!(checks(&findings), [(FindingSeverity::Critical, "Account data")]);
+        assert!(findings[0].detail.starts_with("two returns 6 lamports"));
+
+        // Different lamports at different slots are not a divergence, just not comparable.
+        let mismatched = [account("one", 200, 5), account("two", 201, 6)];
+        let findings = account_findings(&mismatched, &accounts);
+        assert_eq!(checks(&findings), [(FindingSeverity::Warning, "Account data")]);
+        assert!(findings[0].detail.ends_with("different context slots (200, 201)"));
+    }
+
+    #[test]
+    fn format_csv_includes_accounts_and_findings() {
+        let report = ConsistencyReport {
+            slot: Some(100),
+            blocks: vec![block("one", 110, 0, "hashA")],
+            accounts: vec![account("one", 200, 5)],
+            findings: vec![Finding::ok("Finalized block", "all good")],
+        };
+
+        let csv = format_csv(&report).unwrap();
+        let sections: Vec<&str> = csv.split("\n\n").collect();
+        assert_eq!(sections.len(), 3);
+        assert!(sections[0].starts_with("endpoint,finalized_slot,finalized_lag,blockhash"));
+        assert!(sections[1].starts_with("endpoint,account,context_slot,lamports"));
+        assert_eq!(sections[2], "severity,check,detail,remediation\nok,Finalized block,all good,\n");
+    }
+}
@@ -0,0 +1,83 @@
+use chrono::Local;
+use serde::Serialize;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Renders flat structs as CSV. The header row is the struct's field names in
+/// declaration order, so every export of a `types` struct uses the same columns.
+/// `None` becomes an empty cell.
+pub fn to_csv<T: Serialize>(rows: &[T]) -> Result<String, Box<dyn Error>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+/// Header-only CSV for an empty table, so exports always carry column names.
+pub fn to_csv_or_header<T: Serialize + Default>(rows: &[T]) -> Result<String, Box<dyn Error>> {
+    if rows.is_empty() {
+        // The writer takes the header from the first record it serializes.
+        let csv = to_csv(&[T::default()])?;
+        let header = csv.find('\n').map_or(csv.len(), |end| end + 1);
+        return Ok(csv[..header].to_string());
+    }
+    to_csv(rows)
+}
+
+/// Writes a command's rendered output to `output`, or to stdout without one.
+pub fn write_output(rendered: &str, output: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    match output {
+        Some(path) => fs::write(path, rendered)?,
+        None => println!("{}", rendered),
+    }
+    Ok(())
+}
+
+/// Writes CSV exported from the TUI to `solprobe-<name>-<timestamp>.csv` in
+/// the working directory and returns the path.
+pub fn write_export(name: &str, csv: &str) -> Result<PathBuf, Box<dyn Error>> {
+    let path = PathBuf::from(format!("solprobe-{}-{}.csv", name, Local::now().format("%Y%m%d-%H%M%S")));
+    fs::write(&path, csv)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default, Serialize)]
+    struct Row {
+        name: String,
+        value: Option<f64>,
+    }
+
+    #[test]
+    fn to_csv_quotes_fields_and_leaves_none_empty() {
+        let rows = vec![
+            Row { name: "plain".to_string(), value: Some(1.5) },
+            Row { name: "a,b".to_string(), value: None },
+            Row { name: "say \"hi\"".to_string(), value: Some(2.0) },
+            Row { name: "two\nlines".to_string(), value: None },
+        ];
+        assert_eq!(
+            to_csv(&rows).unwrap(),
+            "name,value\nplain,1.5\n\"a,b\",\n\"say \"\"hi\"\"\",2.0\n\"two\nlines\",\n"
+        );
+    }
+
+    #[test]
+    fn to_csv_or_header_writes_columns_for_empty_tables() {
+        assert_eq!(to_csv_or_header::<Row>(&[]).unwrap(), "name,value\n");
+        assert_eq!(to_csv(&Vec::<Row>::new()).unwrap(), "");
+    }
+
+    #[test]
+    fn write_output_writes_the_file() {
+        let path = std::env::temp_dir().join(format!("solprobe-output-{}.csv", std::process::id()));
+        write_output("name,value\n", Some(&path)).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "name,value\n");
+        fs::remove_file(&path).unwrap();
+    }
+}
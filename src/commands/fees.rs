@@ -0,0 +1,276 @@
+use serde::Serialize;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::VersionedTransaction;
+use solana_sdk_ids::compute_budget;
+use solana_transaction_status_client_types::UiConfirmedBlock;
+use std::error::Error;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::commands::blocks::{is_vote_transaction, sample_recent_blocks};
+use crate::commands::export::{to_csv_or_header, write_output};
+use crate::commands::rpc;
+use crate::commands::OutputFormat;
+use crate::types::{AccountFees, FeeMarket, FeePercentiles};
+
+/// Base fee charged per signature, in lamports.
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5000;
+
+/// Discriminators of the ComputeBudget `SetComputeUnitLimit` and
+/// `SetComputeUnitPrice` instructions.
+const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+const SET_COMPUTE_UNIT_PRICE: u8 = 3;
+
+pub fn fetch_fee_market(
+    client: &RpcClient,
+    accounts: &[String],
+    blocks: &[(u64, UiConfirmedBlock)],
+) -> Result<FeeMarket, Box<dyn Error>> {
+    let mut market = FeeMarket {
+        global: recent_prioritization_fees(client, &[])?,
+        ..FeeMarket::default()
+    };
+
+    for account in accounts {
+        let pubkey = Pubkey::from_str(account).map_err(|e| format!("Invalid account {}: {}", account, e))?;
+        market.accounts.push(AccountFees {
+            account: account.clone(),
+            prioritization_fees: recent_prioritization_fees(client, &[pubkey])?,
+        });
+    }
+
+    let mut cu_prices = Vec::new();
+    let mut priority_fees = Vec::new();
+    let mut non_vote_transactions = 0u64;
+
+    for (_, block) in blocks {
+        market.sampled_blocks += 1;
+
+        for encoded in block.transactions.iter().flatten() {
+            let (Some(transaction), Some(meta)) = (encoded.transaction.decode(), &encoded.meta) else {
+                continue;
+            };
+            if is_vote_transaction(&transaction) {
+                continue;
+            }
+            non_vote_transactions += 1;
+
+            if let Some(price) = compute_unit_price(&transaction) {
+                cu_prices.push(price);
+            }
+            let base_fee = transaction.signatures.len() as u64 * LAMPORTS_PER_SIGNATURE;
+            priority_fees.push(meta.fee.saturating_sub(base_fee));
+        }
+    }
+
+    market.priority_tx_ratio = if non_vote_transactions == 0 {
+        0.0
+    } else {
+        cu_prices.len() as f64 / non_vote_transactions as f64
+    };
+    market.block_cu_prices = FeePercentiles::from_samples(cu_prices);
+    market.block_priority_fees = FeePercentiles::from_samples(priority_fees);
+    market.recommended_cu_price = recommended_cu_price(&market);
+
+    Ok(market)
+}
+
+/// A price that would have placed a transaction in the top quarter of recent
+/// prioritization fees, both cluster-wide and for every requested account,
+/// and at or above the median price set by transactions in sampled blocks.
+fn recommended_cu_price(market: &FeeMarket) -> u64 {
+    market
+        .accounts
+        .iter()
+        .map(|account| account.prioritization_fees.p75)
+        .chain([market.global.p75, market.block_cu_prices.p50])
+        .max()
+        .unwrap_or(0)
+}
+
+fn recent_prioritization_fees(client: &RpcClient, accounts: &[Pubkey]) -> Result<FeePercentiles, Box<dyn Error>> {
+    let fees = client.get_recent_prioritization_fees(accounts)?;
+    Ok(FeePercentiles::from_samples(
+        fees.into_iter().map(|fee| fee.prioritization_fee).collect(),
+    ))
+}
+
+/// Compute unit price in micro-lamports requested via `SetComputeUnitPrice`.
+pub fn compute_unit_price(transaction: &VersionedTransaction) -> Option<u64> {
+    compute_budget_argument(transaction, SET_COMPUTE_UNIT_PRICE, 8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap_or_default()))
+}
+
+/// Compute unit limit requested via `SetComputeUnitLimit`.
+pub fn compute_unit_limit(transaction: &VersionedTransaction) -> Option<u32> {
+    compute_budget_argument(transaction, SET_COMPUTE_UNIT_LIMIT, 4)
+        .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap_or_default()))
+}
+
+/// The first `len` argument bytes of the ComputeBudget instruction with the
+/// given discriminator.
+fn compute_budget_argument(transaction: &VersionedTransaction, discriminator: u8, len: usize) -> Option<&[u8]> {
+    let keys = transaction.message.static_account_keys();
+    transaction.message.instructions().iter().find_map(|ix| {
+        if keys.get(ix.program_id_index as usize) != Some(&compute_budget::id()) {
+            return None;
+        }
+        match ix.data.split_first() {
+            Some((&tag, rest)) if tag == discriminator && rest.len() >= len => Some(&rest[..len]),
+            _ => None,
+        }
+    })
+}
+
+pub fn get_fee_market(url: &str, accounts: &[String], sample_blocks: usize) -> Result<FeeMarket, Box<dyn Error>> {
+    let client = rpc::client(url)?;
+    let blocks = sample_recent_blocks(&client, sample_blocks)?;
+    fetch_fee_market(&client, accounts, &blocks)
+}
+
+pub fn run_fees(
+    url: &str,
+    accounts: &[String],
+    sample_blocks: usize,
+    format: OutputFormat,
+    output: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    let market = get_fee_market(url, accounts, sample_blocks)?;
+
+    let rendered = match format {
+        OutputFormat::Table => format_table(&market),
+        OutputFormat::Csv => format_csv(&market)?,
+        OutputFormat::Json => serde_json::to_string_pretty(&market)?,
+    };
+
+    write_output(&rendered, output)?;
+
+    Ok(())
+}
+
+fn percentile_rows(market: &FeeMarket) -> Vec<(String, &FeePercentiles)> {
+    let mut rows = vec![("global".to_string(), &market.global)];
+    for account in &market.accounts {
+        rows.push((account.account.clone(), &account.prioritization_fees));
+    }
+    rows.push(("block_cu_price".to_string(), &market.block_cu_prices));
+    rows.push(("block_priority_fee_lamports".to_string(), &market.block_priority_fees));
+    rows
+}
+
+fn format_table(market: &FeeMarket) -> String {
+    let mut out = String::new();
+    out.push_str("Priority Fees:\n");
+    out.push_str(&format!("Sampled Blocks: {}\n", market.sampled_blocks));
+    out.push_str(&format!("Transactions Setting a CU Price: {:.1}%\n", market.priority_tx_ratio * 100.0));
+    out.push_str(&format!("Recommended CU Price: {} micro-lamports\n\n", market.recommended_cu_price));
+    out.push_str(&format!(
+        "{:<44} {:>8} {:>10} {:>10} {:>10} {:>10} {:>10} {:>12}\n",
+        "Scope", "Samples", "Min", "P25", "P50", "P75", "P90", "Max"
+    ));
+    for (scope, fees) in percentile_rows(market) {
+        out.push_str(&format!(
+            "{:<44} {:>8} {:>10} {:>10} {:>10} {:>10} {:>10} {:>12}\n",
+            scope, fees.samples, fees.min, fees.p25, fees.p50, fees.p75, fees.p90, fees.max
+        ));
+    }
+    out
+}
+
+/// One [`percentile_rows`] entry as a CSV record.
+#[derive(Debug, Default, Serialize)]
+struct FeeRow {
+    scope: String,
+    samples: u64,
+    min: u64,
+    p25: u64,
+    p50: u64,
+    p75: u64,
+    p90: u64,
+    max: u64,
+}
+
+pub fn format_csv(market: &FeeMarket) -> Result<String, Box<dyn Error>> {
+    let rows: Vec<FeeRow> = percentile_rows(market)
+        .into_iter()
+        .map(|(scope, fees)| FeeRow {
+            scope,
+            samples: fees.samples,
+            min: fees.min,
+            p25: fees.p25,
+            p50: fees.p50,
+            p75: fees.p75,
+            p90: fees.p90,
+            max: fees.max,
+        })
+        .collect();
+    to_csv_or_header(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_compute_budget_interface::ComputeBudgetInstruction;
+    use solana_sdk::message::Message;
+    use solana_sdk::transaction::Transaction;
+
+    fn percentiles(p50: u64, p75: u64) -> FeePercentiles {
+        FeePercentiles { p50, p75, ..FeePercentiles::default() }
+    }
+
+    #[test]
+    fn from_samples_ranks_unsorted_samples() {
+        let fees = FeePercentiles::from_samples((1..=10).rev().collect());
+        assert_eq!(fees.samples, 10);
+        assert_eq!((fees.min, fees.p25, fees.p50, fees.p75, fees.p90, fees.max), (1, 3, 5, 8, 9, 10));
+
+        let single = FeePercentiles::from_samples(vec![7]);
+        assert_eq!((single.min, single.p25, single.p90, single.max), (7, 7, 7, 7));
+    }
+
+    #[test]
+    fn from_samples_without_samples_is_zero() {
+        let fees = FeePercentiles::from_samples(Vec::new());
+        assert_eq!((fees.samples, fees.min, fees.p50, fees.max), (0, 0, 0, 0));
+    }
+
+    #[test]
+    fn recommended_cu_price_takes_the_highest_signal() {
+        let mut market = FeeMarket {
+            global: percentiles(100, 200),
+            block_cu_prices: percentiles(150, 1_000),
+            ..FeeMarket::default()
+        };
+        assert_eq!(recommended_cu_price(&market), 200);
+
+        market.block_cu_prices = percentiles(500, 1_000);
+        assert_eq!(recommended_cu_price(&market), 500);
+
+        market.accounts.push(AccountFees {
+            account: "hot".to_string(),
+            prioritization_fees: percentiles(800, 5_000),
+        });
+        assert_eq!(recommended_cu_price(&market), 5_000);
+
+        assert_eq!(recommended_cu_price(&FeeMarket::default()), 0);
+    }
+
+    #[test]
+    fn compute_budget_instructions_are_decoded() {
+        let payer = Pubkey::new_unique();
+        let message = Message::new(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(200_000),
+                ComputeBudgetInstruction::set_compute_unit_price(12_345),
+            ],
+            Some(&payer),
+        );
+        let transaction = VersionedTransaction::from(Transaction::new_unsigned(message));
+        assert_eq!(compute_unit_price(&transaction), Some(12_345));
+        assert_eq!(compute_unit_limit(&transaction), Some(200_000));
+
+        let plain = VersionedTransaction::from(Transaction::new_unsigned(Message::new(&[], Some(&payer))));
+        assert_eq!(compute_unit_price(&plain), None);
+    }
+}
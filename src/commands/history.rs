@@ -0,0 +1,466 @@
+use chrono::{DateTime, Local, Utc};
+use fs2::FileExt;
+use serde::Serialize;
+use std::error::Error;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::commands::export::{to_csv_or_header, write_output};
+use crate::commands::probe::ProbeSample;
+use crate::commands::OutputFormat;
+use crate::commands::rpc::redact_url;
+use crate::types::HistorySnapshot;
+use crate::utils::config::HistoryConfig;
+
+/// How often the history file is compacted while recording.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Time span shown by the History tab chart.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryWindow {
+    #[default]
+    Hour,
+    SixHours,
+    Day,
+    Week,
+}
+
+impl HistoryWindow {
+    pub fn next(self) -> Self {
+        match self {
+            HistoryWindow::Hour => HistoryWindow::SixHours,
+            HistoryWindow::SixHours => HistoryWindow::Day,
+            HistoryWindow::Day => HistoryWindow::Week,
+            HistoryWindow::Week => HistoryWindow::Hour,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            HistoryWindow::Hour => "1h",
+            HistoryWindow::SixHours => "6h",
+            HistoryWindow::Day => "24h",
+            HistoryWindow::Week => "7d",
+        }
+    }
+
+    pub fn secs(self) -> i64 {
+        match self {
+            HistoryWindow::Hour => 3600,
+            HistoryWindow::SixHours => 6 * 3600,
+            HistoryWindow::Day => 24 * 3600,
+            HistoryWindow::Week => 7 * 24 * 3600,
+        }
+    }
+}
+
+/// Append-only JSON Lines store of probe snapshots.
+pub struct HistoryStore {
+    path: PathBuf,
+    retention_secs: i64,
+    last_prune: Instant,
+}
+
+impl HistoryStore {
+    /// Opens the configured history file and drops snapshots past retention.
+    pub fn open(config: &HistoryConfig) -> Result<Self, Box<dyn Error>> {
+        let path = match &config.path {
+            Some(path) => path.clone(),
+            None => default_path()?,
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut store = HistoryStore {
+            path,
+            retention_secs: config.retention_hours as i64 * 3600,
+            last_prune: Instant::now(),
+        };
+        store.prune()?;
+        Ok(store)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn append(&mut self, snapshot: &HistorySnapshot) -> Result<(), Box<dyn Error>> {
+        let line = format!("{}\n", serde_json::to_string(snapshot)?);
+        {
+            let _lock = self.lock()?;
+            let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+            file.write_all(line.as_bytes())?;
+        }
+
+        if self.last_prune.elapsed() >= PRUNE_INTERVAL {
+            self.prune()?;
+        }
+        Ok(())
+    }
+
+    /// Takes the lock shared by every process writing this history file, so
+    /// that a TUI and `serve` recording to the same file neither interleave
+    /// appends nor lose them to a concurrent prune. Released on drop.
+    fn lock(&self) -> Result<File, Box<dyn Error>> {
+        let lock = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(self.path.with_extension("jsonl.lock"))?;
+        lock.lock_exclusive()?;
+        Ok(lock)
+    }
+
+    /// Loads snapshots with `since <= timestamp <= until`, skipping lines that
+    /// fail to parse (e.g. a write interrupted by a crash).
+    pub fn load(&self, since: i64, until: i64) -> Result<Vec<HistorySnapshot>, Box<dyn Error>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let reader = BufReader::new(File::open(&self.path)?);
+        let mut snapshots = Vec::new();
+        for line in reader.lines() {
+            let Ok(snapshot) = serde_json::from_str::<HistorySnapshot>(&line?) else {
+                continue;
+            };
+            if snapshot.timestamp >= since && snapshot.timestamp <= until {
+                snapshots.push(snapshot);
+            }
+        }
+        Ok(snapshots)
+    }
+
+    /// Rewrites the file without snapshots older than the retention period.
+    /// Lines that fail to parse are kept as they are.
+    fn prune(&mut self) -> Result<(), Box<dyn Error>> {
+        self.last_prune = Instant::now();
+        let _lock = self.lock()?;
+        if !self.path.exists() {
+            return Ok(());
+        }
+
+        let cutoff = Utc::now().timestamp() - self.retention_secs;
+        let reader = BufReader::new(File::open(&self.path)?);
+        let mut kept = Vec::new();
+        let mut dropped = false;
+        for line in reader.lines() {
+            let line = line?;
+            match serde_json::from_str::<HistorySnapshot>(&line) {
+                Ok(snapshot) if snapshot.timestamp < cutoff => dropped = true,
+                _ => kept.push(line),
+            }
+        }
+        if !dropped {
+            return Ok(());
+        }
+
+        let tmp_path = self.path.with_extension("jsonl.tmp");
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        for line in kept {
+            writeln!(writer, "{}", line)?;
+        }
+        writer.flush()?;
+        fs::rename(tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+pub fn default_path() -> Result<PathBuf, Box<dyn Error>> {
+    let mut path = dirs::data_dir().ok_or("Failed to get data directory")?;
+    path.push("solprobe");
+    path.push("history.jsonl");
+    Ok(path)
+}
+
+/// Builds a snapshot from the current probe samples; missing values are left out.
+pub fn snapshot(endpoint: &str, samples: &[ProbeSample]) -> HistorySnapshot {
+    HistorySnapshot {
+        timestamp: Utc::now().timestamp(),
+        endpoint: redact_url(endpoint),
+        values: samples
+            .iter()
+            .filter_map(|sample| sample.value.map(|value| (sample.name.to_string(), value)))
+            .collect(),
+    }
+}
+
+/// Parses a look-back window such as `90s`, `30m`, `24h` or `7d`.
+pub fn parse_window(window: &str) -> Result<u64, String> {
+    let window = window.trim();
+    let split = window.find(|c: char| !c.is_ascii_digit()).unwrap_or(window.len());
+    let (amount, unit) = window.split_at(split);
+    let amount: u64 = amount.parse().map_err(|_| format!("invalid window '{}'", window))?;
+    let multiplier = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        _ => return Err(format!("invalid window unit '{}' (expected s, m, h or d)", unit)),
+    };
+    amount
+        .checked_mul(multiplier)
+        .filter(|secs| i64::try_from(*secs).is_ok())
+        .ok_or_else(|| format!("window '{}' is too long", window))
+}
+
+pub fn format_timestamp(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|time| time.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+#[derive(Debug, Default, Serialize)]
+struct MetricSummary {
+    metric: String,
+    samples: usize,
+    min: f64,
+    avg: f64,
+    max: f64,
+    last: f64,
+}
+
+fn summarize(snapshots: &[HistorySnapshot]) -> Vec<MetricSummary> {
+    let mut summaries: Vec<MetricSummary> = Vec::new();
+    for snapshot in snapshots {
+        for (metric, value) in &snapshot.values {
+            match summaries.iter_mut().find(|summary| &summary.metric == metric) {
+                Some(summary) => {
+                    summary.samples += 1;
+                    summary.min = summary.min.min(*value);
+                    summary.max = summary.max.max(*value);
+                    summary.avg += value;
+                    summary.last = *value;
+                }
+                None => summaries.push(MetricSummary {
+                    metric: metric.clone(),
+                    samples: 1,
+                    min: *value,
+                    avg: *value,
+                    max: *value,
+                    last: *value,
+                }),
+            }
+        }
+    }
+    for summary in &mut summaries {
+        summary.avg /= summary.samples as f64;
+    }
+    summaries.sort_by(|a, b| a.metric.cmp(&b.metric));
+    summaries
+}
+
+/// Prints recorded snapshots. Without `metrics`, each recorded metric is
+/// summarized over the window; otherwise the selected metrics are listed per
+/// snapshot.
+pub fn run_history(
+    config: &HistoryConfig,
+    metrics: &[String],
+    window_secs: u64,
+    endpoint: Option<&str>,
+    format: OutputFormat,
+    output: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    let store = HistoryStore::open(config)?;
+    let until = Utc::now().timestamp();
+    let mut snapshots = store.load(until - window_secs as i64, until)?;
+    if let Some(endpoint) = endpoint.map(redact_url) {
+        snapshots.retain(|snapshot| snapshot.endpoint == endpoint);
+    }
+
+    let rendered = if metrics.is_empty() {
+        let summaries = summarize(&snapshots);
+        match format {
+            OutputFormat::Table => format_summary_table(&summaries, &snapshots, store.path()),
+            OutputFormat::Csv => to_csv_or_header(&summaries)?,
+            OutputFormat::Json => serde_json::to_string_pretty(&summaries)?,
+        }
+    } else {
+        for snapshot in &mut snapshots {
+            snapshot.values.retain(|metric, _| metrics.contains(metric));
+        }
+        match format {
+            OutputFormat::Table => format_series_table(&snapshots, metrics),
+            OutputFormat::Csv => format_series_csv(&snapshots, metrics)?,
+            OutputFormat::Json => serde_json::to_string_pretty(&snapshots)?,
+        }
+    };
+
+    write_output(&rendered, output)?;
+
+    Ok(())
+}
+
+fn format_summary_table(summaries: &[MetricSummary], snapshots: &[HistorySnapshot], path: &Path) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("History: {}\n", path.display()));
+    match (snapshots.first(), snapshots.last()) {
+        (Some(first), Some(last)) => out.push_str(&format!(
+            "Snapshots: {} ({} - {})\n\n",
+            snapshots.len(),
+            format_timestamp(first.timestamp),
+            format_timestamp(last.timestamp)
+        )),
+        _ => {
+            out.push_str("No snapshots recorded in this window.\n");
+            return out;
+        }
+    }
+
+    out.push_str(&format!(
+        "{:<36} {:>8} {:>16} {:>16} {:>16} {:>16}\n",
+        "Metric", "Samples", "Min", "Avg", "Max", "Last"
+    ));
+    for summary in summaries {
+        out.push_str(&format!(
+            "{:<36} {:>8} {:>16.3} {:>16.3} {:>16.3} {:>16.3}\n",
+            summary.metric, summary.samples, summary.min, summary.avg, summary.max, summary.last
+        ));
+    }
+    out
+}
+
+fn format_series_table(snapshots: &[HistorySnapshot], metrics: &[String]) -> String {
+    let mut out = format!("{:<20}", "Time");
+    for metric in metrics {
+        out.push_str(&format!(" {:>24}", metric));
+    }
+    out.push('\n');
+
+    for snapshot in snapshots {
+        out.push_str(&format!("{:<20}", format_timestamp(snapshot.timestamp)));
+        for metric in metrics {
+            match snapshot.values.get(metric) {
+                Some(value) => out.push_str(&format!(" {:>24.3}", value)),
+                None => out.push_str(&format!(" {:>24}", "-")),
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// One row per snapshot with a column per metric; shared with the TUI export.
+pub fn format_series_csv(snapshots: &[HistorySnapshot], metrics: &[String]) -> Result<String, Box<dyn Error>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(["timestamp", "endpoint"].into_iter().chain(metrics.iter().map(String::as_str)))?;
+
+    for snapshot in snapshots {
+        let mut record = vec![snapshot.timestamp.to_string(), snapshot.endpoint.clone()];
+        for metric in metrics {
+            record.push(snapshot.values.get(metric).map(|value| value.to_string()).unwrap_or_default());
+        }
+        writer.write_record(&record)?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn store(name: &str, retention_hours: u64) -> HistoryStore {
+        let dir = std::env::temp_dir().join(format!("solprobe-history-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        HistoryStore::open(&HistoryConfig {
+            enabled: true,
+            path: Some(dir.join("history.jsonl")),
+            retention_hours,
+        })
+        .unwrap()
+    }
+
+    fn snapshot_at(timestamp: i64, value: f64) -> HistorySnapshot {
+        HistorySnapshot {
+            timestamp,
+            endpoint: "https://rpc.example.com/".to_string(),
+            values: BTreeMap::from([("tps".to_string(), value)]),
+        }
+    }
+
+    #[test]
+    fn parse_window_units() {
+        assert_eq!(parse_window("90s"), Ok(90));
+        assert_eq!(parse_window("30m"), Ok(1800));
+        assert_eq!(parse_window(" 24h "), Ok(86400));
+        assert_eq!(parse_window("7d"), Ok(7 * 86400));
+    }
+
+    #[test]
+    fn parse_window_rejects_bad_input() {
+        assert!(parse_window("").is_err());
+        assert!(parse_window("h").is_err());
+        assert!(parse_window("10").is_err());
+        assert!(parse_window("10w").is_err());
+        assert!(parse_window("-5m").is_err());
+        assert_eq!(parse_window("999999999999999999d"), Err("window '999999999999999999d' is too long".to_string()));
+        assert!(parse_window(&format!("{}s", u64::MAX)).is_err());
+    }
+
+    #[test]
+    fn load_filters_by_time_and_skips_unparseable_lines() {
+        let mut store = store("load", 24);
+        let now = Utc::now().timestamp();
+        store.append(&snapshot_at(now - 120, 1.0)).unwrap();
+        fs::OpenOptions::new().append(true).open(store.path()).unwrap().write_all(b"{\"timest\n").unwrap();
+        store.append(&snapshot_at(now - 60, 2.0)).unwrap();
+        store.append(&snapshot_at(now, 3.0)).unwrap();
+
+        let values: Vec<f64> = store.load(now - 90, now).unwrap().iter().map(|s| s.values["tps"]).collect();
+        assert_eq!(values, vec![2.0, 3.0]);
+    }
+
+    #[test]
+    fn prune_drops_expired_snapshots_and_keeps_unparseable_lines() {
+        let mut store = store("prune", 1);
+        let now = Utc::now().timestamp();
+        store.append(&snapshot_at(now - 7200, 1.0)).unwrap();
+        fs::OpenOptions::new().append(true).open(store.path()).unwrap().write_all(b"partial\n").unwrap();
+        store.append(&snapshot_at(now, 2.0)).unwrap();
+
+        store.prune().unwrap();
+        let contents = fs::read_to_string(store.path()).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], "partial");
+        assert_eq!(store.load(0, i64::MAX).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn concurrent_appends_and_prunes_lose_nothing() {
+        let path = store("concurrent", 1).path().to_path_buf();
+        let config = HistoryConfig {
+            enabled: true,
+            path: Some(path.clone()),
+            retention_hours: 1,
+        };
+        let now = Utc::now().timestamp();
+
+        let writers: Vec<_> = (0..4)
+            .map(|writer| {
+                let config = config.clone();
+                std::thread::spawn(move || {
+                    let mut store = HistoryStore::open(&config).unwrap();
+                    for index in 0..50 {
+                        store.append(&snapshot_at(now, (writer * 100 + index) as f64)).unwrap();
+                        if index % 10 == 0 {
+                            // An expired snapshot makes the prune rewrite the file.
+                            store.append(&snapshot_at(now - 7200, 0.0)).unwrap();
+                            store.prune().unwrap();
+                        }
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let store = HistoryStore::open(&config).unwrap();
+        assert_eq!(store.load(0, i64::MAX).unwrap().len(), 200);
+    }
+}
@@ -0,0 +1,540 @@
+use log::{debug, info, warn};
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcSendTransactionConfig;
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::hash::Hash;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+use solana_system_interface::instruction::transfer;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::commands::export::{to_csv_or_header, write_output};
+use crate::commands::fees::LAMPORTS_PER_SIGNATURE;
+use crate::commands::rpc;
+use crate::commands::OutputFormat;
+use crate::types::{nearest_rank, LandingEndpoint, LandingReport, LandingResult};
+
+/// Compute unit limit of the probe transaction: two ComputeBudget
+/// instructions and a self-transfer use about 450.
+const PROBE_CU_LIMIT: u32 = 1_000;
+/// Blockhashes are valid for ~60s; refreshing well before keeps late
+/// transactions from expiring while they are in flight.
+const BLOCKHASH_REFRESH: Duration = Duration::from_secs(20);
+const STATUS_POLL: Duration = Duration::from_millis(500);
+/// `getSignatureStatuses` accepts at most this many signatures per request.
+const STATUS_BATCH: usize = 256;
+
+pub struct LandingOptions {
+    pub keypair: PathBuf,
+    /// Transactions sent to each endpoint.
+    pub count: usize,
+    pub interval: Duration,
+    /// Compute unit prices in micro-lamports, cycled through in order.
+    pub priority_fees: Vec<u64>,
+    /// How long to wait for the last transaction to land.
+    pub timeout: Duration,
+    /// Rebroadcasts the endpoint may make for each transaction; the node's
+    /// default when `None`.
+    pub max_retries: Option<usize>,
+}
+
+#[derive(Clone, Copy)]
+struct Pending {
+    signature: Signature,
+    priority_fee: u64,
+    /// Latest slot seen before sending; `None` until `getSlot` has answered.
+    sent_slot: Option<u64>,
+    last_valid_block_height: u64,
+}
+
+#[derive(Default)]
+struct FeeOutcome {
+    sent: u64,
+    landed: u64,
+    failed: u64,
+    dropped: u64,
+    send_errors: u64,
+    slots: Vec<u64>,
+}
+
+/// Sends `count` self-transfers of a few lamports through one endpoint,
+/// cycling through the priority fees, then follows every signature until it
+/// lands or its blockhash expires.
+///
+/// `offset` keeps the transfer amounts, and so the signatures, of concurrent
+/// probes with the same payer distinct.
+pub fn probe_endpoint(
+    url: &str,
+    payer: &Keypair,
+    offset: u64,
+    options: &LandingOptions,
+) -> Result<LandingReport, Box<dyn Error>> {
+    let client = rpc::client(url)?;
+    probe_client(&client, &rpc::redact_url(url), payer, offset, options)
+}
+
+/// Runs the probe against `client`. Only a failure to fetch the first
+/// blockhash is returned: once transactions are out, failed lookups are
+/// counted and retried on the next round so that every signature is still
+/// followed.
+fn probe_client(
+    client: &RpcClient,
+    endpoint: &str,
+    payer: &Keypair,
+    offset: u64,
+    options: &LandingOptions,
+) -> Result<LandingReport, Box<dyn Error>> {
+    let config = RpcSendTransactionConfig {
+        skip_preflight: true,
+        max_retries: options.max_retries,
+        ..RpcSendTransactionConfig::default()
+    };
+
+    let mut outcomes: BTreeMap<u64, FeeOutcome> = BTreeMap::new();
+    let mut pending = Vec::new();
+    let mut rpc_errors = 0;
+    let (mut blockhash, mut last_valid_block_height) = latest_blockhash(client)?;
+    let mut fetched = Instant::now();
+    let mut slot = None;
+
+    for index in 0..options.count {
+        if index > 0 {
+            thread::sleep(options.interval);
+        }
+        if fetched.elapsed() >= BLOCKHASH_REFRESH {
+            match latest_blockhash(client) {
+                Ok(latest) => {
+                    (blockhash, last_valid_block_height) = latest;
+                    fetched = Instant::now();
+                }
+                Err(e) => rpc_error(endpoint, "getLatestBlockhash", &*e, &mut rpc_errors),
+            }
+        }
+
+        let priority_fee = options.priority_fees[index % options.priority_fees.len()];
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(PROBE_CU_LIMIT),
+                ComputeBudgetInstruction::set_compute_unit_price(priority_fee),
+                transfer(&payer.pubkey(), &payer.pubkey(), offset + index as u64 + 1),
+            ],
+            Some(&payer.pubkey()),
+            &[payer],
+            blockhash,
+        );
+        match client.get_slot_with_commitment(CommitmentConfig::processed()) {
+            Ok(current) => slot = Some(current),
+            Err(e) => rpc_error(endpoint, "getSlot", &e, &mut rpc_errors),
+        }
+        let outcome = outcomes.entry(priority_fee).or_default();
+        outcome.sent += 1;
+        match client.send_transaction_with_config(&transaction, config) {
+            Ok(signature) => pending.push(Pending {
+                signature,
+                priority_fee,
+                sent_slot: slot,
+                last_valid_block_height,
+            }),
+            Err(e) => {
+                debug!("{} rejected a landing probe: {}", endpoint, e);
+                outcome.send_errors += 1;
+            }
+        }
+    }
+
+    let deadline = Instant::now() + options.timeout;
+    let mut block_height = None;
+    while !pending.is_empty() {
+        let mut still_pending = Vec::new();
+        for batch in pending.chunks(STATUS_BATCH) {
+            let signatures: Vec<Signature> = batch.iter().map(|pending| pending.signature).collect();
+            let statuses = match client.get_signature_statuses(&signatures) {
+                Ok(statuses) => statuses.value,
+                Err(e) => {
+                    rpc_error(endpoint, "getSignatureStatuses", &e, &mut rpc_errors);
+                    still_pending.extend_from_slice(batch);
+                    continue;
+                }
+            };
+            for (sent, status) in batch.iter().zip(statuses) {
+                match status.filter(|status| status.satisfies_commitment(CommitmentConfig::confirmed())) {
+                    Some(status) => {
+                        let outcome = outcomes.entry(sent.priority_fee).or_default();
+                        outcome.landed += 1;
+                        outcome.failed += status.err.is_some() as u64;
+                        if let Some(sent_slot) = sent.sent_slot {
+                            outcome.slots.push(status.slot.saturating_sub(sent_slot));
+                        }
+                    }
+                    None => still_pending.push(*sent),
+                }
+            }
+        }
+
+        match client.get_block_height_with_commitment(CommitmentConfig::confirmed()) {
+            Ok(height) => block_height = Some(height),
+            Err(e) => rpc_error(endpoint, "getBlockHeight", &e, &mut rpc_errors),
+        }
+        let timed_out = Instant::now() >= deadline;
+        let mut waiting = Vec::new();
+        for sent in still_pending {
+            let expired = block_height.is_some_and(|height| sent.last_valid_block_height < height);
+            if timed_out || expired {
+                outcomes.entry(sent.priority_fee).or_default().dropped += 1;
+            } else {
+                waiting.push(sent);
+            }
+        }
+        pending = waiting;
+        if !pending.is_empty() {
+            thread::sleep(STATUS_POLL);
+        }
+    }
+
+    Ok(LandingReport {
+        endpoints: vec![LandingEndpoint {
+            endpoint: endpoint.to_string(),
+            rpc_errors,
+        }],
+        results: outcomes
+            .into_iter()
+            .map(|(priority_fee, outcome)| landing_result(endpoint, priority_fee, outcome))
+            .collect(),
+    })
+}
+
+fn rpc_error(endpoint: &str, method: &str, error: &dyn Error, count: &mut u64) {
+    warn!("{} failed on {} during the landing probe: {}", method, endpoint, error);
+    *count += 1;
+}
+
+fn latest_blockhash(client: &RpcClient) -> Result<(Hash, u64), Box<dyn Error>> {
+    Ok(client.get_latest_blockhash_with_commitment(CommitmentConfig::confirmed())?)
+}
+
+fn landing_result(endpoint: &str, priority_fee: u64, mut outcome: FeeOutcome) -> LandingResult {
+    let mean_slots = if outcome.slots.is_empty() {
+        0.0
+    } else {
+        outcome.slots.iter().sum::<u64>() as f64 / outcome.slots.len() as f64
+    };
+    outcome.slots.sort_unstable();
+    let slots = &outcome.slots;
+    LandingResult {
+        endpoint: endpoint.to_string(),
+        priority_fee,
+        sent: outcome.sent,
+        landed: outcome.landed,
+        failed: outcome.failed,
+        dropped: outcome.dropped,
+        send_errors: outcome.send_errors,
+        landed_ratio: if outcome.sent == 0 { 0.0 } else { outcome.landed as f64 / outcome.sent as f64 * 100.0 },
+        mean_slots,
+        p50_slots: nearest_rank(slots, 50.0).unwrap_or_default(),
+        p90_slots: nearest_rank(slots, 90.0).unwrap_or_default(),
+        max_slots: slots.last().copied().unwrap_or_default(),
+    }
+}
+
+/// Probes every endpoint at the same time with the same payer, so they are
+/// compared under the same network conditions.
+pub fn run_landing(
+    urls: &[String],
+    options: &LandingOptions,
+    format: OutputFormat,
+    output: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    if options.priority_fees.is_empty() {
+        return Err("at least one --priority-fee is needed".into());
+    }
+    let payer = read_keypair_file(&options.keypair)
+        .map_err(|e| format!("Could not read keypair {}: {}", options.keypair.display(), e))?;
+    let max_fee = options.priority_fees.iter().max().copied().unwrap_or_default();
+    let max_cost = LAMPORTS_PER_SIGNATURE + max_fee * PROBE_CU_LIMIT as u64 / 1_000_000;
+    info!(
+        "Sending {} landing probes per endpoint from {}, at most {} lamports in fees each",
+        options.count,
+        payer.pubkey(),
+        max_cost
+    );
+
+    let probes = thread::scope(|scope| {
+        let probes: Vec<_> = urls
+            .iter()
+            .enumerate()
+            .map(|(index, url)| {
+                let payer = &payer;
+                scope.spawn(move || {
+                    probe_endpoint(url, payer, (index * options.count) as u64, options).map_err(|e| e.to_string())
+                })
+            })
+            .collect();
+        probes
+            .into_iter()
+            .map(|probe| probe.join().unwrap_or_else(|_| Err("landing probe panicked".to_string())))
+            .collect::<Vec<_>>()
+    });
+
+    // Keep what the other endpoints measured when one of them fails.
+    let mut report = LandingReport::default();
+    let mut errors = Vec::new();
+    for (url, probe) in urls.iter().zip(probes) {
+        match probe {
+            Ok(probe) => {
+                report.endpoints.extend(probe.endpoints);
+                report.results.extend(probe.results);
+            }
+            Err(e) => {
+                warn!("Landing probe against {} failed: {}", rpc::redact_url(url), e);
+                errors.push(format!("{}: {}", rpc::redact_url(url), e));
+            }
+        }
+    }
+    if report.endpoints.is_empty() && !errors.is_empty() {
+        return Err(errors.join("; ").into());
+    }
+
+    let rendered = match format {
+        OutputFormat::Table => format_table(&report),
+        OutputFormat::Csv => format_csv(&report)?,
+        OutputFormat::Json => serde_json::to_string_pretty(&report)?,
+    };
+
+    write_output(&rendered, output)?;
+
+    Ok(())
+}
+
+/// The per-fee rows and the per-endpoint RPC errors as CSV sections separated
+/// by a blank line, each with its own header row.
+fn format_csv(report: &LandingReport) -> Result<String, Box<dyn Error>> {
+    Ok([to_csv_or_header(&report.results)?, to_csv_or_header(&report.endpoints)?].join("\n"))
+}
+
+fn format_table(report: &LandingReport) -> String {
+    let mut out = String::from("Transaction Landing\n");
+    for endpoint in &report.endpoints {
+        out.push_str(&format!("\nEndpoint: {} ({} RPC errors)\n", endpoint.endpoint, endpoint.rpc_errors));
+        out.push_str(&format!(
+            "{:>14} {:>6} {:>7} {:>8} {:>7} {:>7} {:>7} {:>10} {:>10} {:>10} {:>10}\n",
+            "CU price", "Sent", "Landed", "Landed %", "Failed", "Dropped", "Errors", "Mean slots", "p50 slots", "p90 slots", "Max slots"
+        ));
+        for result in report.results.iter().filter(|result| result.endpoint == endpoint.endpoint) {
+            out.push_str(&format!(
+                "{:>14} {:>6} {:>7} {:>7.1}% {:>7} {:>7} {:>7} {:>10.1} {:>10} {:>10} {:>10}\n",
+                result.priority_fee,
+                result.sent,
+                result.landed,
+                result.landed_ratio,
+                result.failed,
+                result.dropped,
+                result.send_errors,
+                result.mean_slots,
+                result.p50_slots,
+                result.p90_slots,
+                result.max_slots
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use base64::engine::general_purpose::STANDARD as BASE64;
+    use base64::Engine;
+    use serde_json::{json, Value};
+    use solana_client::client_error::{ClientErrorKind, Result as ClientResult};
+    use solana_client::rpc_client::RpcClientConfig;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_client::rpc_sender::{RpcSender, RpcTransportStats};
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// Fake endpoint answering with scripted slots, statuses and failures.
+    struct ScriptedSender {
+        calls: Mutex<HashMap<String, usize>>,
+        /// `(method, call)` pairs that fail, counting calls from zero.
+        failures: Vec<(&'static str, usize)>,
+        /// Status polls before every signature shows up as confirmed; never
+        /// when `None`.
+        lands_after: Option<usize>,
+        block_height: u64,
+    }
+
+    impl ScriptedSender {
+        fn new(lands_after: Option<usize>, block_height: u64, failures: Vec<(&'static str, usize)>) -> Self {
+            ScriptedSender {
+                calls: Mutex::new(HashMap::new()),
+                failures,
+                lands_after,
+                block_height,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl RpcSender for ScriptedSender {
+        async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+            let method = request.to_string();
+            let call = {
+                let mut calls = self.calls.lock().unwrap();
+                let call = calls.entry(method.clone()).or_default();
+                *call += 1;
+                *call - 1
+            };
+            if self.failures.contains(&(method.as_str(), call)) {
+                return Err(ClientErrorKind::Custom("HTTP status server error (503 Service Unavailable)".to_string()).into());
+            }
+
+            let context = json!({ "slot": 100 });
+            Ok(match method.as_str() {
+                "getLatestBlockhash" => json!({
+                    "context": context,
+                    "value": { "blockhash": Hash::default().to_string(), "lastValidBlockHeight": 150 },
+                }),
+                "getSlot" => json!(100),
+                "getBlockHeight" => json!(self.block_height),
+                "sendTransaction" => {
+                    let transaction = BASE64.decode(params[0].as_str().unwrap()).unwrap();
+                    json!(Signature::try_from(&transaction[1..65]).unwrap().to_string())
+                }
+                "getSignatureStatuses" => {
+                    let landed = self.lands_after.is_some_and(|polls| call >= polls);
+                    let statuses: Vec<Value> = params[0]
+                        .as_array()
+                        .unwrap()
+                        .iter()
+                        .map(|_| match landed {
+                            true => json!({
+                                "slot": 103,
+                                "confirmations": 0,
+                                "status": { "Ok": null },
+                                "err": null,
+                                "confirmationStatus": "confirmed",
+                            }),
+                            false => Value::Null,
+                        })
+                        .collect();
+                    json!({ "context": context, "value": statuses })
+                }
+                _ => return Err(ClientErrorKind::Custom(format!("unexpected {}", method)).into()),
+            })
+        }
+
+        fn get_transport_stats(&self) -> RpcTransportStats {
+            RpcTransportStats::default()
+        }
+
+        fn url(&self) -> String {
+            "scripted".to_string()
+        }
+    }
+
+    fn options(count: usize) -> LandingOptions {
+        LandingOptions {
+            keypair: PathBuf::new(),
+            count,
+            interval: Duration::ZERO,
+            priority_fees: vec![0, 1_000],
+            timeout: Duration::from_secs(10),
+            max_retries: None,
+        }
+    }
+
+    fn probe(sender: ScriptedSender, count: usize) -> Result<LandingReport, Box<dyn Error>> {
+        let client = RpcClient::new_sender(sender, RpcClientConfig::with_commitment(CommitmentConfig::confirmed()));
+        probe_client(&client, "scripted", &Keypair::new(), 0, &options(count))
+    }
+
+    #[test]
+    fn transactions_land_per_priority_fee() {
+        let report = probe(ScriptedSender::new(Some(0), 120, Vec::new()), 4).unwrap();
+        let results = &report.results;
+
+        assert_eq!(report.endpoints[0].rpc_errors, 0);
+        assert_eq!(results.len(), 2);
+        for result in results {
+            assert_eq!((result.sent, result.landed, result.dropped), (2, 2, 0));
+            assert_eq!(result.landed_ratio, 100.0);
+            assert_eq!((result.p50_slots, result.max_slots), (3, 3));
+        }
+        assert_eq!(results[0].priority_fee, 0);
+        assert_eq!(results[1].priority_fee, 1_000);
+    }
+
+    #[test]
+    fn transient_failures_are_counted_and_polling_continues() {
+        let failures = vec![("getSlot", 0), ("getSignatureStatuses", 0), ("getBlockHeight", 0)];
+        let report = probe(ScriptedSender::new(Some(1), 120, failures), 4).unwrap();
+        let results = &report.results;
+
+        assert_eq!(report.endpoints[0].rpc_errors, 3);
+        assert_eq!(results.len(), 2);
+        for result in results {
+            assert_eq!((result.sent, result.landed, result.dropped), (2, 2, 0));
+        }
+        // The first transaction was sent before any slot was known, so only
+        // the second one at that fee reports slots to land.
+        assert_eq!(results[0].max_slots, 3);
+    }
+
+    #[test]
+    fn unconfirmed_transactions_are_dropped_once_the_blockhash_expires() {
+        let failures = vec![("getBlockHeight", 0)];
+        let report = probe(ScriptedSender::new(None, 151, failures), 2).unwrap();
+
+        assert_eq!(report.endpoints[0].rpc_errors, 1);
+        for result in &report.results {
+            assert_eq!((result.sent, result.landed, result.dropped), (1, 0, 1));
+            assert_eq!(result.landed_ratio, 0.0);
+        }
+    }
+
+    #[test]
+    fn send_errors_are_not_followed() {
+        let failures = vec![("sendTransaction", 0)];
+        let results = probe(ScriptedSender::new(Some(0), 120, failures), 2).unwrap().results;
+
+        assert_eq!((results[0].sent, results[0].landed, results[0].send_errors), (1, 0, 1));
+        assert_eq!((results[1].sent, results[1].landed, results[1].send_errors), (1, 1, 0));
+    }
+
+    #[test]
+    fn first_blockhash_failure_fails_the_endpoint() {
+        let failures = vec![("getLatestBlockhash", 0)];
+        assert!(probe(ScriptedSender::new(Some(0), 120, failures), 2).is_err());
+    }
+
+    #[test]
+    fn format_table_reports_rpc_errors_once_per_endpoint() {
+        let result = |endpoint: &str, priority_fee| LandingResult {
+            endpoint: endpoint.to_string(),
+            priority_fee,
+            ..LandingResult::default()
+        };
+        let report = LandingReport {
+            endpoints: vec![
+                LandingEndpoint { endpoint: "one".to_string(), rpc_errors: 2 },
+                LandingEndpoint { endpoint: "two".to_string(), rpc_errors: 0 },
+            ],
+            results: vec![result("one", 0), result("one", 1_000), result("two", 0)],
+        };
+
+        let table = format_table(&report);
+        assert_eq!(table.matches("RPC errors").count(), 2);
+        assert!(table.contains("Endpoint: one (2 RPC errors)"));
+        assert_eq!(table.lines().filter(|line| line.trim_start().starts_with("1000 ")).count(), 1);
+
+        let csv = format_csv(&report).unwrap();
+        assert!(!csv.lines().next().unwrap().contains("rpc_errors"));
+        assert!(csv.ends_with("\n\nendpoint,rpc_errors\none,2\ntwo,0\n"));
+    }
+}
@@ -0,0 +1,215 @@
+use chrono::{DateTime, Local, Utc};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::epoch_info::EpochInfo;
+use std::collections::HashMap;
+use std::error::Error;
+
+use crate::commands::rpc;
+use crate::types::UpcomingLeader;
+
+/// Slot duration assumed when no performance samples are available.
+pub const DEFAULT_SLOT_TIME: f64 = 0.4;
+
+/// Leader schedule for a single epoch, indexed by slot. Identities are stored
+/// once and referenced by index since an epoch has ~432k slots.
+pub struct EpochLeaderSchedule {
+    pub epoch: u64,
+    pub first_slot: u64,
+    identities: Vec<String>,
+    slot_leaders: Vec<usize>,
+}
+
+impl EpochLeaderSchedule {
+    /// Groups consecutive slots with the same leader, starting at `from_slot`,
+    /// and returns the next `count` leader rotations with start times estimated
+    /// from `now`, the Unix time at which `from_slot` was observed.
+    pub fn upcoming(
+        &self,
+        from_slot: u64,
+        count: usize,
+        slot_time: f64,
+        now: f64,
+        watched: &[String],
+    ) -> Vec<UpcomingLeader> {
+        let mut upcoming: Vec<UpcomingLeader> = Vec::new();
+        let start_index = from_slot.saturating_sub(self.first_slot) as usize;
+
+        for (index, &leader) in self.slot_leaders.iter().enumerate().skip(start_index) {
+            let slot = self.first_slot + index as u64;
+            let identity = &self.identities[leader];
+
+            if let Some(last) = upcoming.last_mut() {
+                if &last.identity == identity && last.last_slot + 1 == slot {
+                    last.last_slot = slot;
+                    continue;
+                }
+            }
+            if upcoming.len() == count {
+                break;
+            }
+
+            upcoming.push(UpcomingLeader {
+                first_slot: slot,
+                last_slot: slot,
+                identity: identity.clone(),
+                start_time: now + slot.saturating_sub(from_slot) as f64 * slot_time,
+                is_watched: watched.contains(identity),
+            });
+        }
+
+        upcoming
+    }
+
+    /// Slots each identity leads in the epoch.
+    pub fn slot_counts(&self) -> HashMap<String, u64> {
+        let mut counts = HashMap::new();
+        for &leader in &self.slot_leaders {
+            *counts.entry(self.identities[leader].clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+pub fn fetch_leader_schedule(client: &RpcClient, epoch_info: &EpochInfo) -> Result<EpochLeaderSchedule, Box<dyn Error>> {
+    let first_slot = epoch_info.absolute_slot - epoch_info.slot_index;
+    let schedule = client
+        .get_leader_schedule(Some(epoch_info.absolute_slot))?
+        .ok_or("Leader schedule not available for the current epoch")?;
+
+    let mut identities = Vec::with_capacity(schedule.len());
+    let mut slot_leaders = vec![0; epoch_info.slots_in_epoch as usize];
+    let mut assigned = 0;
+
+    for (identity, slots) in schedule {
+        let leader = identities.len();
+        identities.push(identity);
+        for slot_index in slots {
+            if slot_index < slot_leaders.len() {
+                slot_leaders[slot_index] = leader;
+                assigned = assigned.max(slot_index + 1);
+            }
+        }
+    }
+
+    // Trim trailing slots without an assigned leader rather than attributing
+    // them to whichever identity happens to sit at index 0.
+    slot_leaders.truncate(assigned);
+
+    Ok(EpochLeaderSchedule {
+        epoch: epoch_info.epoch,
+        first_slot,
+        identities,
+        slot_leaders,
+    })
+}
+
+/// Measured slot duration from the latest performance sample.
+pub fn measure_slot_time(client: &RpcClient) -> Option<f64> {
+    let samples = client.get_recent_performance_samples(Some(1)).ok()?;
+    let latest = samples.first()?;
+    if latest.num_slots == 0 {
+        return None;
+    }
+    Some(latest.sample_period_secs as f64 / latest.num_slots as f64)
+}
+
+/// Formats an estimated start time as local time plus the seconds left from `now`.
+pub fn format_eta(start_time: f64, now: f64) -> String {
+    let clock = DateTime::from_timestamp_millis((start_time * 1000.0) as i64)
+        .map(|time| time.with_timezone(&Local).format("%H:%M:%S").to_string())
+        .unwrap_or_default();
+    format!("{} (+{:.1}s)", clock, (start_time - now).max(0.0))
+}
+
+pub fn run_leader_schedule(url: &str, count: usize, watched: &[String]) -> Result<(), Box<dyn Error>> {
+    let client = rpc::client(url)?;
+    let epoch_info = client.get_epoch_info()?;
+    let schedule = fetch_leader_schedule(&client, &epoch_info)?;
+    let current_slot = client.get_slot()?;
+    let slot_time = measure_slot_time(&client).unwrap_or(DEFAULT_SLOT_TIME);
+    let now = Utc::now().timestamp_millis() as f64 / 1000.0;
+
+    println!("Leader Schedule:");
+    println!("Epoch: {}", schedule.epoch);
+    println!("Current Slot: {}", current_slot);
+    println!("Slot Time: {:.3}s", slot_time);
+    println!();
+    println!("  {:<23} {:<44} Estimated Start", "Slots", "Leader");
+    for leader in schedule.upcoming(current_slot, count, slot_time, now, watched) {
+        println!(
+            "{} {:<23} {:<44} {}",
+            if leader.is_watched { "*" } else { " " },
+            format!("{} - {}", leader.first_slot, leader.last_slot),
+            leader.identity,
+            format_eta(leader.start_time, now)
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Epoch starting at slot 100 led by a, a, b, b, b, a, c, c.
+    fn schedule() -> EpochLeaderSchedule {
+        EpochLeaderSchedule {
+            epoch: 7,
+            first_slot: 100,
+            identities: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            slot_leaders: vec![0, 0, 1, 1, 1, 0, 2, 2],
+        }
+    }
+
+    fn slots(upcoming: &[UpcomingLeader]) -> Vec<(u64, u64, &str)> {
+        upcoming
+            .iter()
+            .map(|leader| (leader.first_slot, leader.last_slot, leader.identity.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn upcoming_groups_consecutive_slots_by_leader() {
+        let upcoming = schedule().upcoming(100, 10, 0.4, 0.0, &[]);
+
+        assert_eq!(slots(&upcoming), [(100, 101, "a"), (102, 104, "b"), (105, 105, "a"), (106, 107, "c")]);
+    }
+
+    #[test]
+    fn upcoming_starts_mid_rotation_and_stops_at_count() {
+        let upcoming = schedule().upcoming(103, 2, 0.5, 1_000.0, &[]);
+
+        assert_eq!(slots(&upcoming), [(103, 104, "b"), (105, 105, "a")]);
+        assert_eq!(upcoming[0].start_time, 1_000.0);
+        assert_eq!(upcoming[1].start_time, 1_001.0);
+    }
+
+    #[test]
+    fn upcoming_flags_watched_identities() {
+        let upcoming = schedule().upcoming(100, 10, 0.4, 0.0, &["c".to_string()]);
+
+        let watched: Vec<bool> = upcoming.iter().map(|leader| leader.is_watched).collect();
+        assert_eq!(watched, [false, false, false, true]);
+    }
+
+    #[test]
+    fn upcoming_is_empty_past_the_schedule() {
+        assert!(schedule().upcoming(108, 10, 0.4, 0.0, &[]).is_empty());
+    }
+
+    #[test]
+    fn slot_counts_counts_slots_per_identity() {
+        let counts = schedule().slot_counts();
+
+        assert_eq!(counts["a"], 3);
+        assert_eq!(counts["b"], 3);
+        assert_eq!(counts["c"], 2);
+    }
+
+    #[test]
+    fn format_eta_counts_down_from_now() {
+        assert!(format_eta(1_010.0, 1_000.0).ends_with("(+10.0s)"));
+        assert!(format_eta(1_000.0, 1_004.0).ends_with("(+0.0s)"));
+    }
+}
@@ -1,9 +1,38 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use crate::types::{SkipRateSort, SupplyFilter};
 
 pub mod node_health;
 pub mod network_performance;
 pub mod troubleshoot;
 pub mod monitor;
+pub mod block_production;
+pub mod leader_schedule;
+pub mod fees;
+pub mod blocks;
+pub mod congestion;
+pub mod probe;
+pub mod alerts;
+pub mod history;
+pub mod report;
+#[cfg(feature = "otel")]
+pub mod otel;
+pub mod rpc;
+pub mod rpc_stats;
+pub mod session;
+pub mod serve;
+pub mod export;
+pub mod validators;
+pub mod bench;
+pub mod pubsub;
+pub mod consistency;
+pub mod account;
+pub mod transaction;
+pub mod landing;
+pub mod supply;
+pub mod program;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -17,5 +46,302 @@ pub enum Commands {
     NodeHealth,
     NetworkPerformance,
     Troubleshoot,
-    Monitor,
-}
\ No newline at end of file
+    Monitor {
+        /// Record every RPC response the dashboard consumes to a JSON Lines session file
+        #[arg(long)]
+        record: Option<PathBuf>,
+    },
+    /// Replay a session recorded with `monitor --record` in the dashboard
+    Replay {
+        path: PathBuf,
+        /// Playback speed multiplier
+        #[arg(long, default_value_t = 1.0)]
+        speed: f64,
+    },
+    /// Report cluster and per-validator skip rates for the current epoch or a slot range
+    BlockProduction {
+        /// RPC URL (prompts when omitted)
+        #[arg(long)]
+        url: Option<String>,
+        /// First slot of the range (defaults to the start of the current epoch)
+        #[arg(long)]
+        first_slot: Option<u64>,
+        /// Last slot of the range (defaults to the latest slot)
+        #[arg(long, requires = "first_slot")]
+        last_slot: Option<u64>,
+        #[arg(long, value_enum, default_value_t = SkipRateSort::SkipRate)]
+        sort: SkipRateSort,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+        /// Write the report to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// List vote accounts with stake, commission and delinquency
+    Validators {
+        /// RPC URL (prompts when omitted)
+        #[arg(long)]
+        url: Option<String>,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+        /// Write the report to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// List the nodes visible in gossip with their addresses and versions
+    ClusterNodes {
+        /// RPC URL (prompts when omitted)
+        #[arg(long)]
+        url: Option<String>,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+        /// Write the report to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Show the next leaders in the current epoch with estimated start times
+    LeaderSchedule {
+        /// RPC URL (prompts when omitted)
+        #[arg(long)]
+        url: Option<String>,
+        /// Number of upcoming leaders to show
+        #[arg(short = 'n', long, default_value_t = 20)]
+        count: usize,
+    },
+    /// Analyze the priority fee market and recommend a compute unit price
+    Fees {
+        /// RPC URL (prompts when omitted)
+        #[arg(long)]
+        url: Option<String>,
+        /// Writable account to check local fee pressure for (repeatable)
+        #[arg(long = "account")]
+        accounts: Vec<String>,
+        /// Number of recent blocks to sample for compute unit prices
+        #[arg(long, default_value_t = 10)]
+        blocks: usize,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+        /// Write the report to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Run the probes on the configured interval and expose them as Prometheus metrics
+    Serve {
+        /// RPC URL (prompts when omitted)
+        #[arg(long)]
+        url: Option<String>,
+        #[arg(long, default_value = "0.0.0.0:9100")]
+        metrics_addr: SocketAddr,
+        /// Cluster label (detected from the genesis hash when omitted)
+        #[arg(long)]
+        cluster: Option<String>,
+    },
+    /// Write a self-contained diagnostic report (health, performance, findings and raw data)
+    Report {
+        /// RPC URL (prompts when omitted)
+        #[arg(long)]
+        url: Option<String>,
+        #[arg(long, value_enum, default_value_t = ReportFormat::Html)]
+        format: ReportFormat,
+        /// Write the report to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Query probe snapshots recorded by the TUI and the metrics exporter
+    History {
+        /// Metric to list per snapshot (repeatable); summarizes every metric when omitted
+        #[arg(long = "metric")]
+        metrics: Vec<String>,
+        /// How far back to look, e.g. 30m, 24h or 7d
+        #[arg(long, default_value = "24h", value_parser = history::parse_window)]
+        since: u64,
+        /// Only include snapshots taken against this RPC URL
+        #[arg(long)]
+        url: Option<String>,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+        /// Write the report to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Time every RPC call made by the probes and report latency and errors per method
+    RpcStats {
+        /// RPC URL (prompts when omitted)
+        #[arg(long)]
+        url: Option<String>,
+        /// Number of probe rounds to run
+        #[arg(long, default_value_t = 1)]
+        rounds: usize,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+        /// Write the report to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Compare RPC endpoints by throughput, error rates and latency percentiles per method
+    Bench {
+        /// RPC URL to benchmark (repeatable)
+        #[arg(long = "url", required = true)]
+        urls: Vec<String>,
+        /// Method to issue, optionally weighted as `method=weight`, e.g. getSlot=5 (repeatable)
+        #[arg(long = "method", value_parser = bench::parse_method_weight)]
+        methods: Vec<(bench::BenchMethod, u32)>,
+        /// Number of requests kept in flight
+        #[arg(long, default_value_t = 8)]
+        concurrency: usize,
+        /// Target requests per second per endpoint (as fast as possible when omitted)
+        #[arg(long)]
+        rate: Option<f64>,
+        /// How long to benchmark each endpoint, e.g. 30s or 5m
+        #[arg(long, default_value = "30s", value_parser = history::parse_window)]
+        duration: u64,
+        /// Per-request timeout in seconds
+        #[arg(long, default_value_t = 10)]
+        timeout: u64,
+        /// Account read by getAccountInfo
+        #[arg(long)]
+        account: Option<String>,
+        /// Program scanned by getProgramAccounts
+        #[arg(long)]
+        program: Option<String>,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+        /// Write the report to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Compare the same finalized block and account data across endpoints to spot forks and stale state
+    Consistency {
+        /// RPC URL or profile name to compare (repeat for each endpoint)
+        #[arg(long = "url", required = true)]
+        urls: Vec<String>,
+        /// Account whose data is compared at the same context slot (repeatable; rent and epoch schedule sysvars when omitted)
+        #[arg(long = "account")]
+        accounts: Vec<String>,
+        /// Finalized slots an endpoint may trail the most advanced one before it is reported as stale
+        #[arg(long, default_value_t = 150)]
+        max_lag: u64,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+        /// Write the report to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Inspect an account: balance, owner, rent exemption and decoded contents
+    Account {
+        /// Account address
+        address: String,
+        /// RPC URL (prompts when omitted)
+        #[arg(long)]
+        url: Option<String>,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+        /// Write the report to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Inspect a transaction: status, fees, compute units, program invocations, balance changes and logs
+    Tx {
+        /// Transaction signature
+        signature: String,
+        /// RPC URL (prompts when omitted)
+        #[arg(long)]
+        url: Option<String>,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+        /// Write the report to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Send cheap transactions at several priority fees and report how many land, how fast, and how many are dropped
+    Landing {
+        /// RPC URL or profile name to send through (repeatable)
+        #[arg(long = "url", required = true)]
+        urls: Vec<String>,
+        /// Funded keypair file paying for the probe transactions
+        #[arg(long)]
+        keypair: PathBuf,
+        /// Transactions to send through each endpoint
+        #[arg(long, default_value_t = 20)]
+        count: usize,
+        /// Milliseconds between transactions
+        #[arg(long, default_value_t = 500)]
+        interval_ms: u64,
+        /// Compute unit price in micro-lamports, cycled through in order (repeatable)
+        #[arg(long = "priority-fee", default_values_t = [0, 1_000, 10_000])]
+        priority_fees: Vec<u64>,
+        /// Seconds to wait for the last transaction to land
+        #[arg(long, default_value_t = 90)]
+        timeout: u64,
+        /// Times the endpoint may rebroadcast each transaction (node default when omitted)
+        #[arg(long)]
+        max_retries: Option<usize>,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+        /// Write the report to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Show the total, circulating and non-circulating supply and the largest accounts, with changes since the last run
+    Supply {
+        /// RPC URL (prompts when omitted)
+        #[arg(long)]
+        url: Option<String>,
+        /// Only list circulating or non-circulating accounts
+        #[arg(long, value_enum)]
+        filter: Option<SupplyFilter>,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+        /// Write the report to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Inspect a program: upgradeable loader state, owned account count and the cost of scanning them
+    Program {
+        /// Program id
+        program_id: String,
+        /// RPC URL (prompts when omitted)
+        #[arg(long)]
+        url: Option<String>,
+        /// Only count accounts with this data size in bytes
+        #[arg(long)]
+        data_size: Option<u64>,
+        /// Only count accounts whose data matches base58 bytes at an offset, as `offset:bytes` (repeatable)
+        #[arg(long, value_parser = program::parse_memcmp)]
+        memcmp: Vec<(usize, String)>,
+        /// Scan well-known programs with millions of accounts even without filters
+        #[arg(long)]
+        force: bool,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+        /// Write the report to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Run the probes on the configured interval and push them to an OTLP collector
+    #[cfg(feature = "otel")]
+    Push {
+        /// RPC URL (prompts when omitted)
+        #[arg(long)]
+        url: Option<String>,
+        /// OTLP/HTTP collector base URL
+        #[arg(long, default_value = "http://localhost:4318")]
+        otlp_endpoint: String,
+        /// Cluster label (detected from the genesis hash when omitted)
+        #[arg(long)]
+        cluster: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Table,
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    Html,
+    Md,
+}
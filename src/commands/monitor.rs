@@ -97,8 +97,8 @@ fn update_data(app: &mut App, client: &RpcClient) {
         app.troubleshoot_results.delinquent_validators = vote_accounts.delinquent.len() as u64;
     }
 
-    if let Ok(blocks) = client.get_blocks_with_limit(client.get_slot().unwrap_or(0), 100) {
-        app.troubleshoot_results.empty_blocks = blocks.len() as u64;
+    if let Ok(report) = crate::commands::block_production::fetch_block_production(client, None, None) {
+        app.troubleshoot_results.skip_rate = Some(report.skip_rate);
     }
 
     if let Ok(largest_accounts) = client.get_largest_accounts(None) {
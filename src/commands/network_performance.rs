@@ -1,8 +1,12 @@
+use serde::Serialize;
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::commitment_config::CommitmentConfig;
 use std::error::Error;
 use std::time::{Duration, Instant};
 
+use crate::commands::rpc;
+
+#[derive(Serialize)]
 pub struct NetworkPerformance {
     pub tps: f64,
     pub avg_block_time: Option<f64>,
@@ -20,7 +24,7 @@ impl Default for NetworkPerformance {
 }
 
 pub fn get_network_performance(url: &str) -> Result<NetworkPerformance, Box<dyn Error>> {
-    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+    let client = rpc::client(url)?;
     let mut performance = NetworkPerformance::default();
 
     // Get TPS and average block time
@@ -32,24 +36,34 @@ pub fn get_network_performance(url: &str) -> Result<NetworkPerformance, Box<dyn
     }
 
     // Estimate confirmation time
+    let confirmation_time = measure_confirmation_time(&client, Duration::from_secs(30))?;
+    performance.confirmation_time = Some(confirmation_time.ok_or("Timeout waiting for confirmation")?);
+
+    Ok(performance)
+}
+
+/// Seconds until the confirmed slot moves past the current one, as a rough
+/// confirmation time; `None` when it does not move within `timeout`.
+pub fn measure_confirmation_time(client: &RpcClient, timeout: Duration) -> Result<Option<f64>, Box<dyn Error>> {
     let start = Instant::now();
-    let start_slot = client.get_slot()?;
-    
-    loop {
-        if start.elapsed() > Duration::from_secs(30) {
-            return Err("Timeout waiting for confirmation".into());
-        }
-        
-        let current_slot = client.get_slot()?;
+    let start_slot = client.get_slot_with_commitment(CommitmentConfig::confirmed())?;
+
+    while start.elapsed() <= timeout {
+        let current_slot = client.get_slot_with_commitment(CommitmentConfig::confirmed())?;
         if current_slot > start_slot {
-            performance.confirmation_time = Some(start.elapsed().as_secs_f64());
-            break;
+            return Ok(Some(start.elapsed().as_secs_f64()));
         }
-        
+
         std::thread::sleep(Duration::from_millis(100));
     }
 
-    Ok(performance)
+    Ok(None)
+}
+
+/// Confirmation time estimated without waiting: the slots the confirmed slot
+/// trails the processed one, and at least the slot itself, at `slot_time`.
+pub fn estimate_confirmation_time(processed_slot: u64, confirmed_slot: u64, slot_time: f64) -> f64 {
+    processed_slot.saturating_sub(confirmed_slot).max(1) as f64 * slot_time
 }
 
 pub fn run_network_performance(url: &str) -> Result<(), Box<dyn Error>> {
@@ -66,3 +80,17 @@ pub fn run_network_performance(url: &str) -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimate_confirmation_time_counts_trailing_slots() {
+        assert_eq!(estimate_confirmation_time(1_002, 1_000, 0.4), 0.8);
+        // A slot takes at least its own duration to confirm.
+        assert_eq!(estimate_confirmation_time(1_000, 1_000, 0.4), 0.4);
+        // The processed slot can be read before a later confirmed one.
+        assert_eq!(estimate_confirmation_time(999, 1_000, 0.4), 0.4);
+    }
+}
@@ -1,33 +1,98 @@
+use serde::Serialize;
 use solana_client::rpc_client::RpcClient;
-use solana_sdk::commitment_config::CommitmentConfig;
+use solana_client::rpc_response::RpcVoteAccountStatus;
+use solana_program::sysvar::stake_history::{self, StakeHistory};
+use solana_sdk::account::from_account;
+use solana_sdk::epoch_info::EpochInfo;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
 use std::error::Error;
 
+use crate::commands::leader_schedule::{measure_slot_time, DEFAULT_SLOT_TIME};
+use crate::commands::pubsub::{check_websocket, format_websocket};
+use crate::commands::rpc::{self, is_rate_limited};
+use crate::types::{EpochProgress, WebSocketHealth};
+
+#[derive(Default, Serialize)]
 pub struct NodeHealth {
     pub is_responsive: bool,
+    pub rate_limited: bool,
     pub current_slot: Option<u64>,
     pub version: Option<String>,
     pub current_epoch: Option<u64>,
     pub total_nodes: Option<u64>,
+    pub epoch_progress: Option<EpochProgress>,
+    pub websocket: Option<WebSocketHealth>,
 }
 
-impl Default for NodeHealth {
-    fn default() -> Self {
-        NodeHealth {
-            is_responsive: false,
-            current_slot: None,
-            version: None,
-            current_epoch: None,
-            total_nodes: None,
-        }
+/// Epoch position, time to the next boundary and cluster stake totals.
+/// Activating/deactivating stake comes from the latest stake history sysvar
+/// entry, which the runtime writes at each epoch boundary.
+pub fn fetch_epoch_progress(
+    client: &RpcClient,
+    epoch_info: &EpochInfo,
+    vote_accounts: &RpcVoteAccountStatus,
+    slot_time: f64,
+) -> Result<EpochProgress, Box<dyn Error>> {
+    let schedule = client.get_epoch_schedule()?;
+    let slots_remaining = epoch_info.slots_in_epoch.saturating_sub(epoch_info.slot_index);
+
+    let mut progress = EpochProgress {
+        epoch: epoch_info.epoch,
+        slot_index: epoch_info.slot_index,
+        slots_in_epoch: epoch_info.slots_in_epoch,
+        percent_complete: if epoch_info.slots_in_epoch == 0 {
+            0.0
+        } else {
+            epoch_info.slot_index as f64 / epoch_info.slots_in_epoch as f64 * 100.0
+        },
+        eta_secs: slots_remaining as f64 * slot_time,
+        active_stake: vote_accounts
+            .current
+            .iter()
+            .chain(vote_accounts.delinquent.iter())
+            .map(|account| account.activated_stake)
+            .sum(),
+        slots_per_epoch: schedule.slots_per_epoch,
+        warmup: schedule.warmup,
+        first_normal_epoch: schedule.first_normal_epoch,
+        ..EpochProgress::default()
+    };
+
+    let history = client
+        .get_account(&stake_history::id())
+        .ok()
+        .and_then(|account| from_account::<StakeHistory, _>(&account));
+    if let Some((epoch, entry)) = history.as_ref().and_then(|history| history.iter().next()) {
+        progress.activating_stake = entry.activating;
+        progress.deactivating_stake = entry.deactivating;
+        progress.stake_history_epoch = Some(*epoch);
+    }
+
+    Ok(progress)
+}
+
+/// Whole SOL, or the exact fraction below 1 SOL so fees and rent minimums
+/// do not round to zero.
+pub fn format_sol(lamports: u64) -> String {
+    if lamports == 0 || lamports >= LAMPORTS_PER_SOL {
+        return format!("{:.0} SOL", lamports as f64 / LAMPORTS_PER_SOL as f64);
     }
+    let fraction = format!("{:09}", lamports);
+    format!("0.{} SOL", fraction.trim_end_matches('0'))
+}
+
+pub fn format_duration(secs: f64) -> String {
+    let secs = secs.max(0.0) as u64;
+    format!("{}h {:02}m {:02}s", secs / 3600, (secs % 3600) / 60, secs % 60)
 }
 
 pub fn get_node_health(url: &str) -> Result<NodeHealth, Box<dyn Error>> {
-    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+    let client = rpc::client(url)?;
     let mut health = NodeHealth::default();
 
-    if client.get_health().is_ok() {
-        health.is_responsive = true;
+    match client.get_health() {
+        Ok(()) => health.is_responsive = true,
+        Err(error) => health.rate_limited = is_rate_limited(error.kind()),
     }
 
     if let Ok(slot) = client.get_slot() {
@@ -38,13 +103,20 @@ pub fn get_node_health(url: &str) -> Result<NodeHealth, Box<dyn Error>> {
         health.version = Some(version.solana_core);
     }
 
+    let vote_accounts = client.get_vote_accounts().ok();
+    if let Some(validators) = &vote_accounts {
+        health.total_nodes = Some((validators.current.len() + validators.delinquent.len()) as u64);
+    }
+
     if let Ok(epoch_info) = client.get_epoch_info() {
         health.current_epoch = Some(epoch_info.epoch);
+        let slot_time = measure_slot_time(&client).unwrap_or(DEFAULT_SLOT_TIME);
+        health.epoch_progress = vote_accounts
+            .as_ref()
+            .and_then(|validators| fetch_epoch_progress(&client, &epoch_info, validators, slot_time).ok());
     }
 
-    if let Ok(validators) = client.get_vote_accounts() {
-        health.total_nodes = Some((validators.current.len() + validators.delinquent.len()) as u64);
-    }
+    health.websocket = check_websocket(url, &client);
 
     Ok(health)
 }
@@ -66,6 +138,157 @@ pub fn run_node_health(url: &str) -> Result<(), Box<dyn Error>> {
     if let Some(total_nodes) = health.total_nodes {
         println!("Total Nodes: {}", total_nodes);
     }
+    if let Some(websocket) = &health.websocket {
+        println!("WebSocket: {}", format_websocket(websocket));
+    }
+    if let Some(progress) = health.epoch_progress {
+        println!(
+            "Epoch Progress: {}/{} slots ({:.2}%)",
+            progress.slot_index, progress.slots_in_epoch, progress.percent_complete
+        );
+        println!("Time to Epoch Boundary: {}", format_duration(progress.eta_secs));
+        println!("Active Stake: {}", format_sol(progress.active_stake));
+        println!("Activating Stake: {}", format_sol(progress.activating_stake));
+        println!("Deactivating Stake: {}", format_sol(progress.deactivating_stake));
+        println!("Slots per Epoch: {}", progress.slots_per_epoch);
+        println!("Warmup: {}", if progress.warmup { "Yes" } else { "No" });
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use base64::prelude::{Engine, BASE64_STANDARD};
+    use serde_json::{json, Value};
+    use solana_client::client_error::{ClientErrorKind, Result as ClientResult};
+    use solana_client::rpc_client::RpcClientConfig;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_client::rpc_sender::{RpcSender, RpcTransportStats};
+    use solana_program::stake_history::StakeHistoryEntry;
+    use solana_sdk::account::create_account_for_test;
+    use solana_sdk::commitment_config::CommitmentConfig;
+
+    /// Fake endpoint with a fixed epoch schedule and an optional stake history.
+    struct EpochSender(Option<StakeHistory>);
+
+    #[async_trait]
+    impl RpcSender for EpochSender {
+        async fn send(&self, request: RpcRequest, _params: Value) -> ClientResult<Value> {
+            match (request, &self.0) {
+                (RpcRequest::GetEpochSchedule, _) => Ok(json!({
+                    "slotsPerEpoch": 432_000,
+                    "leaderScheduleSlotOffset": 432_000,
+                    "warmup": false,
+                    "firstNormalEpoch": 0,
+                    "firstNormalSlot": 0,
+                })),
+                (RpcRequest::GetAccountInfo, Some(history)) => {
+                    let account = create_account_for_test(history);
+                    Ok(json!({
+                        "context": { "slot": 1 },
+                        "value": {
+                            "data": [BASE64_STANDARD.encode(&account.data), "base64"],
+                            "executable": false,
+                            "lamports": account.lamports,
+                            "owner": account.owner.to_string(),
+                            "rentEpoch": 0,
+                            "space": account.data.len(),
+                        },
+                    }))
+                },
+                _ => Err(ClientErrorKind::Custom("unsupported".to_string()).into()),
+            }
+        }
+
+        fn get_transport_stats(&self) -> RpcTransportStats {
+            RpcTransportStats::default()
+        }
+
+        fn url(&self) -> String {
+            "epoch".to_string()
+        }
+    }
+
+    fn vote_account(activated_stake: u64) -> Value {
+        json!({
+            "votePubkey": "vote",
+            "nodePubkey": "node",
+            "activatedStake": activated_stake,
+            "commission": 0,
+            "epochVoteAccount": true,
+            "epochCredits": [],
+            "lastVote": 0,
+            "rootSlot": 0,
+        })
+    }
+
+    fn progress(history: Option<StakeHistory>, epoch_info: &EpochInfo) -> EpochProgress {
+        let config = RpcClientConfig::with_commitment(CommitmentConfig::confirmed());
+        let client = RpcClient::new_sender(EpochSender(history), config);
+        let vote_accounts: RpcVoteAccountStatus = serde_json::from_value(json!({
+            "current": [vote_account(600), vote_account(300)],
+            "delinquent": [vote_account(100)],
+        }))
+        .unwrap();
+
+        fetch_epoch_progress(&client, epoch_info, &vote_accounts, 0.5).unwrap()
+    }
+
+    fn epoch_info(slot_index: u64, slots_in_epoch: u64) -> EpochInfo {
+        EpochInfo {
+            epoch: 42,
+            slot_index,
+            slots_in_epoch,
+            absolute_slot: 42 * slots_in_epoch + slot_index,
+            block_height: 0,
+            transaction_count: None,
+        }
+    }
+
+    #[test]
+    fn format_sol_keeps_fractions_below_one_sol() {
+        assert_eq!(format_sol(0), "0 SOL");
+        assert_eq!(format_sol(5_000), "0.000005 SOL");
+        assert_eq!(format_sol(890_880), "0.00089088 SOL");
+        assert_eq!(format_sol(389_000 * LAMPORTS_PER_SOL), "389000 SOL");
+    }
+
+    #[test]
+    fn fetch_epoch_progress_computes_percent_eta_and_stake() {
+        let progress = progress(None, &epoch_info(108_000, 432_000));
+
+        assert_eq!(progress.epoch, 42);
+        assert_eq!(progress.percent_complete, 25.0);
+        assert_eq!(progress.eta_secs, 162_000.0);
+        assert_eq!(progress.active_stake, 1_000);
+        assert_eq!(progress.slots_per_epoch, 432_000);
+        assert!(!progress.warmup);
+        assert_eq!(progress.stake_history_epoch, None);
+        assert_eq!(progress.activating_stake, 0);
+    }
+
+    #[test]
+    fn fetch_epoch_progress_handles_an_empty_epoch() {
+        let progress = progress(None, &epoch_info(0, 0));
+
+        assert_eq!(progress.percent_complete, 0.0);
+        assert_eq!(progress.eta_secs, 0.0);
+    }
+
+    #[test]
+    fn fetch_epoch_progress_reads_the_latest_stake_history_entry() {
+        let mut history = StakeHistory::default();
+        let entry = |activating, deactivating| StakeHistoryEntry { effective: 1_000, activating, deactivating };
+        history.add(40, entry(10, 20));
+        history.add(41, entry(30, 40));
+
+        let progress = progress(Some(history), &epoch_info(1, 432_000));
+
+        assert_eq!(progress.stake_history_epoch, Some(41));
+        assert_eq!(progress.activating_stake, 30);
+        assert_eq!(progress.deactivating_stake, 40);
+    }
+}
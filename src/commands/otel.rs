@@ -0,0 +1,305 @@
+use log::{info, warn};
+use opentelemetry::metrics::{Gauge, MeterProvider};
+use opentelemetry::trace::{Span, SpanKind, Status, Tracer, TracerProvider};
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::{MetricExporter, SpanExporter, WithExportConfig};
+use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
+use opentelemetry_sdk::trace::{SdkTracer, SdkTracerProvider};
+use opentelemetry_sdk::Resource;
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::commands::probe::{probe_cluster, probe_samples, ProbeSample};
+use crate::commands::pubsub::{websocket_health, SlotStream};
+use crate::commands::rpc::{self, error_kind, observed_client, RpcCall, RpcObserver};
+use crate::commands::serve::detect_cluster;
+use crate::types::{NetworkPerformance, NodeHealth, TroubleshootResults};
+
+/// Blocks sampled per push interval for the congestion metrics.
+const SAMPLE_BLOCKS: usize = 4;
+
+/// Records one client span per RPC call.
+struct SpanObserver {
+    tracer: SdkTracer,
+}
+
+impl RpcObserver for SpanObserver {
+    fn observe_rpc(&self, call: &RpcCall) {
+        let mut attributes = vec![
+            KeyValue::new("rpc.system", "jsonrpc"),
+            KeyValue::new("rpc.method", call.method.to_string()),
+            KeyValue::new("server.address", call.endpoint.to_string()),
+            KeyValue::new("rpc.duration_ms", call.elapsed.as_secs_f64() * 1000.0),
+        ];
+        if let Some(status) = call.http_status {
+            attributes.push(KeyValue::new("http.response.status_code", status as i64));
+        }
+        if call.retries > 0 {
+            attributes.push(KeyValue::new("rpc.retries", call.retries as i64));
+        }
+        if let Some(error) = call.error {
+            attributes.push(KeyValue::new("error.type", error_kind(error)));
+        }
+
+        let mut span = self
+            .tracer
+            .span_builder(call.method.to_string())
+            .with_kind(SpanKind::Client)
+            .with_start_time(call.started)
+            .with_attributes(attributes)
+            .start(&self.tracer);
+        if let Some(error) = call.error {
+            span.set_status(Status::error(error.to_string()));
+        }
+        span.end_with_timestamp(call.started + call.elapsed);
+    }
+}
+
+/// Joins the OTLP/HTTP base endpoint with a signal path, e.g. `/v1/metrics`.
+fn signal_endpoint(endpoint: &str, path: &str) -> String {
+    format!("{}{}", endpoint.trim_end_matches('/'), path)
+}
+
+/// OTLP metric and trace pipelines, with one gauge per probe sample.
+struct Exporters {
+    meter_provider: SdkMeterProvider,
+    tracer_provider: SdkTracerProvider,
+    gauges: HashMap<&'static str, Gauge<f64>>,
+}
+
+impl Exporters {
+    fn new(otlp_endpoint: &str, resource: Resource, interval: Duration) -> Result<Self, Box<dyn Error>> {
+        let metric_exporter = MetricExporter::builder()
+            .with_http()
+            .with_endpoint(signal_endpoint(otlp_endpoint, "/v1/metrics"))
+            .build()?;
+        let meter_provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(metric_exporter).with_interval(interval).build())
+            .with_resource(resource.clone())
+            .build();
+
+        let span_exporter = SpanExporter::builder()
+            .with_http()
+            .with_endpoint(signal_endpoint(otlp_endpoint, "/v1/traces"))
+            .build()?;
+        let tracer_provider = SdkTracerProvider::builder()
+            .with_batch_exporter(span_exporter)
+            .with_resource(resource)
+            .build();
+
+        let meter = meter_provider.meter("solprobe");
+        let gauges = probe_samples(&NodeHealth::default(), &NetworkPerformance::default(), &TroubleshootResults::default())
+            .into_iter()
+            .map(|sample| {
+                let gauge = meter
+                    .f64_gauge(format!("solprobe.{}", sample.name))
+                    .with_description(sample.help)
+                    .build();
+                (sample.name, gauge)
+            })
+            .collect();
+
+        Ok(Exporters {
+            meter_provider,
+            tracer_provider,
+            gauges,
+        })
+    }
+
+    fn observer(&self) -> SpanObserver {
+        SpanObserver {
+            tracer: self.tracer_provider.tracer("solprobe"),
+        }
+    }
+
+    /// Exports the latest gauge values and the spans of this round's calls
+    /// right away instead of waiting for the next periodic export.
+    fn flush(&self) {
+        if let Err(e) = self.meter_provider.force_flush() {
+            warn!("Failed to export metrics: {}", e);
+        }
+        if let Err(e) = self.tracer_provider.force_flush() {
+            warn!("Failed to export spans: {}", e);
+        }
+    }
+
+    /// Values that were never obtained are left out rather than reported as zero.
+    fn record(&self, samples: &[ProbeSample]) {
+        for sample in samples {
+            if let (Some(gauge), Some(value)) = (self.gauges.get(sample.name), sample.value) {
+                gauge.record(value, &[]);
+            }
+        }
+    }
+}
+
+/// Runs the probes on the configured interval and pushes the results as OTLP
+/// metrics, with every RPC call exported as a trace span.
+///
+/// The OTLP exporters and the RPC client block, so the loop runs on the
+/// blocking pool rather than on a runtime worker.
+pub async fn run_push(
+    url: &str,
+    otlp_endpoint: &str,
+    cluster: Option<String>,
+    update_interval: u64,
+) -> Result<(), Box<dyn Error>> {
+    let (url, otlp_endpoint) = (url.to_string(), otlp_endpoint.to_string());
+    tokio::task::spawn_blocking(move || {
+        push_loop(&url, &otlp_endpoint, cluster, update_interval).map_err(|e| e.to_string())
+    })
+    .await??;
+    Ok(())
+}
+
+fn push_loop(url: &str, otlp_endpoint: &str, cluster: Option<String>, update_interval: u64) -> Result<(), Box<dyn Error>> {
+    let cluster = match cluster {
+        Some(cluster) => cluster,
+        None => detect_cluster(&rpc::client(url)?),
+    };
+    let endpoint = rpc::redact_url(url);
+    let interval = Duration::from_secs(update_interval.max(1));
+    let resource = Resource::builder()
+        .with_service_name("solprobe")
+        .with_attributes([
+            KeyValue::new("solana.endpoint", endpoint.clone()),
+            KeyValue::new("solana.cluster", cluster.clone()),
+        ])
+        .build();
+
+    let exporters = Exporters::new(otlp_endpoint, resource, interval)?;
+    let client = observed_client(url, vec![Arc::new(exporters.observer())])?;
+    let slot_stream = SlotStream::spawn(url)?;
+
+    info!("Pushing OTLP metrics and traces for {} ({}) to {}", endpoint, cluster, otlp_endpoint);
+
+    let mut node_health = NodeHealth::default();
+    let mut network_performance = NetworkPerformance::default();
+    let mut troubleshoot_results = TroubleshootResults::default();
+    let mut leader_schedule = None;
+
+    loop {
+        probe_cluster(
+            &client,
+            &mut node_health,
+            &mut network_performance,
+            &mut troubleshoot_results,
+            &mut leader_schedule,
+            SAMPLE_BLOCKS,
+        );
+        if let Some(stream) = &slot_stream {
+            node_health.websocket = Some(websocket_health(&client, &stream.snapshot()));
+        }
+
+        exporters.record(&probe_samples(&node_health, &network_performance, &troubleshoot_results));
+        exporters.flush();
+        thread::sleep(interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc::{channel, Receiver};
+    use std::time::SystemTime;
+
+    /// Minimal OTLP/HTTP collector: answers every request with 200 and
+    /// forwards its path and body.
+    fn collector() -> (String, Receiver<(String, Vec<u8>)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = channel();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { return };
+                let sender = sender.clone();
+                thread::spawn(move || {
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut stream = stream;
+                    loop {
+                        let mut request_line = String::new();
+                        if reader.read_line(&mut request_line).unwrap_or(0) == 0 {
+                            return;
+                        }
+                        let path = request_line.split_whitespace().nth(1).unwrap_or_default().to_string();
+                        let mut length = 0;
+                        loop {
+                            let mut header = String::new();
+                            reader.read_line(&mut header).unwrap();
+                            let header = header.trim_end();
+                            if header.is_empty() {
+                                break;
+                            }
+                            if let Some((name, value)) = header.split_once(':') {
+                                if name.eq_ignore_ascii_case("content-length") {
+                                    length = value.trim().parse().unwrap();
+                                }
+                            }
+                        }
+                        let mut body = vec![0; length];
+                        reader.read_exact(&mut body).unwrap();
+                        stream.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n").unwrap();
+                        let _ = sender.send((path, body));
+                    }
+                });
+            }
+        });
+        (endpoint, receiver)
+    }
+
+    fn received(requests: &Receiver<(String, Vec<u8>)>, path: &str) -> Vec<u8> {
+        loop {
+            let (received, body) = requests.recv_timeout(Duration::from_secs(10)).unwrap();
+            if received == path {
+                return body;
+            }
+        }
+    }
+
+    fn contains(body: &[u8], text: &str) -> bool {
+        body.windows(text.len()).any(|window| window == text.as_bytes())
+    }
+
+    #[test]
+    fn pushes_probe_metrics_and_rpc_spans() {
+        let (endpoint, requests) = collector();
+        let resource = Resource::builder().with_service_name("solprobe").build();
+        let exporters = Exporters::new(&endpoint, resource, Duration::from_secs(3600)).unwrap();
+
+        let health = NodeHealth {
+            current_slot: Some(42),
+            ..NodeHealth::default()
+        };
+        exporters.record(&probe_samples(&health, &NetworkPerformance::default(), &TroubleshootResults::default()));
+        exporters.flush();
+
+        let metrics = received(&requests, "/v1/metrics");
+        assert!(contains(&metrics, "solprobe.current_slot"));
+        assert!(contains(&metrics, "solprobe.node_responsive"));
+        // Never obtained, so never recorded.
+        assert!(!contains(&metrics, "solprobe.skip_rate_percent"));
+
+        let params = serde_json::json!([]);
+        exporters.observer().observe_rpc(&RpcCall {
+            method: "getSlot",
+            endpoint: "https://rpc.example.com/",
+            started: SystemTime::now(),
+            elapsed: Duration::from_millis(12),
+            params: &params,
+            response: None,
+            error: None,
+            http_status: Some(200),
+            retries: 0,
+        });
+        exporters.flush();
+
+        let spans = received(&requests, "/v1/traces");
+        assert!(contains(&spans, "getSlot"));
+        assert!(contains(&spans, "rpc.method"));
+    }
+}
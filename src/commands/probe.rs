@@ -0,0 +1,255 @@
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::epoch_info::EpochInfo;
+use std::time::Instant;
+use solana_transaction_status_client_types::UiConfirmedBlock;
+
+use crate::commands::block_production::fetch_block_production;
+use crate::commands::blocks::sample_recent_blocks;
+use crate::commands::congestion::analyze_blocks;
+use crate::commands::leader_schedule::{fetch_leader_schedule, EpochLeaderSchedule, DEFAULT_SLOT_TIME};
+use crate::commands::network_performance::estimate_confirmation_time;
+use crate::commands::node_health::fetch_epoch_progress;
+use crate::commands::rpc::is_rate_limited;
+use crate::commands::supply::fetch_supply;
+use crate::types::{BlockProductionReport, NetworkPerformance, NodeHealth, TroubleshootResults, WebSocketStatus};
+
+/// Data gathered while probing that callers can reuse without repeating the
+/// RPC calls (leader schedule, skip rate table, fee market).
+#[derive(Default)]
+pub struct ProbeContext {
+    pub epoch_info: Option<EpochInfo>,
+    pub block_production: Option<BlockProductionReport>,
+    pub blocks: Vec<(u64, UiConfirmedBlock)>,
+}
+
+/// Runs one pass of the node health, network performance and troubleshoot
+/// probes. Fields keep their previous value when the call behind them fails.
+/// `leader_schedule` is kept by the caller and refetched once per epoch.
+pub fn probe_cluster(
+    client: &RpcClient,
+    node_health: &mut NodeHealth,
+    network_performance: &mut NetworkPerformance,
+    troubleshoot_results: &mut TroubleshootResults,
+    leader_schedule: &mut Option<EpochLeaderSchedule>,
+    sample_blocks: usize,
+) -> ProbeContext {
+    let mut context = ProbeContext::default();
+
+    match client.get_version() {
+        Ok(version) => {
+            node_health.is_responsive = true;
+            node_health.rate_limited = false;
+            node_health.version = Some(version.solana_core);
+        },
+        Err(error) => {
+            node_health.is_responsive = false;
+            node_health.rate_limited = is_rate_limited(error.kind());
+        },
+    }
+
+    // Clients are created with confirmed commitment, so this is the confirmed slot.
+    let start = Instant::now();
+    let confirmed_slot = client.get_slot().ok();
+    if let Some(slot) = confirmed_slot {
+        node_health.rpc_latency_ms = Some(start.elapsed().as_secs_f64() * 1000.0);
+        node_health.current_slot = Some(slot);
+    }
+
+    let processed_slot = client.get_slot_with_commitment(CommitmentConfig::processed()).ok();
+    if let Some(processed_slot) = processed_slot {
+        if let Ok(max_shred_insert_slot) = client.get_max_shred_insert_slot() {
+            node_health.slot_lag = Some(max_shred_insert_slot.saturating_sub(processed_slot));
+        }
+        if let Ok(root_slot) = client.get_slot_with_commitment(CommitmentConfig::finalized()) {
+            node_health.root_slot = Some(root_slot);
+            node_health.root_lag = Some(processed_slot.saturating_sub(root_slot));
+        }
+    }
+
+    if let Ok(recent_performance) = client.get_recent_performance_samples(Some(1)) {
+        if let Some(latest) = recent_performance.first() {
+            network_performance.tps = latest.num_transactions as f64 / latest.sample_period_secs as f64;
+            network_performance.avg_block_time = Some(latest.sample_period_secs as f64 / latest.num_slots as f64);
+        }
+    }
+
+    if let (Some(processed_slot), Some(confirmed_slot), Some(slot_time)) =
+        (processed_slot, confirmed_slot, network_performance.avg_block_time)
+    {
+        network_performance.confirmation_time =
+            Some(estimate_confirmation_time(processed_slot, confirmed_slot, slot_time));
+    }
+
+    let vote_accounts = client.get_vote_accounts().ok();
+    if let Some(vote_accounts) = &vote_accounts {
+        troubleshoot_results.delinquent_validators = vote_accounts.delinquent.len() as u64;
+
+        let delinquent_stake: u64 = vote_accounts.delinquent.iter().map(|account| account.activated_stake).sum();
+        let current_stake: u64 = vote_accounts.current.iter().map(|account| account.activated_stake).sum();
+        let total_stake = delinquent_stake + current_stake;
+        troubleshoot_results.delinquent_stake = delinquent_stake;
+        troubleshoot_results.delinquent_stake_percent =
+            (total_stake > 0).then(|| delinquent_stake as f64 / total_stake as f64 * 100.0);
+    }
+
+    if let Ok(epoch_info) = client.get_epoch_info() {
+        node_health.current_epoch = Some(epoch_info.epoch);
+
+        let slot_time = network_performance.avg_block_time.unwrap_or(DEFAULT_SLOT_TIME);
+        node_health.epoch_progress = vote_accounts
+            .as_ref()
+            .and_then(|vote_accounts| fetch_epoch_progress(client, &epoch_info, vote_accounts, slot_time).ok());
+
+        if leader_schedule.as_ref().is_none_or(|schedule| schedule.epoch != epoch_info.epoch) {
+            *leader_schedule = fetch_leader_schedule(client, &epoch_info).ok();
+        }
+        context.epoch_info = Some(epoch_info);
+    }
+
+    if let Ok(cluster_nodes) = client.get_cluster_nodes() {
+        node_health.total_nodes = Some(cluster_nodes.len() as u64);
+    }
+
+    if let Ok(report) = fetch_block_production(client, None, leader_schedule.as_ref()) {
+        troubleshoot_results.skip_rate = Some(report.skip_rate);
+        context.block_production = Some(report);
+    }
+
+    if let Ok(blocks) = sample_recent_blocks(client, sample_blocks) {
+        troubleshoot_results.congestion = analyze_blocks(&blocks);
+        context.blocks = blocks;
+    }
+
+    if let Ok(supply) = fetch_supply(client) {
+        troubleshoot_results.previous_supply = troubleshoot_results.supply.replace(supply);
+    }
+
+    context
+}
+
+/// A single numeric probe value, shared by the metric exporters so every
+/// field is exported under the same name everywhere.
+pub struct ProbeSample {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub value: Option<f64>,
+}
+
+pub fn probe_samples(
+    health: &NodeHealth,
+    performance: &NetworkPerformance,
+    results: &TroubleshootResults,
+) -> Vec<ProbeSample> {
+    let sample = |name, help, value| ProbeSample { name, help, value };
+    let progress = health.epoch_progress.as_ref();
+    let websocket = health.websocket.as_ref();
+    let congestion = results.congestion.as_ref();
+    let supply = results.supply.as_ref();
+
+    vec![
+        sample("node_responsive", "Whether the node answered getVersion", Some(health.is_responsive as u8 as f64)),
+        sample("rate_limited", "Whether the endpoint rejected getVersion with HTTP 429", Some(health.rate_limited as u8 as f64)),
+        sample("current_slot", "Latest slot reported by the node", health.current_slot.map(|slot| slot as f64)),
+        sample("current_epoch", "Current epoch", health.current_epoch.map(|epoch| epoch as f64)),
+        sample("total_nodes", "Nodes in the cluster gossip table", health.total_nodes.map(|nodes| nodes as f64)),
+        sample("rpc_latency_ms", "Round trip of the latest getSlot call", health.rpc_latency_ms),
+        sample(
+            "slot_lag",
+            "Slots between the highest received shred and the processed slot",
+            health.slot_lag.map(|lag| lag as f64),
+        ),
+        sample("root_lag", "Slots between the processed slot and the root", health.root_lag.map(|lag| lag as f64)),
+        sample(
+            "websocket_healthy",
+            "Whether the PubSub endpoint acknowledges subscriptions and notifies on time",
+            websocket.map(|ws| (ws.status == WebSocketStatus::Healthy) as u8 as f64),
+        ),
+        sample(
+            "websocket_slot_lag",
+            "Slots the latest slot notification trails HTTP getSlot",
+            websocket.and_then(|ws| ws.slot_lag).map(|lag| lag as f64),
+        ),
+        sample(
+            "websocket_max_gap_ms",
+            "Longest recent gap between slot notifications",
+            websocket.and_then(|ws| ws.max_gap_ms),
+        ),
+        sample("epoch_slot_index", "Slot index within the current epoch", progress.map(|p| p.slot_index as f64)),
+        sample("epoch_slots", "Slots in the current epoch", progress.map(|p| p.slots_in_epoch as f64)),
+        sample("epoch_progress_percent", "Share of the current epoch elapsed", progress.map(|p| p.percent_complete)),
+        sample("epoch_remaining_seconds", "Estimated time to the next epoch boundary", progress.map(|p| p.eta_secs)),
+        sample("active_stake_lamports", "Stake delegated to vote accounts", progress.map(|p| p.active_stake as f64)),
+        sample(
+            "activating_stake_lamports",
+            "Activating stake from the latest stake history entry",
+            progress.map(|p| p.activating_stake as f64),
+        ),
+        sample(
+            "deactivating_stake_lamports",
+            "Deactivating stake from the latest stake history entry",
+            progress.map(|p| p.deactivating_stake as f64),
+        ),
+        sample(
+            "epoch_schedule_slots_per_epoch",
+            "Slots per epoch in the epoch schedule",
+            progress.map(|p| p.slots_per_epoch as f64),
+        ),
+        sample(
+            "epoch_schedule_warmup",
+            "Whether the epoch schedule has warmup epochs",
+            progress.map(|p| p.warmup as u8 as f64),
+        ),
+        sample(
+            "epoch_schedule_first_normal_epoch",
+            "First epoch with the full slot count",
+            progress.map(|p| p.first_normal_epoch as f64),
+        ),
+        sample("tps", "Transactions per second from the latest performance sample", Some(performance.tps)),
+        sample(
+            "avg_block_time_seconds",
+            "Average slot time from the latest performance sample",
+            performance.avg_block_time,
+        ),
+        sample(
+            "confirmation_time_seconds",
+            "Slots the confirmed slot trails the processed one, in seconds",
+            performance.confirmation_time,
+        ),
+        sample("delinquent_validators", "Delinquent vote accounts", Some(results.delinquent_validators as f64)),
+        sample(
+            "delinquent_stake_lamports",
+            "Stake delegated to delinquent vote accounts",
+            Some(results.delinquent_stake as f64),
+        ),
+        sample(
+            "delinquent_stake_percent",
+            "Share of active stake delegated to delinquent vote accounts",
+            results.delinquent_stake_percent,
+        ),
+        sample("skip_rate_percent", "Cluster skip rate for the current epoch", results.skip_rate),
+        sample("supply_total_lamports", "Total SOL supply", supply.map(|s| s.total as f64)),
+        sample("supply_circulating_lamports", "Circulating SOL supply", supply.map(|s| s.circulating as f64)),
+        sample(
+            "supply_non_circulating_lamports",
+            "SOL held by accounts excluded from the circulating supply",
+            supply.map(|s| s.non_circulating as f64),
+        ),
+        sample("congestion_score", "Congestion score from 0 to 100", congestion.map(|c| c.score as f64)),
+        sample(
+            "block_utilization_percent",
+            "Average share of the block compute limit used",
+            congestion.map(|c| c.avg_utilization),
+        ),
+        sample(
+            "block_utilization_max_percent",
+            "Highest share of the block compute limit used",
+            congestion.map(|c| c.max_utilization),
+        ),
+        sample(
+            "failed_transaction_ratio",
+            "Failed share of sampled non-vote transactions",
+            congestion.map(|c| c.failed_tx_ratio),
+        ),
+    ]
+}
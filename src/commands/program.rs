@@ -0,0 +1,516 @@
+use base64::Engine;
+use serde_json::json;
+use solana_account_decoder_client_types::{UiAccount, UiAccountData, UiAccountEncoding, UiDataSliceConfig};
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType};
+use solana_client::rpc_request::RpcRequest;
+use solana_client::rpc_response::Response;
+use solana_sdk::bs58;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk_ids::bpf_loader_upgradeable;
+use std::error::Error;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::commands::account::program_name;
+use crate::commands::export::{to_csv_or_header, write_output};
+use crate::commands::node_health::format_sol;
+use crate::commands::rpc;
+use crate::commands::OutputFormat;
+use crate::types::{AccountField, Finding, FindingSeverity, ProgramReport, ProgramScan};
+
+/// `UpgradeableLoaderState` is bincode encoded: a u32 variant tag followed
+/// by the variant's fields.
+const PROGRAM_TAG: u32 = 2;
+const PROGRAM_DATA_TAG: u32 = 3;
+/// Tag and program data address of a program account.
+const PROGRAM_LEN: usize = 36;
+/// Tag, deployment slot and optional upgrade authority ahead of the code.
+const PROGRAM_DATA_HEADER: usize = 45;
+
+/// Programs owning millions of accounts on mainnet. Most providers reject an
+/// unfiltered `getProgramAccounts` for them and the rest take minutes.
+const LARGE_PROGRAMS: [&str; 5] = [
+    "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+    "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb",
+    "Stake11111111111111111111111111111111111111",
+    "Vote111111111111111111111111111111111111111",
+    "11111111111111111111111111111111",
+];
+
+/// Scans slower or larger than this are flagged as costly.
+const SLOW_SCAN: Duration = Duration::from_secs(5);
+const LARGE_SCAN: u64 = 10_000;
+
+/// Largest memcmp filter the RPC accepts, in bytes.
+const MAX_MEMCMP_BYTES: usize = 128;
+
+/// Parses a `--memcmp` filter given as `offset:bytes`, bytes in base58.
+pub fn parse_memcmp(value: &str) -> Result<(usize, String), String> {
+    let (offset, bytes) = value
+        .split_once(':')
+        .ok_or_else(|| format!("expected offset:bytes, got '{}'", value))?;
+    let offset = offset.parse().map_err(|_| format!("invalid offset '{}'", offset))?;
+    let decoded = bs58::decode(bytes)
+        .into_vec()
+        .map_err(|e| format!("invalid base58 bytes '{}': {}", bytes, e))?;
+    if decoded.is_empty() || decoded.len() > MAX_MEMCMP_BYTES {
+        return Err(format!("memcmp bytes must be 1 to {} bytes long", MAX_MEMCMP_BYTES));
+    }
+    Ok((offset, bytes.to_string()))
+}
+
+/// Reads the first `length` bytes of an account, with the full data size in
+/// `space`, so program data accounts are inspected without downloading the
+/// deployed code.
+fn account_header(client: &RpcClient, pubkey: &Pubkey, length: usize) -> Result<Response<Option<UiAccount>>, Box<dyn Error>> {
+    Ok(client.send(
+        RpcRequest::GetAccountInfo,
+        json!([
+            pubkey.to_string(),
+            { "encoding": "base64", "dataSlice": { "offset": 0, "length": length }, "commitment": "confirmed" }
+        ]),
+    )?)
+}
+
+fn header_bytes(account: &UiAccount) -> Vec<u8> {
+    match &account.data {
+        UiAccountData::Binary(data, UiAccountEncoding::Base64) => {
+            base64::engine::general_purpose::STANDARD.decode(data).unwrap_or_default()
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn tag(bytes: &[u8]) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(..4)?.try_into().ok()?))
+}
+
+/// Reads the program account and, for the upgradeable loader, its program
+/// data header: deployment slot, upgrade authority and code size.
+pub fn fetch_program(client: &RpcClient, program_id: &Pubkey) -> Result<ProgramReport, Box<dyn Error>> {
+    let response = account_header(client, program_id, PROGRAM_LEN)?;
+    let account = response
+        .value
+        .ok_or_else(|| format!("Account {} does not exist", program_id))?;
+
+    let mut report = ProgramReport {
+        program_id: program_id.to_string(),
+        slot: response.context.slot,
+        lamports: account.lamports,
+        executable: account.executable,
+        loader: program_name(&account.owner).map(str::to_string).unwrap_or_else(|| account.owner.clone()),
+        data_len: account.space,
+        ..ProgramReport::default()
+    };
+
+    let upgradeable = account.owner == bpf_loader_upgradeable::ID.to_string();
+    let bytes = header_bytes(&account);
+    if !account.executable {
+        let remediation = if upgradeable && tag(&bytes) == Some(PROGRAM_DATA_TAG) {
+            "This is a program data account; pass the program id that points to it"
+        } else {
+            "Pass the address of a deployed program"
+        };
+        report.findings.push(Finding {
+            severity: FindingSeverity::Critical,
+            check: "Program".to_string(),
+            detail: format!("{} is not executable", program_id),
+            remediation: Some(remediation.to_string()),
+        });
+        return Ok(report);
+    }
+
+    if !upgradeable {
+        report.findings.push(Finding::ok(
+            "Upgrade authority",
+            &format!("Deployed with the {}, which does not support upgrades", report.loader),
+        ));
+        return Ok(report);
+    }
+
+    let program_data = match (tag(&bytes), bytes.get(4..PROGRAM_LEN)) {
+        (Some(PROGRAM_TAG), Some(address)) => Pubkey::try_from(address)?,
+        _ => return Err(format!("{} has no upgradeable program state", program_id).into()),
+    };
+    report.program_data = Some(program_data.to_string());
+
+    let Some(data_account) = account_header(client, &program_data, PROGRAM_DATA_HEADER)?.value else {
+        report.data_len = None;
+        report.findings.push(Finding {
+            severity: FindingSeverity::Critical,
+            check: "Program data".to_string(),
+            detail: format!("Program data account {} does not exist, the program was closed", program_data),
+            remediation: None,
+        });
+        return Ok(report);
+    };
+    let header = header_bytes(&data_account);
+    if tag(&header) != Some(PROGRAM_DATA_TAG) || header.len() < PROGRAM_DATA_HEADER {
+        return Err(format!("{} is not a program data account", program_data).into());
+    }
+    report.deployed_slot = Some(u64::from_le_bytes(header[4..12].try_into()?));
+    if header[12] == 1 {
+        report.upgrade_authority = Some(Pubkey::try_from(&header[13..PROGRAM_DATA_HEADER])?.to_string());
+    }
+    report.data_len = data_account
+        .space
+        .map(|space| space.saturating_sub(PROGRAM_DATA_HEADER as u64));
+    report.program_data_lamports = Some(data_account.lamports);
+
+    report.findings.push(match &report.upgrade_authority {
+        Some(authority) => Finding::ok("Upgrade authority", &format!("Upgradeable by {}", authority)),
+        None => Finding::ok("Upgrade authority", "Immutable, the upgrade authority was removed"),
+    });
+    Ok(report)
+}
+
+/// Counts the program's accounts matching the filters. Only the account
+/// keys are returned, but the node still visits every account the program
+/// owns.
+pub fn scan_program(
+    client: &RpcClient,
+    program_id: &Pubkey,
+    data_size: Option<u64>,
+    memcmp: &[(usize, String)],
+) -> ProgramScan {
+    let mut filters = Vec::new();
+    let mut described = Vec::new();
+    if let Some(size) = data_size {
+        filters.push(RpcFilterType::DataSize(size));
+        described.push(format!("dataSize={}", size));
+    }
+    for (offset, bytes) in memcmp {
+        filters.push(RpcFilterType::Memcmp(Memcmp::new(*offset, MemcmpEncodedBytes::Base58(bytes.clone()))));
+        described.push(format!("memcmp={}:{}", offset, bytes));
+    }
+
+    let config = RpcProgramAccountsConfig {
+        filters: (!filters.is_empty()).then_some(filters),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            data_slice: Some(UiDataSliceConfig { offset: 0, length: 0 }),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    let start = Instant::now();
+    let result = client.get_program_accounts_with_config(program_id, config);
+    let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+    match result {
+        Ok(accounts) => ProgramScan {
+            filters: described,
+            accounts: Some(accounts.len() as u64),
+            elapsed_ms,
+            error: None,
+        },
+        Err(e) => ProgramScan {
+            filters: described,
+            accounts: None,
+            elapsed_ms,
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+fn scan_findings(scan: &ProgramScan) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    if scan.filters.is_empty() {
+        findings.push(Finding {
+            severity: FindingSeverity::Warning,
+            check: "Account scan".to_string(),
+            detail: "getProgramAccounts without filters returns every account the program owns".to_string(),
+            remediation: Some("Add --data-size or --memcmp filters matching the account layout".to_string()),
+        });
+    }
+
+    let Some(accounts) = scan.accounts else {
+        findings.push(Finding {
+            severity: FindingSeverity::Critical,
+            check: "Account scan".to_string(),
+            detail: format!(
+                "getProgramAccounts failed after {:.1}s: {}",
+                scan.elapsed_ms / 1000.0,
+                scan.error.as_deref().unwrap_or("unknown error")
+            ),
+            remediation: Some(
+                "Providers often disable or time out getProgramAccounts for large programs; narrow the filters or use an indexer"
+                    .to_string(),
+            ),
+        });
+        return findings;
+    };
+
+    let slow = scan.elapsed_ms >= SLOW_SCAN.as_secs_f64() * 1000.0;
+    if slow || accounts >= LARGE_SCAN {
+        findings.push(Finding {
+            severity: FindingSeverity::Warning,
+            check: "Account scan".to_string(),
+            detail: format!(
+                "Costly scan: {} accounts matched in {:.1}s",
+                accounts,
+                scan.elapsed_ms / 1000.0
+            ),
+            remediation: Some(
+                "Avoid repeating this scan; cache the result, narrow the filters or track changes with programSubscribe"
+                    .to_string(),
+            ),
+        });
+    } else {
+        findings.push(Finding::ok(
+            "Account scan",
+            &format!("{} accounts matched in {:.0}ms", accounts, scan.elapsed_ms),
+        ));
+    }
+    findings
+}
+
+fn field(name: &str, value: String) -> AccountField {
+    AccountField {
+        name: name.to_string(),
+        value,
+    }
+}
+
+/// Program state and scan result in display order.
+pub fn program_fields(report: &ProgramReport) -> Vec<AccountField> {
+    let mut fields = vec![
+        field("Program", report.program_id.clone()),
+        field("Balance", format_sol(report.lamports)),
+        field("Executable", if report.executable { "yes" } else { "no" }.to_string()),
+        field("Loader", report.loader.clone()),
+    ];
+    if let Some(program_data) = &report.program_data {
+        fields.push(field("Program data", program_data.clone()));
+    }
+    // The authority and slot are only known once the program data was read.
+    if let Some(slot) = report.deployed_slot {
+        fields.push(field(
+            "Upgrade authority",
+            report.upgrade_authority.clone().unwrap_or_else(|| "none (immutable)".to_string()),
+        ));
+        fields.push(field("Last deployed slot", slot.to_string()));
+    }
+    if let Some(data_len) = report.data_len {
+        let name = if report.executable { "Program size" } else { "Data size" };
+        fields.push(field(name, format!("{} bytes", data_len)));
+    }
+    if let Some(lamports) = report.program_data_lamports {
+        fields.push(field("Program data balance", format_sol(lamports)));
+    }
+    if let Some(scan) = &report.scan {
+        let filters = if scan.filters.is_empty() {
+            "none".to_string()
+        } else {
+            scan.filters.join(", ")
+        };
+        fields.push(field("Scan filters", filters));
+        let accounts = match (scan.accounts, &scan.error) {
+            (Some(accounts), _) => accounts.to_string(),
+            (None, Some(error)) => format!("failed: {}", error),
+            (None, None) => "-".to_string(),
+        };
+        fields.push(field("Owned accounts", accounts));
+        fields.push(field("Scan time", format!("{:.0}ms", scan.elapsed_ms)));
+    }
+    fields
+}
+
+pub fn run_program(
+    url: &str,
+    program_id: &str,
+    data_size: Option<u64>,
+    memcmp: &[(usize, String)],
+    force: bool,
+    format: OutputFormat,
+    output: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    let client = rpc::client(url)?;
+    let pubkey = Pubkey::from_str(program_id).map_err(|e| format!("Invalid program id {}: {}", program_id, e))?;
+    let mut report = fetch_program(&client, &pubkey)?;
+
+    let unfiltered = data_size.is_none() && memcmp.is_empty();
+    let large = LARGE_PROGRAMS.contains(&report.program_id.as_str());
+    if unfiltered && large && !force {
+        report.findings.push(Finding {
+            severity: FindingSeverity::Warning,
+            check: "Account scan".to_string(),
+            detail: format!(
+                "Skipped: {} owns millions of accounts and an unfiltered scan is rejected or takes minutes",
+                program_name(&report.program_id).unwrap_or("this program")
+            ),
+            remediation: Some("Add --data-size or --memcmp filters, or pass --force to scan anyway".to_string()),
+        });
+    } else if report.executable {
+        let scan = scan_program(&client, &pubkey, data_size, memcmp);
+        report.findings.extend(scan_findings(&scan));
+        report.scan = Some(scan);
+    }
+
+    let rendered = match format {
+        OutputFormat::Table => format_table(&report),
+        OutputFormat::Csv => to_csv_or_header(&program_fields(&report))?,
+        OutputFormat::Json => serde_json::to_string_pretty(&report)?,
+    };
+
+    write_output(&rendered, output)?;
+
+    Ok(())
+}
+
+fn format_table(report: &ProgramReport) -> String {
+    let mut out = format!("Program at slot {}\n\n", report.slot);
+    for field in program_fields(report) {
+        out.push_str(&format!("{:<24} {}\n", field.name, field.value));
+    }
+
+    out.push_str("\nFindings:\n");
+    for finding in &report.findings {
+        out.push_str(&format!("[{}] {}: {}\n", finding.severity.label(), finding.check, finding.detail));
+        if let Some(remediation) = &finding.remediation {
+            out.push_str(&format!("  -> {}\n", remediation));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use base64::engine::general_purpose::STANDARD as BASE64;
+    use serde_json::Value;
+    use solana_client::client_error::{ClientErrorKind, Result as ClientResult};
+    use solana_client::rpc_client::RpcClientConfig;
+    use solana_client::rpc_sender::{RpcSender, RpcTransportStats};
+    use solana_sdk::commitment_config::CommitmentConfig;
+    use std::collections::HashMap;
+
+    /// Fake endpoint serving fixed account headers by address.
+    struct AccountsSender {
+        accounts: HashMap<String, Value>,
+    }
+
+    #[async_trait]
+    impl RpcSender for AccountsSender {
+        async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+            if request != RpcRequest::GetAccountInfo {
+                return Err(ClientErrorKind::Custom(format!("unexpected {}", request)).into());
+            }
+            let account = self.accounts.get(params[0].as_str().unwrap()).cloned().unwrap_or(Value::Null);
+            Ok(json!({ "context": { "slot": 500 }, "value": account }))
+        }
+
+        fn get_transport_stats(&self) -> RpcTransportStats {
+            RpcTransportStats::default()
+        }
+
+        fn url(&self) -> String {
+            "accounts".to_string()
+        }
+    }
+
+    fn account(data: &[u8], space: usize, executable: bool) -> Value {
+        json!({
+            "lamports": 1_000_000,
+            "data": [BASE64.encode(data), "base64"],
+            "owner": bpf_loader_upgradeable::ID.to_string(),
+            "executable": executable,
+            "rentEpoch": 0,
+            "space": space,
+        })
+    }
+
+    /// Bincode `UpgradeableLoaderState::Program` and the `ProgramData` header.
+    fn upgradeable(program_data: &Pubkey, slot: u64, authority: Option<&Pubkey>) -> (Vec<u8>, Vec<u8>) {
+        let mut program = PROGRAM_TAG.to_le_bytes().to_vec();
+        program.extend(program_data.to_bytes());
+
+        let mut header = PROGRAM_DATA_TAG.to_le_bytes().to_vec();
+        header.extend(slot.to_le_bytes());
+        match authority {
+            Some(authority) => {
+                header.push(1);
+                header.extend(authority.to_bytes());
+            }
+            None => header.extend([0; 33]),
+        }
+        (program, header)
+    }
+
+    fn fetch(accounts: Vec<(&Pubkey, Value)>, program_id: &Pubkey) -> ProgramReport {
+        let sender = AccountsSender {
+            accounts: accounts.into_iter().map(|(pubkey, account)| (pubkey.to_string(), account)).collect(),
+        };
+        let client = RpcClient::new_sender(sender, RpcClientConfig::with_commitment(CommitmentConfig::confirmed()));
+        fetch_program(&client, program_id).unwrap()
+    }
+
+    #[test]
+    fn parse_memcmp_accepts_offset_and_base58_bytes() {
+        assert_eq!(parse_memcmp("32:3Mc6vR"), Ok((32, "3Mc6vR".to_string())));
+        assert!(parse_memcmp("3Mc6vR").is_err());
+        assert!(parse_memcmp("-1:3Mc6vR").is_err());
+        assert!(parse_memcmp("0:0OIl").is_err());
+        assert!(parse_memcmp("0:").is_err());
+        let too_long = bs58::encode(vec![1u8; MAX_MEMCMP_BYTES + 1]).into_string();
+        assert!(parse_memcmp(&format!("0:{}", too_long)).is_err());
+        let longest = bs58::encode(vec![1u8; MAX_MEMCMP_BYTES]).into_string();
+        assert!(parse_memcmp(&format!("0:{}", longest)).is_ok());
+    }
+
+    #[test]
+    fn loader_headers_match_the_bincode_layout() {
+        let (program, header) = upgradeable(&Pubkey::new_unique(), 0, Some(&Pubkey::new_unique()));
+        assert_eq!(program.len(), PROGRAM_LEN);
+        assert_eq!(header.len(), PROGRAM_DATA_HEADER);
+    }
+
+    #[test]
+    fn fetch_program_reads_the_program_data_header() {
+        let program_id = Pubkey::new_unique();
+        let program_data = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let (program, header) = upgradeable(&program_data, 123_456_789, Some(&authority));
+
+        let report = fetch(
+            vec![
+                (&program_id, account(&program, PROGRAM_LEN, true)),
+                (&program_data, account(&header, PROGRAM_DATA_HEADER + 2_048, false)),
+            ],
+            &program_id,
+        );
+
+        assert_eq!(report.slot, 500);
+        assert_eq!(report.program_data, Some(program_data.to_string()));
+        assert_eq!(report.deployed_slot, Some(123_456_789));
+        assert_eq!(report.upgrade_authority, Some(authority.to_string()));
+        assert_eq!(report.data_len, Some(2_048));
+        assert_eq!(report.findings[0].severity, FindingSeverity::Ok);
+    }
+
+    #[test]
+    fn fetch_program_reports_immutable_and_closed_programs() {
+        let program_id = Pubkey::new_unique();
+        let program_data = Pubkey::new_unique();
+        let (program, header) = upgradeable(&program_data, 42, None);
+
+        let immutable = fetch(
+            vec![
+                (&program_id, account(&program, PROGRAM_LEN, true)),
+                (&program_data, account(&header, PROGRAM_DATA_HEADER + 10, false)),
+            ],
+            &program_id,
+        );
+        assert_eq!(immutable.deployed_slot, Some(42));
+        assert_eq!(immutable.upgrade_authority, None);
+        assert!(immutable.findings[0].detail.starts_with("Immutable"));
+
+        let closed = fetch(vec![(&program_id, account(&program, PROGRAM_LEN, true))], &program_id);
+        assert_eq!(closed.data_len, None);
+        assert_eq!(closed.findings[0].severity, FindingSeverity::Critical);
+    }
+}
@@ -0,0 +1,482 @@
+use futures::{SinkExt, StreamExt};
+use log::debug;
+use serde_json::{json, Value};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::commands::rpc::{self, redact_url, websocket_request};
+use crate::types::{WebSocketHealth, WebSocketStatus};
+
+/// Notifications older than this no longer count as live.
+const STALE_AFTER: Duration = Duration::from_secs(5);
+
+/// The connection is dropped and re-established after this long without a
+/// message; slot notifications normally arrive several times a second.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// Span of slot notifications the live slot rate is measured over.
+const RATE_WINDOW: Duration = Duration::from_secs(10);
+
+/// How long a one-off check listens before judging the endpoint.
+const CHECK_WINDOW: Duration = Duration::from_secs(3);
+
+/// A gap between slot notifications longer than this (about five slots)
+/// marks the endpoint as degraded.
+const DEGRADED_GAP: Duration = Duration::from_secs(2);
+
+/// Slots the notifications may trail HTTP `getSlot` before the endpoint is
+/// considered degraded.
+const DEGRADED_LAG_SLOTS: i64 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Subscription {
+    Slot,
+    Root,
+    SlotsUpdates,
+}
+
+impl Subscription {
+    pub const ALL: [Subscription; 3] = [Subscription::Slot, Subscription::Root, Subscription::SlotsUpdates];
+
+    pub fn method(self) -> &'static str {
+        match self {
+            Subscription::Slot => "slotSubscribe",
+            Subscription::Root => "rootSubscribe",
+            Subscription::SlotsUpdates => "slotsUpdatesSubscribe",
+        }
+    }
+}
+
+/// Slot progress pushed by the node's PubSub endpoint.
+#[derive(Debug, Default, Clone)]
+pub struct LiveSlots {
+    pub connected: bool,
+    /// Highest processed slot from `slotSubscribe`.
+    pub slot: Option<u64>,
+    /// Highest root from `rootSubscribe` (or the root carried by slot
+    /// notifications).
+    pub root: Option<u64>,
+    /// Highest slot the node has received a first shred for, from
+    /// `slotsUpdatesSubscribe`.
+    pub first_shred_slot: Option<u64>,
+    /// Slots per second over the last [`RATE_WINDOW`].
+    pub slot_rate: Option<f64>,
+    pub last_notification: Option<Instant>,
+    /// Subscriptions the node acknowledged on the current connection.
+    pub subscriptions: Vec<Subscription>,
+    /// Subscriptions the node answered with an error on the current connection.
+    pub rejected: Vec<Subscription>,
+    pub reconnects: u64,
+    /// Why the last connection attempt failed or was dropped.
+    pub error: Option<String>,
+    recent_slots: VecDeque<(Instant, u64)>,
+}
+
+impl LiveSlots {
+    /// Connected and receiving notifications, so the values can replace polled ones.
+    pub fn is_live(&self) -> bool {
+        self.connected && self.last_notification.is_some_and(|at| at.elapsed() < STALE_AFTER)
+    }
+
+    /// Slots between the processed slot and the root.
+    pub fn root_lag(&self) -> Option<u64> {
+        Some(self.slot?.saturating_sub(self.root?))
+    }
+
+    /// Mean and longest gap between recent slot notifications in
+    /// milliseconds; the longest includes the time since the last one.
+    pub fn cadence(&self, now: Instant) -> Option<(f64, f64)> {
+        if self.recent_slots.len() < 2 {
+            return None;
+        }
+        let (first_at, _) = self.recent_slots.front()?;
+        let (last_at, _) = self.recent_slots.back()?;
+        let mean = last_at.duration_since(*first_at).as_secs_f64() * 1000.0 / (self.recent_slots.len() - 1) as f64;
+        let max = self
+            .recent_slots
+            .iter()
+            .zip(self.recent_slots.iter().skip(1))
+            .map(|((previous, _), (next, _))| next.duration_since(*previous))
+            .chain([now.duration_since(*last_at)])
+            .max()
+            .unwrap_or_default();
+        Some((mean, max.as_secs_f64() * 1000.0))
+    }
+
+    fn record_slot(&mut self, slot: u64, now: Instant) {
+        self.slot = Some(self.slot.map_or(slot, |current| current.max(slot)));
+        self.recent_slots.push_back((now, slot));
+        while self
+            .recent_slots
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) > RATE_WINDOW)
+        {
+            self.recent_slots.pop_front();
+        }
+        self.slot_rate = match (self.recent_slots.front(), self.recent_slots.back()) {
+            (Some((first_at, first)), Some((last_at, last))) if last_at > first_at => {
+                Some(last.saturating_sub(*first) as f64 / last_at.duration_since(*first_at).as_secs_f64())
+            }
+            _ => self.slot_rate,
+        };
+    }
+
+    fn record_root(&mut self, root: u64) {
+        self.root = Some(self.root.map_or(root, |current| current.max(root)));
+    }
+
+    fn handle_message(&mut self, message: &Value) {
+        if let Some(id) = message.get("id").and_then(Value::as_u64) {
+            let subscription = id.checked_sub(1).and_then(|index| Subscription::ALL.get(index as usize));
+            match (subscription, message.get("result")) {
+                (Some(subscription), Some(_)) => self.subscriptions.push(*subscription),
+                (Some(subscription), None) => {
+                    debug!("{} rejected: {}", subscription.method(), message["error"]);
+                    self.rejected.push(*subscription);
+                }
+                (None, _) => {}
+            }
+            return;
+        }
+
+        let now = Instant::now();
+        let result = &message["params"]["result"];
+        match message["method"].as_str() {
+            Some("slotNotification") => {
+                if let Some(slot) = result["slot"].as_u64() {
+                    self.record_slot(slot, now);
+                }
+                if let Some(root) = result["root"].as_u64() {
+                    self.record_root(root);
+                }
+            }
+            Some("rootNotification") => {
+                if let Some(root) = result.as_u64() {
+                    self.record_root(root);
+                }
+            }
+            Some("slotsUpdatesNotification") => {
+                if let (Some("firstShredReceived"), Some(slot)) = (result["type"].as_str(), result["slot"].as_u64()) {
+                    self.first_shred_slot = Some(self.first_shred_slot.map_or(slot, |current| current.max(slot)));
+                }
+            }
+            _ => return,
+        }
+        self.last_notification = Some(now);
+    }
+}
+
+/// Background subscription to slot, root and slot update notifications,
+/// reconnecting with backoff whenever the connection drops.
+pub struct SlotStream {
+    state: Arc<Mutex<LiveSlots>>,
+    task: JoinHandle<()>,
+}
+
+impl SlotStream {
+    /// Starts streaming from the PubSub endpoint of `url`; `None` when
+    /// websockets are disabled or the endpoint cannot be reached over one
+    /// (e.g. a proxied profile).
+    pub fn spawn(url: &str) -> Result<Option<SlotStream>, Box<dyn Error>> {
+        if !rpc::websocket_enabled() || websocket_request(url)?.is_none() {
+            return Ok(None);
+        }
+        let state = Arc::new(Mutex::new(LiveSlots::default()));
+        let task = tokio::spawn(run(url.to_string(), state.clone()));
+        Ok(Some(SlotStream { state, task }))
+    }
+
+    pub fn snapshot(&self) -> LiveSlots {
+        self.state.lock().map(|live| live.clone()).unwrap_or_default()
+    }
+}
+
+impl Drop for SlotStream {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Judges the PubSub endpoint from the stream state, comparing the latest
+/// slot notification with HTTP `getSlot` at processed commitment.
+pub fn websocket_health(client: &RpcClient, live: &LiveSlots) -> WebSocketHealth {
+    let cadence = live.cadence(Instant::now());
+    let max_gap_ms = cadence.map(|(_, max)| max);
+    let slot_lag = match (live.connected, live.slot) {
+        (true, Some(slot)) => client
+            .get_slot_with_commitment(CommitmentConfig::processed())
+            .ok()
+            .map(|http_slot| http_slot as i64 - slot as i64),
+        _ => None,
+    };
+
+    let (status, detail) = if !live.connected {
+        (WebSocketStatus::Down, Some(live.error.clone().unwrap_or_else(|| "connecting".to_string())))
+    } else if !live.subscriptions.contains(&Subscription::Slot) {
+        (WebSocketStatus::Down, Some("slotSubscribe was not acknowledged".to_string()))
+    } else if !live.is_live() {
+        (WebSocketStatus::Down, Some(format!("no slot notification for {}s", STALE_AFTER.as_secs())))
+    } else if let Some(gap) = max_gap_ms.filter(|gap| *gap > DEGRADED_GAP.as_secs_f64() * 1000.0) {
+        (WebSocketStatus::Degraded, Some(format!("slot notifications paused for {:.1}s", gap / 1000.0)))
+    } else if let Some(lag) = slot_lag.filter(|lag| *lag > DEGRADED_LAG_SLOTS) {
+        (WebSocketStatus::Degraded, Some(format!("notifications trail getSlot by {} slots", lag)))
+    } else if !live.subscriptions.contains(&Subscription::Root) {
+        (WebSocketStatus::Degraded, Some("rootSubscribe was not acknowledged".to_string()))
+    } else {
+        (WebSocketStatus::Healthy, None)
+    };
+
+    WebSocketHealth {
+        status,
+        connected: live.connected,
+        acknowledged: live.subscriptions.iter().map(|subscription| subscription.method().to_string()).collect(),
+        rejected: live.rejected.iter().map(|subscription| subscription.method().to_string()).collect(),
+        mean_interval_ms: cadence.map(|(mean, _)| mean),
+        max_gap_ms,
+        slot_lag,
+        reconnects: live.reconnects,
+        detail,
+    }
+}
+
+/// One-line summary, e.g. `Healthy: slotSubscribe, rootSubscribe acknowledged,
+/// every 402ms (max gap 530ms), 0 slots behind getSlot`.
+pub fn format_websocket(health: &WebSocketHealth) -> String {
+    let mut parts = Vec::new();
+    if !health.acknowledged.is_empty() {
+        parts.push(format!("{} acknowledged", health.acknowledged.join(", ")));
+    }
+    if !health.rejected.is_empty() {
+        parts.push(format!("{} rejected", health.rejected.join(", ")));
+    }
+    if let (Some(mean), Some(max)) = (health.mean_interval_ms, health.max_gap_ms) {
+        parts.push(format!("every {:.0}ms (max gap {:.0}ms)", mean, max));
+    }
+    match health.slot_lag {
+        Some(lag) if lag < 0 => parts.push(format!("{} slots ahead of getSlot", -lag)),
+        Some(lag) => parts.push(format!("{} slots behind getSlot", lag)),
+        None => {}
+    }
+    if health.reconnects > 0 {
+        parts.push(format!("{} reconnects", health.reconnects));
+    }
+    if let Some(detail) = &health.detail {
+        parts.insert(0, detail.clone());
+    }
+    format!("{}: {}", health.status.label(), parts.join(", "))
+}
+
+/// One-off check for commands without a running stream: listens for a few
+/// seconds, then judges the endpoint. `None` when websockets are disabled
+/// or unavailable for `url`; an endpoint that cannot be set up is reported
+/// as down with the reason.
+pub fn check_websocket(url: &str, client: &RpcClient) -> Option<WebSocketHealth> {
+    let stream = match SlotStream::spawn(url) {
+        Ok(stream) => stream?,
+        Err(e) => {
+            return Some(WebSocketHealth {
+                status: WebSocketStatus::Down,
+                detail: Some(e.to_string()),
+                ..WebSocketHealth::default()
+            })
+        }
+    };
+    // Hand this worker's other tasks, including the stream itself, to the
+    // rest of the runtime while waiting.
+    tokio::task::block_in_place(|| std::thread::sleep(CHECK_WINDOW));
+    Some(websocket_health(client, &stream.snapshot()))
+}
+
+async fn run(url: String, state: Arc<Mutex<LiveSlots>>) {
+    let mut delay = INITIAL_RECONNECT_DELAY;
+    loop {
+        let connected = Instant::now();
+        let error = stream(&url, &state).await.err().unwrap_or_else(|| "connection closed".to_string());
+        debug!("PubSub connection to {} lost: {}", redact_url(&url), error);
+        if let Ok(mut live) = state.lock() {
+            live.connected = false;
+            live.subscriptions.clear();
+            live.rejected.clear();
+            live.error = Some(error);
+        }
+
+        // A connection that stayed up for a while starts the backoff over.
+        if connected.elapsed() > MAX_RECONNECT_DELAY {
+            delay = INITIAL_RECONNECT_DELAY;
+        }
+        tokio::time::sleep(delay).await;
+        delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+        if let Ok(mut live) = state.lock() {
+            live.reconnects += 1;
+        }
+    }
+}
+
+/// Runs one connection until it fails; credentials are resolved again on
+/// every attempt.
+async fn stream(url: &str, state: &Mutex<LiveSlots>) -> Result<(), String> {
+    let request = websocket_request(url)
+        .map_err(|e| e.to_string())?
+        .ok_or("websocket unavailable")?;
+    let (mut socket, _) = tokio_tungstenite::connect_async(request).await.map_err(|e| e.to_string())?;
+
+    for (index, subscription) in Subscription::ALL.iter().enumerate() {
+        let request = json!({ "jsonrpc": "2.0", "id": index + 1, "method": subscription.method() });
+        socket.send(Message::Text(request.to_string())).await.map_err(|e| e.to_string())?;
+    }
+    if let Ok(mut live) = state.lock() {
+        live.connected = true;
+        live.error = None;
+    }
+
+    loop {
+        let message = tokio::time::timeout(READ_TIMEOUT, socket.next())
+            .await
+            .map_err(|_| format!("no messages for {}s", READ_TIMEOUT.as_secs()))?
+            .ok_or("connection closed")?
+            .map_err(|e| e.to_string())?;
+        match message {
+            Message::Text(text) => {
+                if let (Ok(message), Ok(mut live)) = (serde_json::from_str::<Value>(&text), state.lock()) {
+                    live.handle_message(&message);
+                }
+            }
+            Message::Close(frame) => {
+                return Err(frame.map_or("closed by server".to_string(), |frame| format!("closed by server: {}", frame)));
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slot_notification(slot: u64, root: u64) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "method": "slotNotification",
+            "params": { "result": { "parent": slot - 1, "root": root, "slot": slot }, "subscription": 0 },
+        })
+    }
+
+    #[test]
+    fn handle_message_tracks_subscription_acks() {
+        let mut live = LiveSlots::default();
+        live.handle_message(&json!({ "jsonrpc": "2.0", "id": 1, "result": 7 }));
+        live.handle_message(&json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "error": { "code": -32601, "message": "Method not found" },
+        }));
+        live.handle_message(&json!({ "jsonrpc": "2.0", "id": 3, "result": 8 }));
+        live.handle_message(&json!({ "jsonrpc": "2.0", "id": 9, "result": 9 }));
+
+        assert_eq!(live.subscriptions, [Subscription::Slot, Subscription::SlotsUpdates]);
+        assert_eq!(live.rejected, [Subscription::Root]);
+        assert!(live.last_notification.is_none());
+    }
+
+    #[test]
+    fn handle_message_keeps_the_highest_slot_and_root() {
+        let mut live = LiveSlots::default();
+        live.handle_message(&slot_notification(110, 80));
+        live.handle_message(&slot_notification(105, 70));
+        assert_eq!((live.slot, live.root), (Some(110), Some(80)));
+
+        let root_notification = |root: u64| {
+            json!({ "jsonrpc": "2.0", "method": "rootNotification", "params": { "result": root, "subscription": 1 } })
+        };
+        live.handle_message(&root_notification(90));
+        live.handle_message(&root_notification(85));
+        assert_eq!(live.root, Some(90));
+        assert_eq!(live.root_lag(), Some(20));
+        assert!(live.last_notification.is_some());
+    }
+
+    #[test]
+    fn handle_message_records_first_shreds_only() {
+        let mut live = LiveSlots::default();
+        let update = |kind: &str, slot: u64| {
+            json!({
+                "jsonrpc": "2.0",
+                "method": "slotsUpdatesNotification",
+                "params": { "result": { "slot": slot, "timestamp": 0, "type": kind }, "subscription": 2 },
+            })
+        };
+        live.handle_message(&update("firstShredReceived", 120));
+        live.handle_message(&update("completed", 130));
+        live.handle_message(&update("firstShredReceived", 118));
+
+        assert_eq!(live.first_shred_slot, Some(120));
+        assert_eq!(live.slot, None);
+    }
+
+    #[test]
+    fn handle_message_ignores_unknown_methods() {
+        let mut live = LiveSlots::default();
+        live.handle_message(&json!({ "jsonrpc": "2.0", "method": "voteNotification", "params": { "result": {} } }));
+
+        assert!(live.last_notification.is_none());
+        assert_eq!(live.slot, None);
+    }
+
+    #[test]
+    fn record_slot_measures_the_rate_over_the_window() {
+        let start = Instant::now();
+        let mut live = LiveSlots::default();
+        live.record_slot(100, start);
+        assert_eq!(live.slot_rate, None);
+
+        live.record_slot(105, start + Duration::from_secs(2));
+        assert_eq!(live.slot_rate, Some(2.5));
+
+        // The first sample falls out of the window, leaving 105 -> 135 over 10s.
+        live.record_slot(125, start + Duration::from_secs(10) + Duration::from_millis(1));
+        live.record_slot(135, start + Duration::from_secs(12));
+        assert_eq!(live.recent_slots.len(), 3);
+        assert_eq!(live.slot_rate, Some(3.0));
+    }
+
+    #[test]
+    fn cadence_reports_the_mean_and_longest_gap() {
+        let start = Instant::now();
+        let mut live = LiveSlots::default();
+        live.record_slot(100, start);
+        assert_eq!(live.cadence(start), None);
+
+        live.record_slot(101, start + Duration::from_millis(400));
+        live.record_slot(102, start + Duration::from_millis(1_200));
+        assert_eq!(live.cadence(start + Duration::from_millis(1_400)), Some((600.0, 800.0)));
+
+        // A silent endpoint shows up as a growing gap since the last notification.
+        assert_eq!(live.cadence(start + Duration::from_millis(4_200)), Some((600.0, 3_000.0)));
+    }
+
+    #[test]
+    fn check_websocket_reports_setup_errors_as_down() {
+        let client = RpcClient::new("http://127.0.0.1:1".to_string());
+        let health = check_websocket("not a url", &client).expect("websockets are enabled by default");
+        assert_eq!(health.status, WebSocketStatus::Down);
+        assert!(health.detail.is_some());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn check_websocket_reports_unreachable_endpoint_as_down() {
+        let client = RpcClient::new("http://127.0.0.1:1".to_string());
+        let health = check_websocket("http://127.0.0.1:1", &client).expect("websockets are enabled by default");
+        assert_eq!(health.status, WebSocketStatus::Down);
+        assert!(!health.connected);
+        assert!(health.detail.is_some());
+    }
+}
@@ -0,0 +1,443 @@
+use chrono::Local;
+use serde::Serialize;
+use solana_client::rpc_response::RpcPerfSample;
+use std::error::Error;
+use std::path::Path;
+
+use crate::commands::export::write_output;
+use crate::commands::network_performance::{get_network_performance, NetworkPerformance};
+use crate::commands::node_health::{format_duration, format_sol, get_node_health, NodeHealth};
+use crate::commands::pubsub::format_websocket;
+use crate::commands::rpc;
+use crate::commands::troubleshoot::{run_troubleshoot, troubleshoot_findings, TroubleshootResults};
+use crate::commands::ReportFormat;
+use crate::types::{Finding, FindingSeverity};
+
+/// Performance samples (one per minute) charted in the report.
+const PERFORMANCE_SAMPLES: usize = 60;
+
+const CHART_WIDTH: f64 = 640.0;
+const CHART_HEIGHT: f64 = 160.0;
+
+#[derive(Serialize)]
+pub struct Report {
+    pub endpoint: String,
+    pub generated_at: String,
+    pub health: NodeHealth,
+    /// Missing when the node did not advance a slot within the timeout.
+    pub performance: Option<NetworkPerformance>,
+    pub troubleshoot: TroubleshootResults,
+    pub findings: Vec<Finding>,
+    /// Oldest first.
+    pub performance_samples: Vec<RpcPerfSample>,
+}
+
+pub fn build_report(url: &str) -> Result<Report, Box<dyn Error>> {
+    let client = rpc::client(url)?;
+    let health = get_node_health(url)?;
+    let performance = get_network_performance(url).ok();
+    let troubleshoot = run_troubleshoot(url)?;
+    let findings = troubleshoot_findings(&troubleshoot);
+
+    let mut performance_samples = client
+        .get_recent_performance_samples(Some(PERFORMANCE_SAMPLES))
+        .unwrap_or_default();
+    performance_samples.reverse();
+
+    Ok(Report {
+        endpoint: rpc::redact_url(url),
+        generated_at: Local::now().format("%Y-%m-%d %H:%M:%S %Z").to_string(),
+        health,
+        performance,
+        troubleshoot,
+        findings,
+        performance_samples,
+    })
+}
+
+pub fn run_report(url: &str, format: ReportFormat, output: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    let report = build_report(url)?;
+
+    let rendered = match format {
+        ReportFormat::Html => render_html(&report)?,
+        ReportFormat::Md => render_markdown(&report)?,
+    };
+
+    write_output(&rendered, output)?;
+
+    Ok(())
+}
+
+fn tps_series(samples: &[RpcPerfSample]) -> Vec<f64> {
+    samples
+        .iter()
+        .filter(|sample| sample.sample_period_secs > 0)
+        .map(|sample| sample.num_transactions as f64 / sample.sample_period_secs as f64)
+        .collect()
+}
+
+fn non_vote_tps_series(samples: &[RpcPerfSample]) -> Vec<f64> {
+    samples
+        .iter()
+        .filter(|sample| sample.sample_period_secs > 0)
+        .filter_map(|sample| {
+            sample
+                .num_non_vote_transactions
+                .map(|transactions| transactions as f64 / sample.sample_period_secs as f64)
+        })
+        .collect()
+}
+
+fn utilization_series(report: &Report) -> Vec<f64> {
+    report
+        .troubleshoot
+        .congestion
+        .as_ref()
+        .map(|congestion| congestion.blocks.iter().rev().map(|block| block.utilization).collect())
+        .unwrap_or_default()
+}
+
+/// Key/value rows shared by the HTML and Markdown node health sections.
+fn health_rows(report: &Report) -> Vec<(&'static str, String)> {
+    let health = &report.health;
+    let mut rows = vec![
+        (
+            "Responsive",
+            match (health.is_responsive, health.rate_limited) {
+                (true, _) => "Yes",
+                (false, true) => "Rate limited",
+                (false, false) => "No",
+            }
+            .to_string(),
+        ),
+        ("Version", health.version.clone().unwrap_or_else(|| "Unknown".to_string())),
+        ("Current Slot", health.current_slot.map(|slot| slot.to_string()).unwrap_or_else(|| "-".to_string())),
+        ("Current Epoch", health.current_epoch.map(|epoch| epoch.to_string()).unwrap_or_else(|| "-".to_string())),
+        ("Total Nodes", health.total_nodes.map(|nodes| nodes.to_string()).unwrap_or_else(|| "-".to_string())),
+    ];
+    if let Some(websocket) = &health.websocket {
+        rows.push(("WebSocket", format_websocket(websocket)));
+    }
+    if let Some(progress) = &health.epoch_progress {
+        rows.push((
+            "Epoch Progress",
+            format!(
+                "{}/{} slots ({:.2}%)",
+                progress.slot_index, progress.slots_in_epoch, progress.percent_complete
+            ),
+        ));
+        rows.push(("Time to Epoch Boundary", format_duration(progress.eta_secs)));
+        rows.push(("Active Stake", format_sol(progress.active_stake)));
+    }
+    rows
+}
+
+fn performance_rows(report: &Report) -> Vec<(&'static str, String)> {
+    let mut rows = Vec::new();
+    match &report.performance {
+        Some(performance) => {
+            rows.push(("TPS", format!("{:.2}", performance.tps)));
+            if let Some(block_time) = performance.avg_block_time {
+                rows.push(("Average Block Time", format!("{:.3}s", block_time)));
+            }
+            if let Some(confirmation_time) = performance.confirmation_time {
+                rows.push(("Estimated Confirmation Time", format!("{:.3}s", confirmation_time)));
+            }
+        }
+        None => rows.push(("TPS", "Unavailable (no slot progress within 30s)".to_string())),
+    }
+    if let Some(congestion) = &report.troubleshoot.congestion {
+        rows.push(("Congestion Score", format!("{}/100", congestion.score)));
+        rows.push(("Average Block CU Utilization", format!("{:.1}%", congestion.avg_utilization)));
+        rows.push(("Failed Non-Vote Transactions", format!("{:.1}%", congestion.failed_tx_ratio * 100.0)));
+    }
+    rows
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn severity_color(severity: FindingSeverity) -> &'static str {
+    match severity {
+        FindingSeverity::Ok => "#2e7d32",
+        FindingSeverity::Warning => "#ef6c00",
+        FindingSeverity::Critical => "#c62828",
+    }
+}
+
+/// Inline SVG line chart of `values`, scaled to their range.
+fn svg_chart(title: &str, values: &[f64], color: &str) -> String {
+    if values.is_empty() {
+        return format!("<p class=\"muted\">{}: no data</p>", escape_html(title));
+    }
+
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let range = if max > min { max - min } else { 1.0 };
+    let step = if values.len() > 1 { CHART_WIDTH / (values.len() - 1) as f64 } else { 0.0 };
+    let points: Vec<String> = values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let x = index as f64 * step;
+            let y = CHART_HEIGHT - (value - min) / range * (CHART_HEIGHT - 10.0) - 5.0;
+            format!("{:.1},{:.1}", x, y)
+        })
+        .collect();
+
+    format!(
+        "<figure><figcaption>{title}</figcaption>\
+         <svg viewBox=\"-60 0 {w} {h}\" width=\"{w}\" height=\"{h}\" role=\"img\">\
+         <line x1=\"0\" y1=\"0\" x2=\"0\" y2=\"{ch}\" stroke=\"#999\"/>\
+         <line x1=\"0\" y1=\"{ch}\" x2=\"{cw}\" y2=\"{ch}\" stroke=\"#999\"/>\
+         <text x=\"-5\" y=\"12\" text-anchor=\"end\">{max:.1}</text>\
+         <text x=\"-5\" y=\"{ch}\" text-anchor=\"end\">{min:.1}</text>\
+         <polyline fill=\"none\" stroke=\"{color}\" stroke-width=\"2\" points=\"{points}\"/>\
+         </svg></figure>",
+        title = escape_html(title),
+        w = CHART_WIDTH + 60.0,
+        h = CHART_HEIGHT,
+        cw = CHART_WIDTH,
+        ch = CHART_HEIGHT,
+        max = max,
+        min = min,
+        color = color,
+        points = points.join(" "),
+    )
+}
+
+/// Unicode block sparkline for Markdown, where images cannot be inlined.
+fn sparkline(values: &[f64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let range = if max > min { max - min } else { 1.0 };
+    values
+        .iter()
+        .map(|value| BARS[(((value - min) / range) * (BARS.len() - 1) as f64).round() as usize])
+        .collect()
+}
+
+pub fn render_html(report: &Report) -> Result<String, Box<dyn Error>> {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str(&format!("<title>SolProbe report: {}</title>\n", escape_html(&report.endpoint)));
+    out.push_str(
+        "<style>\
+         body{font-family:system-ui,sans-serif;margin:2rem auto;max-width:60rem;color:#222}\
+         table{border-collapse:collapse;margin-bottom:1.5rem}\
+         td,th{border:1px solid #ddd;padding:.35rem .75rem;text-align:left;vertical-align:top}\
+         th{background:#f5f5f5}\
+         figure{margin:1rem 0}figcaption{font-weight:600;margin-bottom:.25rem}\
+         svg text{font-size:11px;fill:#555}\
+         pre{background:#f5f5f5;padding:1rem;overflow-x:auto;font-size:12px}\
+         .muted{color:#777}.severity{font-weight:600}\
+         </style>\n</head>\n<body>\n",
+    );
+
+    out.push_str("<h1>SolProbe Diagnostic Report</h1>\n");
+    out.push_str(&format!(
+        "<p>Endpoint <code>{}</code>, generated {}</p>\n",
+        escape_html(&report.endpoint),
+        escape_html(&report.generated_at)
+    ));
+
+    let table = |rows: Vec<(&'static str, String)>| {
+        let mut table = String::from("<table>\n");
+        for (name, value) in rows {
+            table.push_str(&format!("<tr><th>{}</th><td>{}</td></tr>\n", name, escape_html(&value)));
+        }
+        table.push_str("</table>\n");
+        table
+    };
+
+    out.push_str("<h2>Node Health</h2>\n");
+    out.push_str(&table(health_rows(report)));
+
+    out.push_str("<h2>Performance</h2>\n");
+    out.push_str(&table(performance_rows(report)));
+    out.push_str(&svg_chart(
+        "Transactions per second (last hour, per minute)",
+        &tps_series(&report.performance_samples),
+        "#1565c0",
+    ));
+    out.push_str(&svg_chart(
+        "Non-vote transactions per second (last hour, per minute)",
+        &non_vote_tps_series(&report.performance_samples),
+        "#6a1b9a",
+    ));
+    out.push_str(&svg_chart("Block CU utilization % (sampled blocks)", &utilization_series(report), "#ef6c00"));
+
+    out.push_str("<h2>Findings</h2>\n<table>\n<tr><th>Check</th><th>Status</th><th>Detail</th><th>Remediation</th></tr>\n");
+    for finding in &report.findings {
+        out.push_str(&format!(
+            "<tr><td>{}</td><td class=\"severity\" style=\"color:{}\">{}</td><td>{}</td><td>{}</td></tr>\n",
+            escape_html(&finding.check),
+            severity_color(finding.severity),
+            finding.severity.label(),
+            escape_html(&finding.detail),
+            escape_html(finding.remediation.as_deref().unwrap_or("-"))
+        ));
+    }
+    out.push_str("</table>\n");
+
+    out.push_str("<h2>Appendix: Raw Data</h2>\n");
+    out.push_str(&format!("<pre>{}</pre>\n", escape_html(&serde_json::to_string_pretty(report)?)));
+    out.push_str("</body>\n</html>\n");
+    Ok(out)
+}
+
+pub fn render_markdown(report: &Report) -> Result<String, Box<dyn Error>> {
+    let mut out = String::new();
+    out.push_str("# SolProbe Diagnostic Report\n\n");
+    out.push_str(&format!("Endpoint `{}`, generated {}\n\n", report.endpoint, report.generated_at));
+
+    let table = |rows: Vec<(&'static str, String)>| {
+        let mut table = String::from("| Metric | Value |\n| --- | --- |\n");
+        for (name, value) in rows {
+            table.push_str(&format!("| {} | {} |\n", name, value.replace('|', "\\|")));
+        }
+        table.push('\n');
+        table
+    };
+
+    out.push_str("## Node Health\n\n");
+    out.push_str(&table(health_rows(report)));
+
+    out.push_str("## Performance\n\n");
+    out.push_str(&table(performance_rows(report)));
+    let charts = [
+        ("TPS (last hour, per minute)", tps_series(&report.performance_samples)),
+        ("Non-vote TPS (last hour, per minute)", non_vote_tps_series(&report.performance_samples)),
+        ("Block CU utilization % (sampled blocks)", utilization_series(report)),
+    ];
+    for (title, values) in charts {
+        if values.is_empty() {
+            continue;
+        }
+        let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        out.push_str(&format!(
+            "{} (min {:.1}, max {:.1}):\n\n```\n{}\n```\n\n",
+            title,
+            min,
+            max,
+            sparkline(&values)
+        ));
+    }
+
+    out.push_str("## Findings\n\n| Check | Status | Detail | Remediation |\n| --- | --- | --- | --- |\n");
+    for finding in &report.findings {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            finding.check,
+            finding.severity.label(),
+            finding.detail.replace('|', "\\|"),
+            finding.remediation.as_deref().unwrap_or("-").replace('|', "\\|")
+        ));
+    }
+    out.push('\n');
+
+    out.push_str("## Appendix: Raw Data\n\n```json\n");
+    out.push_str(&serde_json::to_string_pretty(report)?);
+    out.push_str("\n```\n");
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(num_transactions: u64, num_non_vote_transactions: u64) -> RpcPerfSample {
+        RpcPerfSample {
+            slot: 0,
+            num_transactions,
+            num_non_vote_transactions: Some(num_non_vote_transactions),
+            num_slots: 150,
+            sample_period_secs: 60,
+        }
+    }
+
+    fn report() -> Report {
+        Report {
+            endpoint: "https://rpc.example.com/?team=<ops>&env=\"prod\"".to_string(),
+            generated_at: "2024-01-01 00:00:00".to_string(),
+            health: NodeHealth {
+                is_responsive: true,
+                version: Some("2.0.1".to_string()),
+                current_slot: Some(1_000),
+                ..NodeHealth::default()
+            },
+            performance: Some(NetworkPerformance {
+                tps: 1_234.5,
+                avg_block_time: Some(0.4),
+                confirmation_time: None,
+            }),
+            troubleshoot: TroubleshootResults::default(),
+            findings: vec![
+                Finding::ok("Connection", "Node answered getHealth"),
+                Finding {
+                    severity: FindingSeverity::Critical,
+                    check: "Version <mismatch>".to_string(),
+                    detail: "Node runs 1.18 | cluster runs 2.0 & newer".to_string(),
+                    remediation: Some("Upgrade to \"2.0\"".to_string()),
+                },
+            ],
+            performance_samples: vec![sample(60_000, 6_000), sample(120_000, 12_000), sample(90_000, 9_000)],
+        }
+    }
+
+    #[test]
+    fn escape_html_escapes_markup_characters() {
+        assert_eq!(escape_html("<a href=\"x\">&</a>"), "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;");
+    }
+
+    #[test]
+    fn svg_chart_scales_points_to_the_value_range() {
+        let chart = svg_chart("TPS <live>", &[1.0, 3.0, 2.0], "#000");
+
+        assert!(chart.contains("<figcaption>TPS &lt;live&gt;</figcaption>"));
+        assert!(chart.contains("points=\"0.0,155.0 320.0,5.0 640.0,80.0\""));
+        assert!(chart.contains(">3.0</text>"));
+        assert!(chart.contains(">1.0</text>"));
+    }
+
+    #[test]
+    fn svg_chart_without_values_says_so() {
+        assert_eq!(svg_chart("TPS", &[], "#000"), "<p class=\"muted\">TPS: no data</p>");
+    }
+
+    #[test]
+    fn render_html_escapes_endpoint_and_findings() {
+        let html = render_html(&report()).unwrap();
+
+        assert!(html.contains("report: https://rpc.example.com/?team=&lt;ops&gt;&amp;env=&quot;prod&quot;</title>"));
+        assert!(!html.contains("<ops>"));
+        assert!(html.contains("<tr><th>Version</th><td>2.0.1</td></tr>"));
+        assert!(html.contains("<tr><th>TPS</th><td>1234.50</td></tr>"));
+        assert!(html.contains(
+            "<tr><td>Version &lt;mismatch&gt;</td><td class=\"severity\" style=\"color:#c62828\">Critical</td>\
+             <td>Node runs 1.18 | cluster runs 2.0 &amp; newer</td><td>Upgrade to &quot;2.0&quot;</td></tr>"
+        ));
+        assert!(html.contains("<tr><td>Connection</td><td class=\"severity\" style=\"color:#2e7d32\">OK</td>"));
+        assert!(html.contains("Transactions per second (last hour, per minute)</figcaption>"));
+        assert!(html.contains("<p class=\"muted\">Block CU utilization % (sampled blocks): no data</p>"));
+    }
+
+    #[test]
+    fn render_markdown_escapes_table_pipes_and_draws_sparklines() {
+        let markdown = render_markdown(&report()).unwrap();
+
+        assert!(markdown.contains("| Responsive | Yes |\n"));
+        assert!(markdown.contains("| Current Epoch | - |\n"));
+        assert!(markdown.contains(
+            "| Version <mismatch> | Critical | Node runs 1.18 \\| cluster runs 2.0 & newer | Upgrade to \"2.0\" |\n"
+        ));
+        assert!(markdown.contains("TPS (last hour, per minute) (min 1000.0, max 2000.0):\n\n```\n▁█▅\n```"));
+        assert!(!markdown.contains("Block CU utilization"));
+    }
+}
@@ -0,0 +1,133 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::commands::export::{to_csv_or_header, write_output};
+use crate::commands::probe::probe_cluster;
+use crate::commands::rpc::{observed_client, RpcStats, LATENCY_BUCKETS_MS};
+use crate::commands::OutputFormat;
+use crate::types::{NetworkPerformance, NodeHealth, RpcMethodStats, TroubleshootResults};
+
+/// Blocks sampled per round, matching the dashboard refresh.
+const SAMPLE_BLOCKS: usize = 4;
+
+/// Runs the dashboard probes `rounds` times and reports how long each RPC
+/// method took, to tell a slow node apart from one expensive method.
+pub fn run_rpc_stats(url: &str, rounds: usize, format: OutputFormat, output: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    let stats = Arc::new(RpcStats::default());
+    let client = observed_client(url, vec![stats.clone()])?;
+
+    let mut leader_schedule = None;
+    for _ in 0..rounds.max(1) {
+        probe_cluster(
+            &client,
+            &mut NodeHealth::default(),
+            &mut NetworkPerformance::default(),
+            &mut TroubleshootResults::default(),
+            &mut leader_schedule,
+            SAMPLE_BLOCKS,
+        );
+    }
+    let methods = stats.snapshot();
+
+    let rendered = match format {
+        OutputFormat::Table => format_table(&methods),
+        OutputFormat::Csv => to_csv_or_header(&csv_rows(&methods))?,
+        OutputFormat::Json => serde_json::to_string_pretty(&methods)?,
+    };
+
+    write_output(&rendered, output)?;
+
+    Ok(())
+}
+
+/// [`RpcMethodStats`] flattened into CSV cells: the latency histogram and the
+/// outcome counters are written as `bound xcount` lists.
+#[derive(Debug, Default, Serialize)]
+pub struct RpcMethodRow {
+    method: String,
+    calls: u64,
+    errors: u64,
+    retries: u64,
+    total_ms: f64,
+    mean_ms: f64,
+    p50_ms: f64,
+    p90_ms: f64,
+    p99_ms: f64,
+    max_ms: f64,
+    latency_buckets: String,
+    http_status: String,
+    errors_by_code: String,
+}
+
+pub fn csv_rows(methods: &[RpcMethodStats]) -> Vec<RpcMethodRow> {
+    methods
+        .iter()
+        .map(|stats| RpcMethodRow {
+            method: stats.method.clone(),
+            calls: stats.calls,
+            errors: stats.errors,
+            retries: stats.retries,
+            total_ms: stats.total_ms,
+            mean_ms: stats.mean_ms,
+            p50_ms: stats.p50_ms,
+            p90_ms: stats.p90_ms,
+            p99_ms: stats.p99_ms,
+            max_ms: stats.max_ms,
+            latency_buckets: stats
+                .latency_buckets
+                .iter()
+                .enumerate()
+                .map(|(index, count)| match LATENCY_BUCKETS_MS.get(index) {
+                    Some(bound) => format!("{} x{}", bound, count),
+                    None => format!("+Inf x{}", count),
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+            http_status: format_counts(&stats.http_status),
+            errors_by_code: format_counts(&stats.errors_by_code),
+        })
+        .collect()
+}
+
+/// Joins counters such as `{"200": 12, "429": 1}` into `200 x12, 429 x1`.
+pub fn format_counts(counts: &BTreeMap<String, u64>) -> String {
+    counts
+        .iter()
+        .map(|(key, count)| format!("{} x{}", key, count))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_table(methods: &[RpcMethodStats]) -> String {
+    let total_ms: f64 = methods.iter().map(|stats| stats.total_ms).sum();
+    let mut out = String::new();
+    out.push_str("RPC Methods (by total time):\n");
+    out.push_str(&format!(
+        "{:<34} {:>6} {:>6} {:>7} {:>10} {:>6} {:>9} {:>9} {:>9} {:>9}  {}\n",
+        "Method", "Calls", "Errors", "Retries", "Total ms", "Share", "Mean ms", "p90 ms", "p99 ms", "Max ms", "HTTP / RPC errors"
+    ));
+    for stats in methods {
+        let mut outcomes = format_counts(&stats.http_status);
+        if !stats.errors_by_code.is_empty() {
+            outcomes.push_str(&format!(" / {}", format_counts(&stats.errors_by_code)));
+        }
+        out.push_str(&format!(
+            "{:<34} {:>6} {:>6} {:>7} {:>10.1} {:>5.1}% {:>9.1} {:>9.1} {:>9.1} {:>9.1}  {}\n",
+            stats.method,
+            stats.calls,
+            stats.errors,
+            stats.retries,
+            stats.total_ms,
+            if total_ms > 0.0 { stats.total_ms / total_ms * 100.0 } else { 0.0 },
+            stats.mean_ms,
+            stats.p90_ms,
+            stats.p99_ms,
+            stats.max_ms,
+            outcomes
+        ));
+    }
+    out
+}
@@ -0,0 +1,377 @@
+use log::{info, warn};
+use prometheus::{Encoder, Gauge, GaugeVec, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry, TextEncoder};
+use solana_client::rpc_client::RpcClient;
+use std::collections::HashMap;
+use std::error::Error;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::commands::alerts::AlertManager;
+use crate::commands::history::{snapshot, HistoryStore};
+use crate::commands::probe::{probe_cluster, probe_samples};
+use crate::commands::pubsub::{websocket_health, SlotStream};
+use crate::commands::rpc::{self, error_kind, observed_client, RpcCall, RpcObserver};
+use crate::types::{NetworkPerformance, NodeHealth, TroubleshootResults};
+use crate::utils::config::Config;
+
+/// Blocks sampled per scrape interval for the congestion metrics.
+const SAMPLE_BLOCKS: usize = 4;
+
+/// Largest request head accepted from a scraper.
+const MAX_REQUEST_HEAD: usize = 16 * 1024;
+
+const MAINNET_GENESIS_HASH: &str = "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d";
+const DEVNET_GENESIS_HASH: &str = "EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG";
+const TESTNET_GENESIS_HASH: &str = "4uhcVJyU9pJkvQyS88uRDiswHXSCkY3zQawwpjk2NsNY";
+
+pub struct Metrics {
+    registry: Registry,
+    probes: HashMap<&'static str, Gauge>,
+    node_version: GaugeVec,
+    rpc_duration: HistogramVec,
+    rpc_requests: IntCounterVec,
+    rpc_errors: IntCounterVec,
+    rpc_retries: IntCounterVec,
+}
+
+impl Metrics {
+    pub fn new(endpoint: &str, cluster: &str) -> Result<Self, prometheus::Error> {
+        let registry = Registry::new();
+        let opts = |name: &str, help: &str| {
+            Opts::new(name, help)
+                .const_label("endpoint", endpoint)
+                .const_label("cluster", cluster)
+        };
+
+        let mut probes = HashMap::new();
+        let defaults = probe_samples(&NodeHealth::default(), &NetworkPerformance::default(), &TroubleshootResults::default());
+        for sample in defaults {
+            let gauge = Gauge::with_opts(opts(&format!("solprobe_{}", sample.name), sample.help))?;
+            registry.register(Box::new(gauge.clone()))?;
+            probes.insert(sample.name, gauge);
+        }
+
+        let node_version = GaugeVec::new(
+            opts("solprobe_node_version_info", "solana-core version reported by the node"),
+            &["version"],
+        )?;
+        let rpc_duration = HistogramVec::new(
+            HistogramOpts::from(opts("solprobe_rpc_request_duration_seconds", "RPC request latency by method")),
+            &["method"],
+        )?;
+        let rpc_requests = IntCounterVec::new(opts("solprobe_rpc_requests_total", "RPC requests by method"), &["method"])?;
+        let rpc_errors = IntCounterVec::new(
+            opts("solprobe_rpc_errors_total", "Failed RPC requests by method and error kind"),
+            &["method", "kind"],
+        )?;
+        let rpc_retries = IntCounterVec::new(
+            opts("solprobe_rpc_retries_total", "HTTP attempts beyond the first by method, e.g. after a 429"),
+            &["method"],
+        )?;
+        registry.register(Box::new(node_version.clone()))?;
+        registry.register(Box::new(rpc_duration.clone()))?;
+        registry.register(Box::new(rpc_requests.clone()))?;
+        registry.register(Box::new(rpc_errors.clone()))?;
+        registry.register(Box::new(rpc_retries.clone()))?;
+
+        Ok(Metrics {
+            registry,
+            probes,
+            node_version,
+            rpc_duration,
+            rpc_requests,
+            rpc_errors,
+            rpc_retries,
+        })
+    }
+
+    /// Values that were never obtained are exported as NaN rather than zero.
+    pub fn observe_probes(&self, health: &NodeHealth, performance: &NetworkPerformance, results: &TroubleshootResults) {
+        for sample in probe_samples(health, performance, results) {
+            if let Some(gauge) = self.probes.get(sample.name) {
+                gauge.set(sample.value.unwrap_or(f64::NAN));
+            }
+        }
+
+        self.node_version.reset();
+        if let Some(version) = &health.version {
+            self.node_version.with_label_values(&[version]).set(1.0);
+        }
+    }
+
+    pub fn encode(&self) -> Result<Vec<u8>, prometheus::Error> {
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        Ok(buffer)
+    }
+}
+
+impl RpcObserver for Metrics {
+    fn observe_rpc(&self, call: &RpcCall) {
+        self.rpc_duration.with_label_values(&[call.method]).observe(call.elapsed.as_secs_f64());
+        self.rpc_requests.with_label_values(&[call.method]).inc();
+        if call.retries > 0 {
+            self.rpc_retries.with_label_values(&[call.method]).inc_by(call.retries as u64);
+        }
+        if let Some(error) = call.error {
+            self.rpc_errors.with_label_values(&[call.method, error_kind(error)]).inc();
+        }
+    }
+}
+
+/// Maps well-known genesis hashes to cluster names.
+pub fn detect_cluster(client: &RpcClient) -> String {
+    match client.get_genesis_hash().map(|hash| hash.to_string()).as_deref() {
+        Ok(MAINNET_GENESIS_HASH) => "mainnet-beta".to_string(),
+        Ok(DEVNET_GENESIS_HASH) => "devnet".to_string(),
+        Ok(TESTNET_GENESIS_HASH) => "testnet".to_string(),
+        Ok(_) => "custom".to_string(),
+        Err(_) => "unknown".to_string(),
+    }
+}
+
+async fn serve_metrics(listener: TcpListener, metrics: Arc<Metrics>) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                let metrics = metrics.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = handle_scrape(stream, &metrics).await {
+                        warn!("Failed to serve metrics request: {}", e);
+                    }
+                });
+            }
+            Err(e) => warn!("Failed to accept metrics connection: {}", e),
+        }
+    }
+}
+
+/// Reads the request line and headers, up to the blank line that ends them.
+async fn read_request_head(stream: &mut TcpStream) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let mut head = Vec::new();
+    let mut buffer = [0u8; 1024];
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        if head.len() > MAX_REQUEST_HEAD {
+            return Err("request headers too large".into());
+        }
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        head.extend_from_slice(&buffer[..read]);
+    }
+    Ok(String::from_utf8_lossy(&head).into_owned())
+}
+
+async fn handle_scrape(mut stream: TcpStream, metrics: &Metrics) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let request = read_request_head(&mut stream).await?;
+
+    let (status, content_type, body) = if request.starts_with("GET /metrics") {
+        ("200 OK", TextEncoder::new().format_type().to_string(), metrics.encode()?)
+    } else {
+        ("404 Not Found", "text/plain".to_string(), b"Not Found\n".to_vec())
+    };
+
+    let header = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    );
+    stream.write_all(header.as_bytes()).await?;
+    stream.write_all(&body).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Serves `/metrics` and probes the endpoint every update interval. The RPC
+/// client blocks, so the probe loop runs on the blocking pool rather than on
+/// a runtime worker.
+pub async fn run_serve(
+    url: &str,
+    metrics_addr: SocketAddr,
+    cluster: Option<String>,
+    config: &Config,
+) -> Result<(), Box<dyn Error>> {
+    let cluster = match cluster {
+        Some(cluster) => cluster,
+        None => detect_cluster(&rpc::client(url)?),
+    };
+    let endpoint = rpc::redact_url(url);
+    let metrics = Arc::new(Metrics::new(&endpoint, &cluster)?);
+    let client = observed_client(url, vec![metrics.clone()])?;
+    let slot_stream = SlotStream::spawn(url)?;
+
+    let listener = TcpListener::bind(metrics_addr).await?;
+    info!("Serving metrics for {} ({}) on http://{}/metrics", endpoint, cluster, metrics_addr);
+    tokio::spawn(serve_metrics(listener, metrics.clone()));
+
+    let alerts = AlertManager::new(url, &config.alerts);
+    let history = if config.history.enabled {
+        Some(HistoryStore::open(&config.history)?)
+    } else {
+        None
+    };
+    let interval = Duration::from_secs(config.update_interval.max(1));
+    let url = url.to_string();
+    tokio::task::spawn_blocking(move || probe_loop(&url, &client, &metrics, slot_stream, alerts, history, interval))
+        .await?;
+    Ok(())
+}
+
+fn probe_loop(
+    url: &str,
+    client: &RpcClient,
+    metrics: &Metrics,
+    slot_stream: Option<SlotStream>,
+    mut alerts: AlertManager,
+    mut history: Option<HistoryStore>,
+    interval: Duration,
+) {
+    let mut node_health = NodeHealth::default();
+    let mut network_performance = NetworkPerformance::default();
+    let mut troubleshoot_results = TroubleshootResults::default();
+    let mut leader_schedule = None;
+
+    loop {
+        let started = Instant::now();
+        probe_cluster(
+            client,
+            &mut node_health,
+            &mut network_performance,
+            &mut troubleshoot_results,
+            &mut leader_schedule,
+            SAMPLE_BLOCKS,
+        );
+        if let Some(stream) = &slot_stream {
+            node_health.websocket = Some(websocket_health(client, &stream.snapshot()));
+        }
+        metrics.observe_probes(&node_health, &network_performance, &troubleshoot_results);
+
+        let samples = probe_samples(&node_health, &network_performance, &troubleshoot_results);
+        if let Some(store) = &mut history {
+            if let Err(e) = store.append(&snapshot(url, &samples)) {
+                warn!("Failed to record history: {}", e);
+            }
+        }
+
+        let events = alerts.evaluate(&samples);
+        for event in &events {
+            info!("{}", event.summary);
+        }
+        alerts.notify(&events);
+        if let Some(error) = alerts.take_error() {
+            warn!("Failed to deliver alert: {}", error);
+        }
+
+        thread::sleep(interval.saturating_sub(started.elapsed()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use serde_json::{json, Value};
+    use solana_client::client_error::{ClientErrorKind, Result as ClientResult};
+    use solana_client::rpc_client::RpcClientConfig;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_client::rpc_sender::{RpcSender, RpcTransportStats};
+    use solana_sdk::commitment_config::CommitmentConfig;
+
+    /// Fake endpoint answering `getGenesisHash`, or failing without a hash.
+    struct GenesisSender(Option<&'static str>);
+
+    #[async_trait]
+    impl RpcSender for GenesisSender {
+        async fn send(&self, request: RpcRequest, _params: Value) -> ClientResult<Value> {
+            match (request, self.0) {
+                (RpcRequest::GetGenesisHash, Some(hash)) => Ok(json!(hash)),
+                _ => Err(ClientErrorKind::Custom("connection refused".to_string()).into()),
+            }
+        }
+
+        fn get_transport_stats(&self) -> RpcTransportStats {
+            RpcTransportStats::default()
+        }
+
+        fn url(&self) -> String {
+            "genesis".to_string()
+        }
+    }
+
+    fn cluster(genesis_hash: Option<&'static str>) -> String {
+        let config = RpcClientConfig::with_commitment(CommitmentConfig::confirmed());
+        let client = RpcClient::new_sender(GenesisSender(genesis_hash), config);
+        detect_cluster(&client)
+    }
+
+    #[test]
+    fn detect_cluster_maps_genesis_hashes() {
+        assert_eq!(cluster(Some(MAINNET_GENESIS_HASH)), "mainnet-beta");
+        assert_eq!(cluster(Some(DEVNET_GENESIS_HASH)), "devnet");
+        assert_eq!(cluster(Some(TESTNET_GENESIS_HASH)), "testnet");
+        assert_eq!(cluster(Some("11111111111111111111111111111111")), "custom");
+        assert_eq!(cluster(None), "unknown");
+    }
+
+    #[test]
+    fn metrics_encode_probe_samples_with_endpoint_and_cluster_labels() {
+        let metrics = Metrics::new("https://rpc.example.com/", "devnet").unwrap();
+        let health = NodeHealth {
+            current_slot: Some(1_234),
+            version: Some("2.2.0".to_string()),
+            ..NodeHealth::default()
+        };
+        let performance = NetworkPerformance {
+            tps: 2_500.0,
+            ..NetworkPerformance::default()
+        };
+        metrics.observe_probes(&health, &performance, &TroubleshootResults::default());
+
+        let text = String::from_utf8(metrics.encode().unwrap()).unwrap();
+        let line = |name: &str| {
+            text.lines()
+                .find(|line| line.starts_with(&format!("{}{{", name)))
+                .unwrap_or_else(|| panic!("{} missing from\n{}", name, text))
+                .to_string()
+        };
+        for sample in probe_samples(&health, &performance, &TroubleshootResults::default()) {
+            let line = line(&format!("solprobe_{}", sample.name));
+            assert!(line.contains("cluster=\"devnet\""), "{}", line);
+            assert!(line.contains("endpoint=\"https://rpc.example.com/\""), "{}", line);
+        }
+        assert!(line("solprobe_tps").ends_with(" 2500"));
+        assert!(line("solprobe_current_slot").ends_with(" 1234"));
+        // Values never obtained are NaN rather than zero.
+        assert!(line("solprobe_root_lag").ends_with(" NaN"));
+        assert!(line("solprobe_node_version_info").contains("version=\"2.2.0\""));
+    }
+
+    #[tokio::test]
+    async fn handle_scrape_waits_for_the_whole_request_head() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let metrics = Arc::new(Metrics::new("https://rpc.example.com/", "devnet").unwrap());
+        tokio::spawn(serve_metrics(listener, metrics));
+
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        stream.write_all(b"GET /metrics HTTP/1.1\r\n").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        stream.write_all(b"Host: localhost\r\nAccept: text/plain\r\n\r\n").await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+        assert!(response.contains("solprobe_tps{"));
+
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        stream.write_all(b"GET / HTTP/1.1\r\n\r\n").await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"), "{}", response);
+    }
+}
@@ -0,0 +1,490 @@
+use chrono::Utc;
+use serde_json::json;
+use solana_account_decoder_client_types::{UiAccountEncoding, UiDataSliceConfig};
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcLargestAccountsConfig, RpcLargestAccountsFilter, RpcSupplyConfig};
+use solana_client::rpc_request::RpcRequest;
+use solana_client::rpc_response::{Response, RpcSupply};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::commands::account::program_name;
+use crate::commands::export::{to_csv_or_header, write_output};
+use crate::commands::history::{default_path, format_timestamp};
+use crate::commands::node_health::format_sol;
+use crate::commands::rpc;
+use crate::commands::OutputFormat;
+use crate::types::{LargestAccount, SupplyFilter, SupplyReport, SupplySummary};
+use crate::utils::config::{Config, HistoryConfig};
+
+fn get_supply(client: &RpcClient, exclude_accounts: bool) -> Result<Response<RpcSupply>, Box<dyn Error>> {
+    let config = RpcSupplyConfig {
+        commitment: Some(client.commitment()),
+        exclude_non_circulating_accounts_list: exclude_accounts,
+    };
+    Ok(client.send(RpcRequest::GetSupply, json!([config]))?)
+}
+
+/// Reads the supply without the non-circulating account list, which runs to
+/// hundreds of addresses on mainnet.
+pub fn fetch_supply(client: &RpcClient) -> Result<SupplySummary, Box<dyn Error>> {
+    let response = get_supply(client, true)?;
+    Ok(summary(response.context.slot, &response.value))
+}
+
+fn summary(slot: u64, supply: &RpcSupply) -> SupplySummary {
+    SupplySummary {
+        slot,
+        total: supply.total,
+        circulating: supply.circulating,
+        non_circulating: supply.non_circulating,
+    }
+}
+
+pub fn circulating_percent(supply: &SupplySummary) -> f64 {
+    if supply.total == 0 {
+        0.0
+    } else {
+        supply.circulating as f64 / supply.total as f64 * 100.0
+    }
+}
+
+pub fn format_change(lamports: i64) -> String {
+    format!("{:+.3} SOL", lamports as f64 / LAMPORTS_PER_SOL as f64)
+}
+
+fn change(current: u64, previous: u64) -> i64 {
+    current as i64 - previous as i64
+}
+
+/// One-line summary for the dashboard, with the change since `previous`.
+pub fn format_supply(supply: &SupplySummary, previous: Option<&SupplySummary>) -> String {
+    let total_change = previous
+        .map(|previous| format!(" ({})", format_change(change(supply.total, previous.total))))
+        .unwrap_or_default();
+    let circulating_change = previous
+        .map(|previous| format!(", {}", format_change(change(supply.circulating, previous.circulating))))
+        .unwrap_or_default();
+    format!(
+        "Total {}{} | Circulating {} ({:.1}%{}) | Non-circulating {}",
+        format_sol(supply.total),
+        total_change,
+        format_sol(supply.circulating),
+        circulating_percent(supply),
+        circulating_change,
+        format_sol(supply.non_circulating)
+    )
+}
+
+/// Reads the supply and the largest accounts, labeled from `labels` and with
+/// their owning program. Nodes cache `getLargestAccounts` for a while, so the
+/// list can lag the supply figures.
+pub fn fetch_supply_report(
+    client: &RpcClient,
+    endpoint: &str,
+    filter: Option<SupplyFilter>,
+    labels: &BTreeMap<String, String>,
+) -> Result<SupplyReport, Box<dyn Error>> {
+    let response = get_supply(client, false)?;
+    let supply = summary(response.context.slot, &response.value);
+    let non_circulating: HashSet<&str> = response
+        .value
+        .non_circulating_accounts
+        .iter()
+        .map(String::as_str)
+        .collect();
+
+    let largest = client
+        .get_largest_accounts_with_config(RpcLargestAccountsConfig {
+            commitment: Some(client.commitment()),
+            filter: filter.map(|filter| match filter {
+                SupplyFilter::Circulating => RpcLargestAccountsFilter::Circulating,
+                SupplyFilter::NonCirculating => RpcLargestAccountsFilter::NonCirculating,
+            }),
+            sort_results: None,
+        })?
+        .value;
+
+    let pubkeys: Vec<Pubkey> = largest
+        .iter()
+        .filter_map(|account| Pubkey::from_str(&account.address).ok())
+        .collect();
+    let owners = fetch_owners(client, &pubkeys).unwrap_or_default();
+
+    let accounts = largest
+        .into_iter()
+        .enumerate()
+        .map(|(index, account)| LargestAccount {
+            rank: index + 1,
+            label: labels.get(&account.address).cloned(),
+            owner: owners.get(&account.address).cloned(),
+            circulating: !non_circulating.contains(account.address.as_str()),
+            supply_percent: if supply.total == 0 {
+                0.0
+            } else {
+                account.lamports as f64 / supply.total as f64 * 100.0
+            },
+            lamports: account.lamports,
+            address: account.address,
+            change: None,
+        })
+        .collect();
+
+    Ok(SupplyReport {
+        endpoint: endpoint.to_string(),
+        timestamp: Utc::now().timestamp(),
+        supply,
+        filter: filter.map(|filter| filter.label().to_string()),
+        accounts,
+        ..SupplyReport::default()
+    })
+}
+
+/// Owning program of each account, by name for the well-known ones. Only the
+/// account headers are requested.
+fn fetch_owners(client: &RpcClient, pubkeys: &[Pubkey]) -> Result<HashMap<String, String>, Box<dyn Error>> {
+    let config = RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        data_slice: Some(UiDataSliceConfig { offset: 0, length: 0 }),
+        ..RpcAccountInfoConfig::default()
+    };
+    let accounts = client.get_multiple_accounts_with_config(pubkeys, config)?.value;
+    Ok(pubkeys
+        .iter()
+        .zip(accounts)
+        .filter_map(|(pubkey, account)| {
+            let owner = account?.owner.to_string();
+            let owner = program_name(&owner).map(str::to_string).unwrap_or(owner);
+            Some((pubkey.to_string(), owner))
+        })
+        .collect())
+}
+
+/// Fills in the changes since `previous`, a sample of the same endpoint and
+/// filter.
+fn compare(report: &mut SupplyReport, previous: &SupplyReport) {
+    let balances: HashMap<&str, u64> = previous
+        .accounts
+        .iter()
+        .map(|account| (account.address.as_str(), account.lamports))
+        .collect();
+    for account in &mut report.accounts {
+        account.change = balances
+            .get(account.address.as_str())
+            .map(|lamports| change(account.lamports, *lamports));
+    }
+
+    let current: HashSet<&str> = report.accounts.iter().map(|account| account.address.as_str()).collect();
+    let dropped = previous
+        .accounts
+        .iter()
+        .filter(|account| !current.contains(account.address.as_str()))
+        .map(|account| account.address.clone())
+        .collect();
+    report.dropped = dropped;
+    report.previous_timestamp = Some(previous.timestamp);
+    report.previous_supply = Some(previous.supply);
+}
+
+/// The last supply sample of each endpoint and filter is kept next to the
+/// history file.
+fn samples_path(config: &HistoryConfig) -> Result<PathBuf, Box<dyn Error>> {
+    let history = match &config.path {
+        Some(path) => path.clone(),
+        None => default_path()?,
+    };
+    Ok(history.with_file_name("supply.json"))
+}
+
+fn sample_key(report: &SupplyReport) -> String {
+    format!("{} {}", report.endpoint, report.filter.as_deref().unwrap_or("all"))
+}
+
+/// Compares against the previous sample and replaces it with this one. A
+/// missing or unreadable samples file starts over.
+fn record_sample(config: &HistoryConfig, report: &mut SupplyReport) -> Result<(), Box<dyn Error>> {
+    let path = samples_path(config)?;
+    let mut samples: BTreeMap<String, SupplyReport> = fs::read_to_string(&path)
+        .ok()
+        .and_then(|samples| serde_json::from_str(&samples).ok())
+        .unwrap_or_default();
+
+    let key = sample_key(report);
+    if let Some(previous) = samples.get(&key) {
+        compare(report, previous);
+    }
+
+    let mut sample = report.clone();
+    sample.previous_timestamp = None;
+    sample.previous_supply = None;
+    sample.dropped.clear();
+    samples.insert(key, sample);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, serde_json::to_string(&samples)?)?;
+    Ok(())
+}
+
+pub fn run_supply(
+    url: &str,
+    filter: Option<SupplyFilter>,
+    config: &Config,
+    format: OutputFormat,
+    output: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    let client = rpc::client(url)?;
+    let mut report = fetch_supply_report(&client, &rpc::redact_url(url), filter, &config.address_labels)?;
+    if config.history.enabled {
+        record_sample(&config.history, &mut report)?;
+    }
+
+    let rendered = match format {
+        OutputFormat::Table => format_table(&report),
+        OutputFormat::Csv => to_csv_or_header(&report.accounts)?,
+        OutputFormat::Json => serde_json::to_string_pretty(&report)?,
+    };
+
+    write_output(&rendered, output)?;
+
+    Ok(())
+}
+
+fn format_table(report: &SupplyReport) -> String {
+    let supply = &report.supply;
+    let previous = report.previous_supply.as_ref();
+    let since = |current: u64, previous: Option<u64>| {
+        previous
+            .map(|previous| format!("  {}", format_change(change(current, previous))))
+            .unwrap_or_default()
+    };
+
+    let mut out = format!("Supply at slot {}\n", supply.slot);
+    if let Some(timestamp) = report.previous_timestamp {
+        out.push_str(&format!("Changes since {}\n", format_timestamp(timestamp)));
+    }
+    out.push_str(&format!(
+        "\n{:<16} {:>16}{}\n",
+        "Total",
+        format_sol(supply.total),
+        since(supply.total, previous.map(|previous| previous.total))
+    ));
+    out.push_str(&format!(
+        "{:<16} {:>16}{}  ({:.2}%)\n",
+        "Circulating",
+        format_sol(supply.circulating),
+        since(supply.circulating, previous.map(|previous| previous.circulating)),
+        circulating_percent(supply)
+    ));
+    out.push_str(&format!(
+        "{:<16} {:>16}{}\n",
+        "Non-circulating",
+        format_sol(supply.non_circulating),
+        since(supply.non_circulating, previous.map(|previous| previous.non_circulating))
+    ));
+
+    let title = match &report.filter {
+        Some(filter) => format!("Largest {} accounts", filter),
+        None => "Largest accounts".to_string(),
+    };
+    out.push_str(&format!("\n{}\n", title));
+    out.push_str(&format!(
+        "{:>4} {:<44} {:>16} {:>8} {:>16} {:<11} {:<24} {}\n",
+        "Rank", "Address", "Balance", "Supply", "Change", "Circulating", "Owner", "Label"
+    ));
+    for account in &report.accounts {
+        let change = match account.change {
+            Some(change) => format_change(change),
+            None if report.previous_timestamp.is_some() => "new".to_string(),
+            None => "-".to_string(),
+        };
+        out.push_str(&format!(
+            "{:>4} {:<44} {:>16} {:>7.2}% {:>16} {:<11} {:<24} {}\n",
+            account.rank,
+            account.address,
+            format_sol(account.lamports),
+            account.supply_percent,
+            change,
+            if account.circulating { "yes" } else { "no" },
+            account.owner.as_deref().unwrap_or("-"),
+            account.label.as_deref().unwrap_or("")
+        ));
+    }
+
+    if !report.dropped.is_empty() {
+        out.push_str("\nNo longer among the largest\n");
+        for address in &report.dropped {
+            out.push_str(&format!("  {}\n", address));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use serde_json::Value;
+    use solana_client::client_error::{ClientErrorKind, Result as ClientResult};
+    use solana_client::rpc_client::RpcClientConfig;
+    use solana_client::rpc_sender::{RpcSender, RpcTransportStats};
+    use solana_sdk::commitment_config::CommitmentConfig;
+
+    const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+
+    fn address(seed: u8) -> String {
+        Pubkey::new_from_array([seed; 32]).to_string()
+    }
+
+    /// Fake endpoint with 1000 SOL in supply and two large accounts, the
+    /// second of them non-circulating and the only one that exists.
+    struct SupplySender;
+
+    #[async_trait]
+    impl RpcSender for SupplySender {
+        async fn send(&self, request: RpcRequest, _params: Value) -> ClientResult<Value> {
+            match request {
+                RpcRequest::GetSupply => Ok(json!({
+                    "context": { "slot": 42 },
+                    "value": {
+                        "total": 1_000 * LAMPORTS_PER_SOL,
+                        "circulating": 900 * LAMPORTS_PER_SOL,
+                        "nonCirculating": 100 * LAMPORTS_PER_SOL,
+                        "nonCirculatingAccounts": [address(2)],
+                    },
+                })),
+                RpcRequest::GetLargestAccounts => Ok(json!({
+                    "context": { "slot": 42 },
+                    "value": [
+                        { "address": address(1), "lamports": 250 * LAMPORTS_PER_SOL },
+                        { "address": address(2), "lamports": 100 * LAMPORTS_PER_SOL },
+                    ],
+                })),
+                RpcRequest::GetMultipleAccounts => Ok(json!({
+                    "context": { "slot": 42 },
+                    "value": [
+                        null,
+                        {
+                            "data": ["", "base64"],
+                            "executable": false,
+                            "lamports": 100 * LAMPORTS_PER_SOL,
+                            "owner": SYSTEM_PROGRAM,
+                            "rentEpoch": 0,
+                            "space": 0,
+                        },
+                    ],
+                })),
+                _ => Err(ClientErrorKind::Custom("unsupported".to_string()).into()),
+            }
+        }
+
+        fn get_transport_stats(&self) -> RpcTransportStats {
+            RpcTransportStats::default()
+        }
+
+        fn url(&self) -> String {
+            "supply".to_string()
+        }
+    }
+
+    fn largest(address: String, lamports: u64) -> LargestAccount {
+        LargestAccount { address, lamports, ..LargestAccount::default() }
+    }
+
+    fn report(accounts: Vec<LargestAccount>) -> SupplyReport {
+        SupplyReport {
+            endpoint: "endpoint".to_string(),
+            timestamp: 2_000,
+            accounts,
+            ..SupplyReport::default()
+        }
+    }
+
+    #[test]
+    fn fetch_supply_report_ranks_labels_and_shares_the_largest_accounts() {
+        let config = RpcClientConfig::with_commitment(CommitmentConfig::confirmed());
+        let client = RpcClient::new_sender(SupplySender, config);
+        let labels = BTreeMap::from([(address(1), "Exchange".to_string())]);
+
+        let report = fetch_supply_report(&client, "endpoint", None, &labels).unwrap();
+
+        assert_eq!(report.supply.slot, 42);
+        assert_eq!(report.accounts.len(), 2);
+        let [first, second] = &report.accounts[..] else { unreachable!() };
+        assert_eq!((first.rank, first.supply_percent), (1, 25.0));
+        assert_eq!(first.label.as_deref(), Some("Exchange"));
+        assert!(first.circulating);
+        assert_eq!(first.owner, None);
+        assert_eq!((second.rank, second.supply_percent), (2, 10.0));
+        assert!(!second.circulating);
+        assert_eq!(second.owner.as_deref(), program_name(SYSTEM_PROGRAM).or(Some(SYSTEM_PROGRAM)));
+    }
+
+    #[test]
+    fn compare_reports_changed_new_and_dropped_accounts() {
+        let mut previous = report(vec![largest(address(1), 500), largest(address(2), 300)]);
+        previous.timestamp = 1_000;
+        previous.supply.total = 10_000;
+        let mut current = report(vec![largest(address(1), 450), largest(address(3), 200)]);
+
+        compare(&mut current, &previous);
+
+        assert_eq!(current.accounts[0].change, Some(-50));
+        assert_eq!(current.accounts[1].change, None);
+        assert_eq!(current.dropped, [address(2)]);
+        assert_eq!(current.previous_timestamp, Some(1_000));
+        assert_eq!(current.previous_supply.map(|supply| supply.total), Some(10_000));
+    }
+
+    #[test]
+    fn record_sample_compares_against_the_previous_sample_only() {
+        let dir = std::env::temp_dir().join(format!("solprobe-supply-{}", std::process::id()));
+        let config = HistoryConfig {
+            path: Some(dir.join("history.jsonl")),
+            ..HistoryConfig::default()
+        };
+
+        let mut first = report(vec![largest(address(1), 500)]);
+        record_sample(&config, &mut first).unwrap();
+        assert_eq!(first.previous_timestamp, None);
+        assert_eq!(first.accounts[0].change, None);
+
+        let mut second = report(vec![largest(address(1), 700)]);
+        record_sample(&config, &mut second).unwrap();
+        assert_eq!(second.previous_timestamp, Some(2_000));
+        assert_eq!(second.accounts[0].change, Some(200));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn format_supply_shows_changes_only_with_a_previous_sample() {
+        let supply = SupplySummary {
+            slot: 2,
+            total: 500 * LAMPORTS_PER_SOL,
+            circulating: 400 * LAMPORTS_PER_SOL,
+            non_circulating: 100 * LAMPORTS_PER_SOL,
+        };
+        let previous = SupplySummary {
+            slot: 1,
+            total: supply.total - LAMPORTS_PER_SOL / 2,
+            circulating: supply.circulating - LAMPORTS_PER_SOL,
+            ..supply
+        };
+
+        assert_eq!(
+            format_supply(&supply, None),
+            "Total 500 SOL | Circulating 400 SOL (80.0%) | Non-circulating 100 SOL"
+        );
+        assert_eq!(
+            format_supply(&supply, Some(&previous)),
+            "Total 500 SOL (+0.500 SOL) | Circulating 400 SOL (80.0%, +1.000 SOL) | Non-circulating 100 SOL"
+        );
+    }
+}
@@ -0,0 +1,490 @@
+use serde_json::json;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_client::rpc_request::RpcRequest;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::{TransactionError, TransactionVersion};
+use solana_transaction_status_client_types::{
+    EncodedConfirmedTransactionWithStatusMeta, TransactionConfirmationStatus, UiLoadedAddresses,
+    UiTransactionEncoding, UiTransactionTokenBalance,
+};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::commands::account::program_name;
+use crate::commands::export::{to_csv_or_header, write_output};
+use crate::commands::fees::{compute_unit_limit, compute_unit_price, LAMPORTS_PER_SIGNATURE};
+use crate::commands::history::format_timestamp;
+use crate::commands::node_health::format_sol;
+use crate::commands::rpc;
+use crate::commands::OutputFormat;
+use crate::types::{BalanceChange, ProgramInvocation, TransactionInfo};
+
+const SOL_DECIMALS: u8 = 9;
+
+/// Messages of the System Program's custom errors, by code.
+const SYSTEM_ERRORS: [&str; 9] = [
+    "an account with the same address already exists",
+    "account does not have enough SOL to perform the operation",
+    "cannot assign account to this program id",
+    "cannot allocate account data of this length",
+    "length of requested seed is too long",
+    "provided address does not match addressed derived from seed",
+    "advancing stored nonce requires a populated RecentBlockhashes sysvar",
+    "stored nonce is still in recent_blockhashes",
+    "specified nonce does not match stored nonce",
+];
+
+/// Messages of the SPL Token custom errors, by code; token-2022 uses the same codes.
+const TOKEN_ERRORS: [&str; 20] = [
+    "lamport balance below rent-exempt threshold",
+    "insufficient funds",
+    "invalid mint",
+    "account not associated with this mint",
+    "owner does not match",
+    "fixed supply",
+    "account already in use",
+    "invalid number of provided signers",
+    "invalid number of required signers",
+    "state is uninitialized",
+    "instruction does not support native tokens",
+    "non-native account can only be closed if its balance is zero",
+    "invalid instruction",
+    "state is invalid for requested operation",
+    "operation overflowed",
+    "account does not support specified authority type",
+    "this token mint cannot freeze accounts",
+    "account is frozen",
+    "the provided decimals value different from the mint decimals",
+    "instruction does not support non-native tokens",
+];
+
+/// Fetches a transaction with its status metadata and breaks it down into
+/// fees, compute usage, program invocations and balance changes.
+pub fn fetch_transaction(client: &RpcClient, signature: &str) -> Result<TransactionInfo, Box<dyn Error>> {
+    let signature = Signature::from_str(signature).map_err(|e| format!("Invalid signature {}: {}", signature, e))?;
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Base64),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    };
+    let confirmed: Option<EncodedConfirmedTransactionWithStatusMeta> =
+        client.send(RpcRequest::GetTransaction, json!([signature.to_string(), config]))?;
+    let confirmed = confirmed.ok_or_else(|| {
+        format!(
+            "Transaction {} not found; it is not confirmed yet or older than this node's ledger history",
+            signature
+        )
+    })?;
+    let transaction = confirmed
+        .transaction
+        .transaction
+        .decode()
+        .ok_or("Could not decode the transaction")?;
+    let meta = confirmed
+        .transaction
+        .meta
+        .ok_or("The node returned the transaction without its status")?;
+
+    // Balances are indexed over the static keys followed by the addresses
+    // loaded from lookup tables, writable first.
+    let mut keys: Vec<String> = transaction
+        .message
+        .static_account_keys()
+        .iter()
+        .map(|key| key.to_string())
+        .collect();
+    if let Some(loaded) = Option::<&UiLoadedAddresses>::from(meta.loaded_addresses.as_ref()) {
+        keys.extend(loaded.writable.iter().cloned());
+        keys.extend(loaded.readonly.iter().cloned());
+    }
+    let programs: Vec<String> = transaction
+        .message
+        .instructions()
+        .iter()
+        .map(|ix| keys.get(ix.program_id_index as usize).cloned().unwrap_or_default())
+        .collect();
+    let logs: Vec<String> = Option::from(meta.log_messages).unwrap_or_default();
+
+    let mut invocations = parse_invocations(&logs);
+    if invocations.is_empty() {
+        invocations = programs
+            .iter()
+            .enumerate()
+            .map(|(instruction, program)| ProgramInvocation {
+                instruction,
+                depth: 1,
+                program_name: program_name(program).map(str::to_string),
+                program: program.clone(),
+                ..ProgramInvocation::default()
+            })
+            .collect();
+    }
+
+    let confirmation_status = client
+        .get_signature_statuses_with_history(&[signature])
+        .ok()
+        .and_then(|statuses| statuses.value.into_iter().next().flatten())
+        .and_then(|status| status.confirmation_status)
+        .map(|status| match status {
+            TransactionConfirmationStatus::Processed => "processed",
+            TransactionConfirmationStatus::Confirmed => "confirmed",
+            TransactionConfirmationStatus::Finalized => "finalized",
+        })
+        .map(str::to_string);
+
+    let base_fee = transaction.signatures.len() as u64 * LAMPORTS_PER_SIGNATURE;
+    let mut balance_changes: Vec<BalanceChange> = keys
+        .iter()
+        .zip(meta.pre_balances.iter().zip(&meta.post_balances))
+        .filter(|(_, (pre, post))| pre != post)
+        .map(|(account, (pre, post))| BalanceChange {
+            account: account.clone(),
+            mint: None,
+            decimals: SOL_DECIMALS,
+            pre: *pre,
+            post: *post,
+        })
+        .collect();
+    balance_changes.extend(token_balance_changes(
+        &keys,
+        Option::from(meta.pre_token_balances).unwrap_or_default(),
+        Option::from(meta.post_token_balances).unwrap_or_default(),
+    ));
+
+    Ok(TransactionInfo {
+        signature: signature.to_string(),
+        slot: confirmed.slot,
+        block_time: confirmed.block_time,
+        confirmation_status,
+        version: match transaction.version() {
+            TransactionVersion::Legacy(_) => "legacy".to_string(),
+            TransactionVersion::Number(version) => version.to_string(),
+        },
+        error: meta.err.as_ref().map(|error| describe_error(error, &programs, &logs)),
+        fee: meta.fee,
+        priority_fee: meta.fee.saturating_sub(base_fee),
+        compute_unit_price: compute_unit_price(&transaction),
+        compute_unit_limit: compute_unit_limit(&transaction),
+        compute_units_consumed: Option::from(meta.compute_units_consumed),
+        invocations,
+        balance_changes,
+        logs,
+    })
+}
+
+/// Rebuilds the invocation tree from the runtime's `Program <id> invoke [n]`,
+/// `consumed`, `success` and `failed` log lines.
+/// Log lines written on behalf of a running program rather than by the runtime.
+/// Their text is arbitrary, so it must not be read as an invoke or result line.
+const PROGRAM_OUTPUT_PREFIXES: [&str; 4] = ["Program log: ", "Program data: ", "Program return: ", "Program consumption: "];
+
+fn parse_invocations(logs: &[String]) -> Vec<ProgramInvocation> {
+    let mut invocations: Vec<ProgramInvocation> = Vec::new();
+    let mut stack: Vec<usize> = Vec::new();
+    let mut instruction = None;
+
+    for line in logs {
+        if PROGRAM_OUTPUT_PREFIXES.iter().any(|prefix| line.starts_with(prefix)) {
+            continue;
+        }
+        let Some(rest) = line.strip_prefix("Program ") else {
+            continue;
+        };
+        let mut words = rest.split_whitespace();
+        let (Some(program), Some(verb)) = (words.next(), words.next()) else {
+            continue;
+        };
+        match verb {
+            "invoke" => {
+                let depth = words
+                    .next()
+                    .and_then(|depth| depth.trim_matches(|c| c == '[' || c == ']').parse().ok())
+                    .unwrap_or(stack.len() + 1);
+                if depth == 1 {
+                    instruction = Some(instruction.map_or(0, |index| index + 1));
+                }
+                stack.push(invocations.len());
+                invocations.push(ProgramInvocation {
+                    instruction: instruction.unwrap_or_default(),
+                    depth,
+                    program: program.to_string(),
+                    program_name: program_name(program).map(str::to_string),
+                    ..ProgramInvocation::default()
+                });
+            }
+            "consumed" => {
+                if let Some(&index) = stack.last() {
+                    invocations[index].compute_units = words.next().and_then(|units| units.parse().ok());
+                }
+            }
+            "success" => {
+                if let Some(index) = stack.pop() {
+                    invocations[index].result = Some("success".to_string());
+                }
+            }
+            "failed:" => {
+                if let Some(index) = stack.pop() {
+                    let reason = rest.split_once("failed: ").map(|(_, reason)| reason).unwrap_or_default();
+                    invocations[index].result = Some(format!("failed: {}", reason));
+                }
+            }
+            _ => {}
+        }
+    }
+    invocations
+}
+
+fn token_balance_changes(
+    keys: &[String],
+    pre: Vec<UiTransactionTokenBalance>,
+    post: Vec<UiTransactionTokenBalance>,
+) -> Vec<BalanceChange> {
+    let mut balances: BTreeMap<(u8, String), (u8, u64, u64)> = BTreeMap::new();
+    for balance in pre {
+        let entry = balances.entry((balance.account_index, balance.mint)).or_default();
+        entry.0 = balance.ui_token_amount.decimals;
+        entry.1 = balance.ui_token_amount.amount.parse().unwrap_or_default();
+    }
+    for balance in post {
+        let entry = balances.entry((balance.account_index, balance.mint)).or_default();
+        entry.0 = balance.ui_token_amount.decimals;
+        entry.2 = balance.ui_token_amount.amount.parse().unwrap_or_default();
+    }
+
+    balances
+        .into_iter()
+        .filter(|(_, (_, pre, post))| pre != post)
+        .map(|((index, mint), (decimals, pre, post))| BalanceChange {
+            account: keys.get(index as usize).cloned().unwrap_or_default(),
+            mint: Some(mint),
+            decimals,
+            pre,
+            post,
+        })
+        .collect()
+}
+
+/// Spells out which instruction failed in which program, with the message
+/// of well-known custom error codes and the program's last error log line.
+fn describe_error(error: &TransactionError, programs: &[String], logs: &[String]) -> String {
+    let TransactionError::InstructionError(index, instruction_error) = error else {
+        return error.to_string();
+    };
+    let program = programs.get(*index as usize).map(String::as_str).unwrap_or("unknown program");
+    let reason = match instruction_error {
+        InstructionError::Custom(code) => {
+            let known = match program_name(program) {
+                Some("System Program") => SYSTEM_ERRORS.get(*code as usize),
+                Some("Token Program") | Some("Token-2022 Program") => TOKEN_ERRORS.get(*code as usize),
+                _ => None,
+            };
+            match known {
+                Some(message) => format!("{} (custom error {})", message, code),
+                None => format!("custom program error {} (0x{:x})", code, code),
+            }
+        }
+        other => other.to_string(),
+    };
+
+    let mut description = format!(
+        "instruction {} ({}) failed: {}",
+        index,
+        program_name(program).unwrap_or(program),
+        reason
+    );
+    let error_log = logs
+        .iter()
+        .rev()
+        .filter_map(|line| line.strip_prefix("Program log: "))
+        .find(|message| message.contains("rror"));
+    if let Some(message) = error_log {
+        description.push_str(&format!("; log: {}", message));
+    }
+    description
+}
+
+/// Formats an amount in base units with the asset's decimals. Decimals too
+/// large to scale by are shown as the raw amount.
+fn format_units(amount: i128, decimals: u8) -> String {
+    let Some(scale) = 10i128.checked_pow(decimals as u32) else {
+        return amount.to_string();
+    };
+    let sign = if amount < 0 { "-" } else { "" };
+    let amount = amount.abs();
+    if decimals == 0 {
+        return format!("{}{}", sign, amount);
+    }
+    let fraction = format!("{:0width$}", amount % scale, width = decimals as usize);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        format!("{}{}", sign, amount / scale)
+    } else {
+        format!("{}{}.{}", sign, amount / scale, fraction)
+    }
+}
+
+pub fn run_transaction(url: &str, signature: &str, format: OutputFormat, output: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    let client = rpc::client(url)?;
+    let info = fetch_transaction(&client, signature)?;
+
+    let rendered = match format {
+        OutputFormat::Table => format_table(&info),
+        OutputFormat::Csv => to_csv_or_header(&info.invocations)?,
+        OutputFormat::Json => serde_json::to_string_pretty(&info)?,
+    };
+
+    write_output(&rendered, output)?;
+
+    Ok(())
+}
+
+fn format_table(info: &TransactionInfo) -> String {
+    let mut out = format!("Transaction {}\n\n", info.signature);
+    out.push_str(&format!("{:<16} {}\n", "Slot", info.slot));
+    out.push_str(&format!(
+        "{:<16} {}\n",
+        "Block time",
+        info.block_time.map(format_timestamp).unwrap_or_else(|| "-".to_string())
+    ));
+    out.push_str(&format!(
+        "{:<16} {}\n",
+        "Confirmation",
+        info.confirmation_status.as_deref().unwrap_or("unknown (no longer in the status cache)")
+    ));
+    out.push_str(&format!("{:<16} {}\n", "Version", info.version));
+    out.push_str(&format!(
+        "{:<16} {}\n",
+        "Result",
+        match &info.error {
+            Some(error) => format!("Failed: {}", error),
+            None => "Success".to_string(),
+        }
+    ));
+    out.push_str(&format!(
+        "{:<16} {}, priority {} lamports{}\n",
+        "Fee",
+        format_sol(info.fee),
+        info.priority_fee,
+        info.compute_unit_price
+            .map(|price| format!(" at {} micro-lamports/CU", price))
+            .unwrap_or_default()
+    ));
+    out.push_str(&format!(
+        "{:<16} {} consumed{}\n",
+        "Compute units",
+        info.compute_units_consumed.map(|units| units.to_string()).unwrap_or_else(|| "-".to_string()),
+        info.compute_unit_limit
+            .map(|limit| format!(" of {} requested", limit))
+            .unwrap_or_default()
+    ));
+
+    out.push_str(&format!("\n{:<4} {:>8}  {:<60} {}\n", "Ix", "CU", "Program", "Result"));
+    for invocation in &info.invocations {
+        let program = match &invocation.program_name {
+            Some(name) => name.clone(),
+            None => invocation.program.clone(),
+        };
+        out.push_str(&format!(
+            "{:<4} {:>8}  {:<60} {}\n",
+            invocation.instruction,
+            invocation.compute_units.map(|units| units.to_string()).unwrap_or_else(|| "-".to_string()),
+            format!("{}{}", "  ".repeat(invocation.depth.saturating_sub(1)), program),
+            invocation.result.as_deref().unwrap_or("-")
+        ));
+    }
+
+    if !info.balance_changes.is_empty() {
+        out.push_str(&format!(
+            "\n{:<44} {:<44} {:>20} {:>20} {:>20}\n",
+            "Account", "Asset", "Before", "After", "Change"
+        ));
+        for change in &info.balance_changes {
+            out.push_str(&format!(
+                "{:<44} {:<44} {:>20} {:>20} {:>20}\n",
+                change.account,
+                change.mint.as_deref().unwrap_or("SOL"),
+                format_units(change.pre as i128, change.decimals),
+                format_units(change.post as i128, change.decimals),
+                format_units(change.post as i128 - change.pre as i128, change.decimals)
+            ));
+        }
+    }
+
+    if !info.logs.is_empty() {
+        out.push_str("\nLogs:\n");
+        for line in &info.logs {
+            out.push_str(&format!("  {}\n", line));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn logs(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_invocations_tracks_depth_units_and_results() {
+        let invocations = parse_invocations(&logs(&[
+            "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+            "Program ComputeBudget111111111111111111111111111111 success",
+            "Program Prog1111111111111111111111111111111111111 invoke [1]",
+            "Program log: Instruction: Swap",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [2]",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA consumed 4645 of 190000 compute units",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA success",
+            "Program Prog1111111111111111111111111111111111111 consumed 30000 of 200000 compute units",
+            "Program Prog1111111111111111111111111111111111111 failed: custom program error: 0x1",
+        ]));
+
+        assert_eq!(invocations.len(), 3);
+        assert_eq!((invocations[0].instruction, invocations[0].depth), (0, 1));
+        assert_eq!(invocations[0].result.as_deref(), Some("success"));
+        assert_eq!((invocations[1].instruction, invocations[1].depth), (1, 1));
+        assert_eq!(invocations[1].compute_units, Some(30000));
+        assert_eq!(invocations[1].result.as_deref(), Some("failed: custom program error: 0x1"));
+        assert_eq!((invocations[2].instruction, invocations[2].depth), (1, 2));
+        assert_eq!(invocations[2].program_name.as_deref(), Some("Token Program"));
+        assert_eq!(invocations[2].compute_units, Some(4645));
+    }
+
+    #[test]
+    fn parse_invocations_ignores_program_output() {
+        let invocations = parse_invocations(&logs(&[
+            "Program Prog1111111111111111111111111111111111111 invoke [1]",
+            "Program log: invoke [2]",
+            "Program log: success",
+            "Program data: failed: spoofed",
+            "Program data: consumed 1 of 2 compute units",
+            "Program return: Prog1111111111111111111111111111111111111 AQ==",
+            "Program Prog1111111111111111111111111111111111111 success",
+        ]));
+
+        assert_eq!(invocations.len(), 1);
+        assert_eq!(invocations[0].program, "Prog1111111111111111111111111111111111111");
+        assert_eq!(invocations[0].result.as_deref(), Some("success"));
+    }
+
+    #[test]
+    fn format_units_scales_by_decimals() {
+        assert_eq!(format_units(1_500_000, 6), "1.5");
+        assert_eq!(format_units(-2_000_000_000, 9), "-2");
+        assert_eq!(format_units(42, 0), "42");
+        assert_eq!(format_units(7, 3), "0.007");
+    }
+
+    #[test]
+    fn format_units_falls_back_to_raw_amount_for_huge_decimals() {
+        assert_eq!(format_units(123, 40), "123");
+        assert_eq!(format_units(-5, u8::MAX), "-5");
+    }
+}
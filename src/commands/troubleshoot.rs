@@ -1,31 +1,35 @@
-use solana_client::rpc_client::RpcClient;
-use solana_sdk::commitment_config::CommitmentConfig;
+use serde::Serialize;
 use std::error::Error;
 use std::time::{Duration, Instant};
 
+use crate::commands::congestion::fetch_congestion;
+use crate::commands::rpc::{self, is_rate_limited};
+use crate::types::{CongestionReport, Finding, FindingSeverity};
+
+/// Number of recent blocks sampled for compute unit utilization.
+const CONGESTION_SAMPLE_BLOCKS: usize = 8;
+
+/// Congestion scores at or above these are reported as warnings / critical.
+const CONGESTION_WARNING_SCORE: u8 = 40;
+const CONGESTION_CRITICAL_SCORE: u8 = 70;
+
+#[derive(Default, Serialize)]
 pub struct TroubleshootResults {
     pub connection_status: bool,
+    pub rate_limited: bool,
     pub version_mismatch: bool,
     pub high_latency: bool,
-    pub network_congestion: bool,
-}
-
-impl Default for TroubleshootResults {
-    fn default() -> Self {
-        TroubleshootResults {
-            connection_status: false,
-            version_mismatch: false,
-            high_latency: false,
-            network_congestion: false,
-        }
-    }
+    pub congestion: Option<CongestionReport>,
 }
 
 pub fn run_troubleshoot(url: &str) -> Result<TroubleshootResults, Box<dyn Error>> {
-    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+    let client = rpc::client(url)?;
     let mut results = TroubleshootResults::default();
 
-    results.connection_status = client.get_health().is_ok();
+    match client.get_health() {
+        Ok(()) => results.connection_status = true,
+        Err(error) => results.rate_limited = is_rate_limited(error.kind()),
+    }
 
     if let Ok(version) = client.get_version() {
         // Assuming the expected version is "1.14.0"
@@ -38,21 +42,90 @@ pub fn run_troubleshoot(url: &str) -> Result<TroubleshootResults, Box<dyn Error>
         results.high_latency = latency > Duration::from_millis(500);
     }
 
-    if let Ok(recent_performance) = client.get_recent_performance_samples(Some(1)) {
-        if let Some(latest) = recent_performance.first() {
-            let tps = latest.num_transactions as f64 / latest.sample_period_secs as f64;
-            // Assuming network congestion if TPS is above 1500
-            results.network_congestion = tps > 1500.0;
-        }
-    }
+    results.congestion = fetch_congestion(&client, CONGESTION_SAMPLE_BLOCKS).ok();
 
     Ok(results)
 }
 
-pub fn print_troubleshoot_results(results: &TroubleshootResults) {
-    println!("Troubleshoot Results:");
-    println!("Connection Status: {}", if results.connection_status { "OK" } else { "Failed" });
-    println!("Version Mismatch: {}", if results.version_mismatch { "Yes" } else { "No" });
-    println!("High Latency: {}", if results.high_latency { "Yes" } else { "No" });
-    println!("Network Congestion: {}", if results.network_congestion { "Yes" } else { "No" });
+/// Turns troubleshoot results into one finding per check, with remediation
+/// advice for the checks that did not pass.
+pub fn troubleshoot_findings(results: &TroubleshootResults) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    findings.push(if results.connection_status {
+        Finding::ok("Connection", "Node answered getHealth")
+    } else if results.rate_limited {
+        Finding {
+            severity: FindingSeverity::Warning,
+            check: "Connection".to_string(),
+            detail: "Endpoint kept answering HTTP 429 (rate limited) after retrying".to_string(),
+            remediation: Some(
+                "Lower `requests_per_second` in the `[rpc]` config section, raise the update interval, or use a \
+                 private RPC endpoint instead of a public one."
+                    .to_string(),
+            ),
+        }
+    } else {
+        Finding {
+            severity: FindingSeverity::Critical,
+            check: "Connection".to_string(),
+            detail: "Node did not answer getHealth or reported itself unhealthy".to_string(),
+            remediation: Some(
+                "Check that the validator/RPC process is running, the RPC port is reachable, and the node has caught \
+                 up (`solana catchup`)."
+                    .to_string(),
+            ),
+        }
+    });
+
+    findings.push(if results.version_mismatch {
+        Finding {
+            severity: FindingSeverity::Warning,
+            check: "Version".to_string(),
+            detail: "Node version differs from the expected release".to_string(),
+            remediation: Some("Compare the node version with the cluster's recommended release and upgrade.".to_string()),
+        }
+    } else {
+        Finding::ok("Version", "Node runs the expected release")
+    });
+
+    findings.push(if results.high_latency {
+        Finding {
+            severity: FindingSeverity::Warning,
+            check: "Latency".to_string(),
+            detail: "getSlot took longer than 500ms".to_string(),
+            remediation: Some(
+                "Use an RPC endpoint closer to this client, or check CPU, disk and request load on the node."
+                    .to_string(),
+            ),
+        }
+    } else {
+        Finding::ok("Latency", "getSlot answered within 500ms")
+    });
+
+    findings.push(match &results.congestion {
+        Some(congestion) if congestion.score >= CONGESTION_WARNING_SCORE => Finding {
+            severity: if congestion.score >= CONGESTION_CRITICAL_SCORE {
+                FindingSeverity::Critical
+            } else {
+                FindingSeverity::Warning
+            },
+            check: "Congestion".to_string(),
+            detail: format!("Congestion score {}/100: {}", congestion.score, congestion.evidence.join("; ")),
+            remediation: Some(
+                "Raise the compute unit price to the recommended level (`solprobe fees`), request only the compute \
+                 units transactions need, and avoid write locks on the hottest accounts."
+                    .to_string(),
+            ),
+        },
+        Some(congestion) => Finding::ok("Congestion", &format!("Congestion score {}/100", congestion.score)),
+        None => Finding {
+            severity: FindingSeverity::Warning,
+            check: "Congestion".to_string(),
+            detail: "Recent blocks could not be sampled".to_string(),
+            remediation: Some("Check that the endpoint serves getBlock for recent slots.".to_string()),
+        },
+    });
+
+    findings
 }
@@ -0,0 +1,121 @@
+use solana_client::rpc_client::RpcClient;
+use std::cmp::Reverse;
+use std::error::Error;
+use std::path::Path;
+
+use crate::commands::export::{to_csv_or_header, write_output};
+use crate::commands::node_health::format_sol;
+use crate::commands::rpc;
+use crate::commands::OutputFormat;
+use crate::types::{ClusterNode, ValidatorInfo};
+
+/// Current and delinquent vote accounts, sorted by activated stake.
+pub fn fetch_validators(client: &RpcClient) -> Result<Vec<ValidatorInfo>, Box<dyn Error>> {
+    let vote_accounts = client.get_vote_accounts()?;
+    let current = vote_accounts.current.into_iter().map(|account| (account, false));
+    let delinquent = vote_accounts.delinquent.into_iter().map(|account| (account, true));
+
+    let mut validators: Vec<ValidatorInfo> = current
+        .chain(delinquent)
+        .map(|(account, delinquent)| ValidatorInfo {
+            identity: account.node_pubkey,
+            vote_account: account.vote_pubkey,
+            activated_stake: account.activated_stake,
+            commission: account.commission,
+            last_vote: account.last_vote,
+            root_slot: account.root_slot,
+            delinquent,
+        })
+        .collect();
+    validators.sort_by_key(|validator| Reverse(validator.activated_stake));
+    Ok(validators)
+}
+
+pub fn fetch_cluster_nodes(client: &RpcClient) -> Result<Vec<ClusterNode>, Box<dyn Error>> {
+    let mut nodes: Vec<ClusterNode> = client
+        .get_cluster_nodes()?
+        .into_iter()
+        .map(|node| ClusterNode {
+            pubkey: node.pubkey,
+            gossip: node.gossip.map(|addr| addr.to_string()),
+            tpu: node.tpu.map(|addr| addr.to_string()),
+            rpc: node.rpc.map(|addr| addr.to_string()),
+            version: node.version,
+            feature_set: node.feature_set,
+            shred_version: node.shred_version,
+        })
+        .collect();
+    nodes.sort_by(|a, b| a.pubkey.cmp(&b.pubkey));
+    Ok(nodes)
+}
+
+pub fn run_validators(url: &str, format: OutputFormat, output: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    let client = rpc::client(url)?;
+    let validators = fetch_validators(&client)?;
+
+    let rendered = match format {
+        OutputFormat::Table => format_validators_table(&validators),
+        OutputFormat::Csv => to_csv_or_header(&validators)?,
+        OutputFormat::Json => serde_json::to_string_pretty(&validators)?,
+    };
+
+    write_output(&rendered, output)?;
+
+    Ok(())
+}
+
+pub fn run_cluster_nodes(url: &str, format: OutputFormat, output: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    let client = rpc::client(url)?;
+    let nodes = fetch_cluster_nodes(&client)?;
+
+    let rendered = match format {
+        OutputFormat::Table => format_cluster_nodes_table(&nodes),
+        OutputFormat::Csv => to_csv_or_header(&nodes)?,
+        OutputFormat::Json => serde_json::to_string_pretty(&nodes)?,
+    };
+
+    write_output(&rendered, output)?;
+
+    Ok(())
+}
+
+fn format_validators_table(validators: &[ValidatorInfo]) -> String {
+    let delinquent = validators.iter().filter(|validator| validator.delinquent).count();
+    let mut out = format!("Validators: {} ({} delinquent)\n\n", validators.len(), delinquent);
+    out.push_str(&format!(
+        "{:<44} {:<44} {:>16} {:>6} {:>12} {:>12} {:>10}\n",
+        "Identity", "Vote Account", "Stake", "Comm", "Last Vote", "Root", "Delinquent"
+    ));
+    for validator in validators {
+        out.push_str(&format!(
+            "{:<44} {:<44} {:>16} {:>5}% {:>12} {:>12} {:>10}\n",
+            validator.identity,
+            validator.vote_account,
+            format_sol(validator.activated_stake),
+            validator.commission,
+            validator.last_vote,
+            validator.root_slot,
+            if validator.delinquent { "yes" } else { "no" }
+        ));
+    }
+    out
+}
+
+fn format_cluster_nodes_table(nodes: &[ClusterNode]) -> String {
+    let mut out = format!("Cluster Nodes: {}\n\n", nodes.len());
+    out.push_str(&format!(
+        "{:<44} {:<22} {:<22} {:<22} {:<12}\n",
+        "Pubkey", "Gossip", "TPU", "RPC", "Version"
+    ));
+    for node in nodes {
+        out.push_str(&format!(
+            "{:<44} {:<22} {:<22} {:<22} {:<12}\n",
+            node.pubkey,
+            node.gossip.as_deref().unwrap_or("-"),
+            node.tpu.as_deref().unwrap_or("-"),
+            node.rpc.as_deref().unwrap_or("-"),
+            node.version.as_deref().unwrap_or("-")
+        ));
+    }
+    out
+}
@@ -1,6 +1,7 @@
 use clap::Parser;
 use log::info;
 use std::error::Error;
+use std::time::Duration;
 
 mod commands;
 mod types;
@@ -19,26 +20,116 @@ async fn main() -> Result<(), Box<dyn Error>> {
     logger::init()?;
 
     let config = Config::load()?;
+    commands::rpc::configure(&config);
 
     info!("SolProbe started");
 
     match cli.command {
         Commands::NodeHealth => {
             let url = utils::input::get_url_input()?;
-            run_app(ui::app::AppMode::NodeHealth, &url, &config).await?;
+            run_app(ui::app::AppMode::NodeHealth, &url, &config, None).await?;
         }
         Commands::NetworkPerformance => {
             let url = utils::input::get_url_input()?;
-            run_app(ui::app::AppMode::NetworkPerformance, &url, &config).await?;
+            run_app(ui::app::AppMode::NetworkPerformance, &url, &config, None).await?;
         }
         Commands::Troubleshoot => {
             let url = utils::input::get_url_input()?;
-            run_app(ui::app::AppMode::Troubleshoot, &url, &config).await?;
+            run_app(ui::app::AppMode::Troubleshoot, &url, &config, None).await?;
         }
-        Commands::Monitor => {
+        Commands::Monitor { record } => {
             let url = utils::input::get_url_input()?;
             let interval = utils::input::get_interval_input()?;
-            run_app(ui::app::AppMode::Monitor, &url, &config).await?;
+            run_app(ui::app::AppMode::Monitor, &url, &config, record.as_deref()).await?;
+        }
+        Commands::Replay { path, speed } => {
+            ui::app::run_replay(&path, speed, &config).await?;
+        }
+        Commands::BlockProduction { url, first_slot, last_slot, sort, format, output } => {
+            let url = utils::input::resolve_url(url)?;
+            let range = first_slot.map(|first_slot| (first_slot, last_slot));
+            commands::block_production::run_block_production(&url, range, sort, format, output.as_deref())?;
+        }
+        Commands::Validators { url, format, output } => {
+            let url = utils::input::resolve_url(url)?;
+            commands::validators::run_validators(&url, format, output.as_deref())?;
+        }
+        Commands::ClusterNodes { url, format, output } => {
+            let url = utils::input::resolve_url(url)?;
+            commands::validators::run_cluster_nodes(&url, format, output.as_deref())?;
+        }
+        Commands::LeaderSchedule { url, count } => {
+            let url = utils::input::resolve_url(url)?;
+            commands::leader_schedule::run_leader_schedule(&url, count, &config.validators)?;
+        }
+        Commands::Fees { url, mut accounts, blocks, format, output } => {
+            let url = utils::input::resolve_url(url)?;
+            if accounts.is_empty() {
+                accounts = config.fee_accounts.clone();
+            }
+            commands::fees::run_fees(&url, &accounts, blocks, format, output.as_deref())?;
+        }
+        Commands::Serve { url, metrics_addr, cluster } => {
+            let url = utils::input::resolve_url(url)?;
+            commands::serve::run_serve(&url, metrics_addr, cluster, &config).await?;
+        }
+        Commands::Report { url, format, output } => {
+            let url = utils::input::resolve_url(url)?;
+            commands::report::run_report(&url, format, output.as_deref())?;
+        }
+        Commands::History { metrics, since, url, format, output } => {
+            commands::history::run_history(&config.history, &metrics, since, url.as_deref(), format, output.as_deref())?;
+        }
+        Commands::RpcStats { url, rounds, format, output } => {
+            let url = utils::input::resolve_url(url)?;
+            commands::rpc_stats::run_rpc_stats(&url, rounds, format, output.as_deref())?;
+        }
+        Commands::Bench { urls, methods, concurrency, rate, duration, timeout, account, program, format, output } => {
+            let options = commands::bench::BenchOptions {
+                methods,
+                concurrency,
+                rate,
+                duration: Duration::from_secs(duration),
+                timeout: Duration::from_secs(timeout),
+                account,
+                program,
+            };
+            commands::bench::run_bench(&urls, &options, format, output.as_deref()).await?;
+        }
+        Commands::Consistency { urls, accounts, max_lag, format, output } => {
+            commands::consistency::run_consistency(&urls, &accounts, max_lag, format, output.as_deref())?;
+        }
+        Commands::Account { address, url, format, output } => {
+            let url = utils::input::resolve_url(url)?;
+            commands::account::run_account(&url, &address, format, output.as_deref())?;
+        }
+        Commands::Tx { signature, url, format, output } => {
+            let url = utils::input::resolve_url(url)?;
+            commands::transaction::run_transaction(&url, &signature, format, output.as_deref())?;
+        }
+        Commands::Landing { urls, keypair, count, interval_ms, priority_fees, timeout, max_retries, format, output } => {
+            let options = commands::landing::LandingOptions {
+                keypair,
+                count,
+                interval: Duration::from_millis(interval_ms),
+                priority_fees,
+                timeout: Duration::from_secs(timeout),
+                max_retries,
+            };
+            commands::landing::run_landing(&urls, &options, format, output.as_deref())?;
+        }
+        Commands::Supply { url, filter, format, output } => {
+            let url = utils::input::resolve_url(url)?;
+            commands::supply::run_supply(&url, filter, &config, format, output.as_deref())?;
+        }
+        Commands::Program { program_id, url, data_size, memcmp, force, format, output } => {
+            let url = utils::input::resolve_url(url)?;
+            commands::program::run_program(&url, &program_id, data_size, &memcmp, force, format, output.as_deref())?;
+        }
+        #[cfg(feature = "otel")]
+        Commands::Push { url, otlp_endpoint, cluster } => {
+            let url = utils::input::resolve_url(url)?;
+            commands::otel::run_push(&url, &otlp_endpoint, cluster, config.update_interval).await?;
         }
     }
 
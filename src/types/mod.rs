@@ -1,12 +1,86 @@
 use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use crate::utils::config::AlertSeverity;
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct NodeHealth {
     pub is_responsive: bool,
+    /// The endpoint answered getVersion with HTTP 429 after every retry.
+    #[serde(default)]
+    pub rate_limited: bool,
     pub version: Option<String>,
     pub current_slot: Option<u64>,
     pub current_epoch: Option<u64>,
     pub total_nodes: Option<u64>,
+    pub epoch_progress: Option<EpochProgress>,
+    /// Round trip of the latest `getSlot` call in milliseconds.
+    pub rpc_latency_ms: Option<f64>,
+    /// Slots between the highest shred the node received and its processed slot.
+    pub slot_lag: Option<u64>,
+    /// Latest root, from `rootSubscribe` or the finalized slot when polling.
+    pub root_slot: Option<u64>,
+    /// Slots between the processed slot and the root.
+    pub root_lag: Option<u64>,
+    /// PubSub endpoint health; `None` when websockets are disabled or unavailable.
+    pub websocket: Option<WebSocketHealth>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebSocketStatus {
+    Healthy,
+    /// Connected and notifying, but slowly, irregularly or behind HTTP.
+    Degraded,
+    #[default]
+    Down,
+}
+
+impl WebSocketStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            WebSocketStatus::Healthy => "Healthy",
+            WebSocketStatus::Degraded => "Degraded",
+            WebSocketStatus::Down => "Down",
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct WebSocketHealth {
+    pub status: WebSocketStatus,
+    pub connected: bool,
+    /// Subscription methods the node acknowledged.
+    pub acknowledged: Vec<String>,
+    /// Subscription methods the node answered with an error.
+    pub rejected: Vec<String>,
+    /// Mean gap between slot notifications in milliseconds.
+    pub mean_interval_ms: Option<f64>,
+    /// Longest gap between slot notifications, including the current one.
+    pub max_gap_ms: Option<f64>,
+    /// Slots the latest slot notification trails the HTTP processed slot.
+    pub slot_lag: Option<i64>,
+    pub reconnects: u64,
+    /// Why the connection failed or why the endpoint is not healthy.
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct EpochProgress {
+    pub epoch: u64,
+    pub slot_index: u64,
+    pub slots_in_epoch: u64,
+    pub percent_complete: f64,
+    pub eta_secs: f64,
+    pub active_stake: u64,
+    pub activating_stake: u64,
+    pub deactivating_stake: u64,
+    /// Epoch the activating/deactivating totals were taken from (latest stake history entry).
+    pub stake_history_epoch: Option<u64>,
+    pub slots_per_epoch: u64,
+    pub warmup: bool,
+    pub first_normal_epoch: u64,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -19,6 +93,585 @@ pub struct NetworkPerformance {
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct TroubleshootResults {
     pub delinquent_validators: u64,
-    pub empty_blocks: u64,
-    pub large_accounts: u64,
-}
\ No newline at end of file
+    /// Stake delegated to delinquent vote accounts, in lamports.
+    pub delinquent_stake: u64,
+    pub delinquent_stake_percent: Option<f64>,
+    pub skip_rate: Option<f64>,
+    pub supply: Option<SupplySummary>,
+    /// Supply at the previous probe, for the change shown next to it.
+    pub previous_supply: Option<SupplySummary>,
+    pub congestion: Option<CongestionReport>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct BlockUtilization {
+    pub slot: u64,
+    pub compute_units: u64,
+    /// Share of the block compute unit limit consumed, in percent.
+    pub utilization: f64,
+    pub transactions: u64,
+    pub failed_transactions: u64,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct AccountHotspot {
+    pub account: String,
+    pub compute_units: u64,
+    pub transactions: u64,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct CongestionReport {
+    /// 0 (idle) to 100 (saturated), weighted from block fullness and failure rate.
+    pub score: u8,
+    pub block_cu_limit: u64,
+    pub avg_utilization: f64,
+    pub max_utilization: f64,
+    /// Failed share of non-vote transactions, as a fraction.
+    pub failed_tx_ratio: f64,
+    pub blocks: Vec<BlockUtilization>,
+    pub hotspots: Vec<AccountHotspot>,
+    pub evidence: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ValidatorSkipRate {
+    pub identity: String,
+    pub leader_slots: u64,
+    pub blocks_produced: u64,
+    pub skipped_slots: u64,
+    pub skip_rate: f64,
+    pub scheduled_slots: Option<u64>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct BlockProductionReport {
+    pub first_slot: u64,
+    pub last_slot: u64,
+    pub leader_slots: u64,
+    pub blocks_produced: u64,
+    pub skipped_slots: u64,
+    pub skip_rate: f64,
+    pub validators: Vec<ValidatorSkipRate>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct UpcomingLeader {
+    pub first_slot: u64,
+    pub last_slot: u64,
+    pub identity: String,
+    /// Estimated Unix time, in seconds, at which `first_slot` starts.
+    pub start_time: f64,
+    pub is_watched: bool,
+}
+
+/// A vote account as reported by `getVoteAccounts`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ValidatorInfo {
+    pub identity: String,
+    pub vote_account: String,
+    pub activated_stake: u64,
+    pub commission: u8,
+    pub last_vote: u64,
+    pub root_slot: u64,
+    pub delinquent: bool,
+}
+
+/// A node visible in gossip as reported by `getClusterNodes`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ClusterNode {
+    pub pubkey: String,
+    pub gossip: Option<String>,
+    pub tpu: Option<String>,
+    pub rpc: Option<String>,
+    pub version: Option<String>,
+    pub feature_set: Option<u32>,
+    pub shred_version: Option<u16>,
+}
+
+/// Nearest-rank percentile of samples sorted in ascending order, `None`
+/// without samples.
+pub fn nearest_rank<T: Copy>(sorted: &[T], percentile: f64) -> Option<T> {
+    let index = ((percentile / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted.get(index.clamp(1, sorted.len().max(1)) - 1).copied()
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct FeePercentiles {
+    pub samples: u64,
+    pub min: u64,
+    pub p25: u64,
+    pub p50: u64,
+    pub p75: u64,
+    pub p90: u64,
+    pub max: u64,
+}
+
+impl FeePercentiles {
+    pub fn from_samples(mut samples: Vec<u64>) -> Self {
+        if samples.is_empty() {
+            return FeePercentiles::default();
+        }
+        samples.sort_unstable();
+        let rank = |percentile| nearest_rank(&samples, percentile).unwrap_or_default();
+        FeePercentiles {
+            samples: samples.len() as u64,
+            min: samples[0],
+            p25: rank(25.0),
+            p50: rank(50.0),
+            p75: rank(75.0),
+            p90: rank(90.0),
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct AccountFees {
+    pub account: String,
+    /// Prioritization fees (micro-lamports per CU) paid by transactions locking this account.
+    pub prioritization_fees: FeePercentiles,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct FeeMarket {
+    /// Prioritization fees (micro-lamports per CU) from `getRecentPrioritizationFees` without account filters.
+    pub global: FeePercentiles,
+    pub accounts: Vec<AccountFees>,
+    pub sampled_blocks: u64,
+    /// Compute unit prices (micro-lamports per CU) set by non-vote transactions in the sampled blocks.
+    pub block_cu_prices: FeePercentiles,
+    /// Priority fees in lamports paid by non-vote transactions in the sampled blocks.
+    pub block_priority_fees: FeePercentiles,
+    /// Share of sampled non-vote transactions that set a compute unit price.
+    pub priority_tx_ratio: f64,
+    /// Suggested compute unit price in micro-lamports per CU.
+    pub recommended_cu_price: u64,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FindingSeverity {
+    #[default]
+    Ok,
+    Warning,
+    Critical,
+}
+
+impl FindingSeverity {
+    pub fn label(self) -> &'static str {
+        match self {
+            FindingSeverity::Ok => "OK",
+            FindingSeverity::Warning => "Warning",
+            FindingSeverity::Critical => "Critical",
+        }
+    }
+}
+
+/// Outcome of a single diagnostic check.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Finding {
+    pub severity: FindingSeverity,
+    pub check: String,
+    pub detail: String,
+    pub remediation: Option<String>,
+}
+
+impl Finding {
+    pub fn ok(check: &str, detail: &str) -> Self {
+        Finding {
+            severity: FindingSeverity::Ok,
+            check: check.to_string(),
+            detail: detail.to_string(),
+            remediation: None,
+        }
+    }
+}
+
+/// Probe values recorded at one refresh, keyed by probe sample name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistorySnapshot {
+    /// Unix time in seconds.
+    pub timestamp: i64,
+    pub endpoint: String,
+    pub values: BTreeMap<String, f64>,
+}
+
+/// One line of a recorded monitoring session. Timestamps are Unix milliseconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SessionEntry {
+    Start {
+        timestamp: i64,
+        endpoint: String,
+    },
+    /// Marks the start of a dashboard refresh; the RPC calls that follow belong to it.
+    Refresh {
+        timestamp: i64,
+    },
+    Rpc {
+        timestamp: i64,
+        method: String,
+        params: serde_json::Value,
+        elapsed_ms: f64,
+        result: Option<serde_json::Value>,
+        error: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertState {
+    Firing,
+    Resolved,
+}
+
+/// A state change of an alert rule, as delivered to notifiers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertEvent {
+    pub rule: String,
+    pub state: AlertState,
+    pub severity: AlertSeverity,
+    pub metric: String,
+    pub value: f64,
+    pub threshold: f64,
+    pub endpoint: String,
+    pub summary: String,
+    /// RFC 3339 time of the state change.
+    pub timestamp: String,
+}
+
+/// Latency and outcome counters for one RPC method, as observed by the client.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RpcMethodStats {
+    pub method: String,
+    pub calls: u64,
+    pub errors: u64,
+    /// HTTP attempts beyond the first, e.g. retries after a 429 response.
+    pub retries: u64,
+    pub total_ms: f64,
+    pub mean_ms: f64,
+    /// Percentiles are estimated from the latency histogram.
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+    /// Calls per latency bucket, bounded by `rpc::LATENCY_BUCKETS_MS` plus a final unbounded bucket.
+    pub latency_buckets: Vec<u64>,
+    /// Calls by HTTP status of the last attempt; `none` when no response was received.
+    pub http_status: BTreeMap<String, u64>,
+    /// Failed calls by JSON-RPC error code, or by error kind when the node returned none.
+    pub errors_by_code: BTreeMap<String, u64>,
+}
+
+/// Benchmark outcome for one RPC method against one endpoint.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct BenchResult {
+    pub endpoint: String,
+    pub method: String,
+    pub requests: u64,
+    pub errors: u64,
+    pub error_rate: f64,
+    /// Successful responses per second over the benchmark duration.
+    pub throughput: f64,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+    /// Failed requests by error type, e.g. `timeout`, `http_429` or `rpc_-32005`.
+    pub errors_by_type: BTreeMap<String, u64>,
+}
+
+/// One endpoint's view of the finalized block compared by `consistency`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct BlockView {
+    pub endpoint: String,
+    pub finalized_slot: Option<u64>,
+    /// Slots behind the most advanced endpoint's finalized slot.
+    pub finalized_lag: Option<u64>,
+    pub blockhash: Option<String>,
+    pub parent_slot: Option<u64>,
+    pub block_time: Option<i64>,
+    pub error: Option<String>,
+}
+
+/// One endpoint's view of an account compared by `consistency`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct AccountView {
+    pub endpoint: String,
+    pub account: String,
+    pub context_slot: Option<u64>,
+    /// `None` when the account does not exist or the request failed.
+    pub lamports: Option<u64>,
+    pub owner: Option<String>,
+    pub data_len: Option<usize>,
+    /// SHA-256 of the account data.
+    pub data_hash: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Result of comparing the same finalized block and accounts across endpoints.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ConsistencyReport {
+    /// Finalized slot whose block was compared.
+    pub slot: Option<u64>,
+    pub blocks: Vec<BlockView>,
+    pub accounts: Vec<AccountView>,
+    pub findings: Vec<Finding>,
+}
+
+/// Account state shown by `account` and the TUI lookup.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct AccountDetails {
+    pub address: String,
+    /// Slot the node read the account at.
+    pub slot: u64,
+    pub lamports: u64,
+    pub owner: String,
+    /// Name of the owner when it is a well-known program.
+    pub owner_name: Option<String>,
+    pub executable: bool,
+    pub data_len: u64,
+    /// Minimum balance for an account of this size to be rent exempt.
+    pub rent_exempt_minimum: u64,
+    pub rent_exempt: bool,
+    /// Parser and account type when the node could decode the data, e.g. `spl-token/account`.
+    pub kind: Option<String>,
+    /// Decoded fields in display order.
+    pub fields: Vec<AccountField>,
+    /// Full decoded contents as returned by the node.
+    pub parsed: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct AccountField {
+    pub name: String,
+    pub value: String,
+}
+
+/// Landing outcome of the probe transactions sent to one endpoint at one
+/// priority fee.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct LandingResult {
+    pub endpoint: String,
+    /// Compute unit price in micro-lamports.
+    pub priority_fee: u64,
+    pub sent: u64,
+    /// Confirmed on chain, including transactions that failed execution.
+    pub landed: u64,
+    /// Landed but failed execution (the fee was still charged).
+    pub failed: u64,
+    /// Accepted by the endpoint but never confirmed before the blockhash
+    /// expired or the probe timed out.
+    pub dropped: u64,
+    /// Rejected by the endpoint when sending.
+    pub send_errors: u64,
+    /// Landed transactions as a percentage of those sent.
+    pub landed_ratio: f64,
+    /// Slots between sending and the slot the transaction landed in.
+    pub mean_slots: f64,
+    pub p50_slots: u64,
+    pub p90_slots: u64,
+    pub max_slots: u64,
+}
+
+/// Lookups that failed against one endpoint during the whole landing probe.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct LandingEndpoint {
+    pub endpoint: String,
+    /// Failed slot, blockhash, status and block height lookups.
+    pub rpc_errors: u64,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct LandingReport {
+    pub endpoints: Vec<LandingEndpoint>,
+    /// One row per endpoint and priority fee.
+    pub results: Vec<LandingResult>,
+}
+
+/// A transaction as shown by `tx`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct TransactionInfo {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    /// `processed`, `confirmed` or `finalized`; `None` once the status has
+    /// left the node's status cache.
+    pub confirmation_status: Option<String>,
+    /// `legacy` or the message version.
+    pub version: String,
+    /// Human-readable failure, `None` when the transaction succeeded.
+    pub error: Option<String>,
+    pub fee: u64,
+    /// Fee paid above the base fee per signature.
+    pub priority_fee: u64,
+    /// Micro-lamports per compute unit.
+    pub compute_unit_price: Option<u64>,
+    pub compute_unit_limit: Option<u32>,
+    pub compute_units_consumed: Option<u64>,
+    pub invocations: Vec<ProgramInvocation>,
+    pub balance_changes: Vec<BalanceChange>,
+    pub logs: Vec<String>,
+}
+
+/// One program invocation, top-level or via CPI, in execution order.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ProgramInvocation {
+    /// Index of the top-level instruction this invocation belongs to.
+    pub instruction: usize,
+    /// 1 for top-level instructions, deeper for cross-program invocations.
+    pub depth: usize,
+    pub program: String,
+    pub program_name: Option<String>,
+    pub compute_units: Option<u64>,
+    /// `success` or the failure reported in the logs.
+    pub result: Option<String>,
+}
+
+/// SOL or token balance of one account before and after a transaction, in
+/// base units (lamports for SOL).
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct BalanceChange {
+    pub account: String,
+    /// Token mint, `None` for SOL.
+    pub mint: Option<String>,
+    pub decimals: u8,
+    pub pre: u64,
+    pub post: u64,
+}
+
+/// SOL supply from `getSupply`, in lamports.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct SupplySummary {
+    pub slot: u64,
+    pub total: u64,
+    pub circulating: u64,
+    pub non_circulating: u64,
+}
+
+/// One entry of `getLargestAccounts`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct LargestAccount {
+    pub rank: usize,
+    pub address: String,
+    /// Name from `address_labels` in the config.
+    pub label: Option<String>,
+    /// Owning program, by name when it is a well-known one.
+    pub owner: Option<String>,
+    pub circulating: bool,
+    pub lamports: u64,
+    /// Share of the total supply, in percent.
+    pub supply_percent: f64,
+    /// Balance change since the previous sample, `None` when the account
+    /// was not in it.
+    pub change: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SupplyFilter {
+    Circulating,
+    NonCirculating,
+}
+
+impl SupplyFilter {
+    pub fn label(self) -> &'static str {
+        match self {
+            SupplyFilter::Circulating => "circulating",
+            SupplyFilter::NonCirculating => "non-circulating",
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct SupplyReport {
+    pub endpoint: String,
+    /// Unix time in seconds.
+    pub timestamp: i64,
+    pub supply: SupplySummary,
+    /// `circulating` or `non-circulating` when the largest accounts were filtered.
+    pub filter: Option<String>,
+    pub accounts: Vec<LargestAccount>,
+    /// Unix time of the sample the changes are measured against.
+    pub previous_timestamp: Option<i64>,
+    pub previous_supply: Option<SupplySummary>,
+    /// Accounts of the previous sample that are no longer among the largest.
+    pub dropped: Vec<String>,
+}
+
+/// Program account and deployment state shown by `program`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ProgramReport {
+    pub program_id: String,
+    /// Slot the node read the program account at.
+    pub slot: u64,
+    pub lamports: u64,
+    pub executable: bool,
+    /// Owning loader, by name when it is a well-known one.
+    pub loader: String,
+    /// Upgradeable loader only: the account holding the deployed code.
+    pub program_data: Option<String>,
+    /// `None` once the program is immutable.
+    pub upgrade_authority: Option<String>,
+    pub deployed_slot: Option<u64>,
+    /// Size of the deployed program in bytes, without the loader header.
+    pub data_len: Option<u64>,
+    pub program_data_lamports: Option<u64>,
+    pub scan: Option<ProgramScan>,
+    pub findings: Vec<Finding>,
+}
+
+/// Result of counting the program's accounts with `getProgramAccounts`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ProgramScan {
+    /// Filters as given on the command line, e.g. `dataSize=165`.
+    pub filters: Vec<String>,
+    pub accounts: Option<u64>,
+    pub elapsed_ms: f64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SkipRateSort {
+    #[default]
+    SkipRate,
+    LeaderSlots,
+    BlocksProduced,
+    Identity,
+}
+
+impl SkipRateSort {
+    pub fn next(self) -> Self {
+        match self {
+            SkipRateSort::SkipRate => SkipRateSort::LeaderSlots,
+            SkipRateSort::LeaderSlots => SkipRateSort::BlocksProduced,
+            SkipRateSort::BlocksProduced => SkipRateSort::Identity,
+            SkipRateSort::Identity => SkipRateSort::SkipRate,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SkipRateSort::SkipRate => "skip rate",
+            SkipRateSort::LeaderSlots => "leader slots",
+            SkipRateSort::BlocksProduced => "blocks produced",
+            SkipRateSort::Identity => "identity",
+        }
+    }
+}
+
+impl BlockProductionReport {
+    pub fn sort_by(&mut self, sort: SkipRateSort) {
+        match sort {
+            SkipRateSort::SkipRate => self.validators.sort_by(|a, b| {
+                b.skip_rate
+                    .total_cmp(&a.skip_rate)
+                    .then(b.leader_slots.cmp(&a.leader_slots))
+            }),
+            SkipRateSort::LeaderSlots => self.validators.sort_by_key(|v| Reverse(v.leader_slots)),
+            SkipRateSort::BlocksProduced => self.validators.sort_by_key(|v| Reverse(v.blocks_produced)),
+            SkipRateSort::Identity => self.validators.sort_by(|a, b| a.identity.cmp(&b.identity)),
+        }
+    }
+}
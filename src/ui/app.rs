@@ -1,12 +1,14 @@
 use std::error::Error;
 use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tui::{
     backend::CrosstermBackend,
     layout::{Constraint, Direction, Layout},
     style::{Color, Modifier, Style},
     text::{Span, Spans},
-    widgets::{Block, Borders, Paragraph, Tabs, List, ListItem},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Tabs, List, ListItem},
     Terminal,
 };
 use crossterm::{
@@ -15,11 +17,27 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use solana_client::rpc_client::RpcClient;
-use solana_sdk::commitment_config::CommitmentConfig;
 
-use crate::types::{NodeHealth, NetworkPerformance, TroubleshootResults};
-use crate::utils::config::Config;
-use super::components::{create_gauge, create_paragraph, create_status_text};
+use crate::commands::account::{account_fields, fetch_account};
+use crate::commands::fees::{self, fetch_fee_market};
+use crate::commands::node_health::{format_duration, format_sol};
+use crate::commands::alerts::AlertManager;
+use crate::commands::export::{to_csv_or_header, write_export};
+use crate::commands::history::{format_series_csv, format_timestamp, snapshot, HistoryStore, HistoryWindow};
+use crate::commands::probe::{probe_cluster, probe_samples};
+use crate::commands::pubsub::{format_websocket, websocket_health, LiveSlots, SlotStream};
+use crate::commands::rpc::{interactive_client, redact_url, RpcObserver, RpcStats};
+use crate::commands::rpc_stats::{csv_rows, format_counts};
+use crate::commands::supply::format_supply;
+use crate::commands::session::{replay_client, ReplayHandle, Session, SessionRecorder};
+use crate::commands::leader_schedule::{format_eta, EpochLeaderSchedule, DEFAULT_SLOT_TIME};
+use crate::types::{
+    BlockProductionReport, FeeMarket, FeePercentiles, NodeHealth, NetworkPerformance, SkipRateSort, TroubleshootResults,
+    AccountDetails, HistorySnapshot, UpcomingLeader, WebSocketStatus,
+};
+use crate::utils::config::{AlertConfig, Config};
+use super::components::{create_gauge, create_line_chart, create_node_status, create_paragraph, create_progress_gauge};
+use super::series::{downsample, MonitorPoint, MonitorSeries, MonitorWindow};
 
 pub enum AppMode {
     NodeHealth,
@@ -33,27 +51,187 @@ struct App {
     node_health: NodeHealth,
     network_performance: NetworkPerformance,
     troubleshoot_results: TroubleshootResults,
+    block_production: BlockProductionReport,
+    skip_rate_sort: SkipRateSort,
+    block_production_scroll: usize,
+    leader_schedule: Option<EpochLeaderSchedule>,
+    upcoming_leaders: Vec<UpcomingLeader>,
+    watched_validators: Vec<String>,
+    fee_market: FeeMarket,
+    fee_accounts: Vec<String>,
+    alerts: AlertManager,
+    alert_error: Option<String>,
+    history: Option<HistoryStore>,
+    /// Last failure to open, read or append to the history file.
+    history_error: Option<String>,
+    history_metrics: Vec<&'static str>,
+    history_metric: usize,
+    history_window: HistoryWindow,
+    /// Snapshots of this endpoint within the selected window, so switching
+    /// metrics or narrowing the window does not re-read the history file.
+    history_snapshots: Vec<HistorySnapshot>,
+    /// Span in seconds covered by `history_snapshots`.
+    history_loaded_secs: i64,
+    history_series: Vec<(i64, f64)>,
+    monitor_series: MonitorSeries,
+    monitor_window: MonitorWindow,
+    recorder: Option<Arc<SessionRecorder>>,
+    rpc_stats: Arc<RpcStats>,
+    /// PubSub slot subscription driving the slot counters between refreshes.
+    slot_stream: Option<SlotStream>,
+    /// Latest state of `slot_stream`, taken before each frame.
+    live_slots: Option<LiveSlots>,
+    /// Playback position shown in the header while replaying a session.
+    replay_status: Option<String>,
+    /// Session time (Unix seconds) of the refresh being replayed.
+    replay_time: Option<f64>,
+    /// Result of the last CSV export, shown in the header until the next key press.
+    export_status: Option<String>,
+    /// Address being typed on the Account tab; `None` when not editing.
+    account_input: Option<String>,
+    account_lookup: Option<Result<AccountDetails, String>>,
     selected_tab: usize,
 }
 
-pub async fn run_app(mode: AppMode, url: &str, config: &Config) -> Result<(), Box<dyn Error>> {
+const TAB_TITLES: [&str; 10] = [
+    "Node Health",
+    "Network Performance",
+    "Troubleshoot",
+    "Monitor",
+    "Block Production",
+    "Leader Schedule",
+    "Fees",
+    "History",
+    "RPC",
+    "Account",
+];
+const MONITOR_TAB: usize = 3;
+const BLOCK_PRODUCTION_TAB: usize = 4;
+const LEADER_SCHEDULE_TAB: usize = 5;
+const FEES_TAB: usize = 6;
+const HISTORY_TAB: usize = 7;
+const RPC_TAB: usize = 8;
+const ACCOUNT_TAB: usize = 9;
+/// Points drawn in the History chart; longer series are averaged into buckets.
+const HISTORY_CHART_POINTS: usize = 600;
+const MONITOR_CHART_POINTS: usize = 300;
+/// Redraw interval while slots are streamed over the websocket.
+const LIVE_FRAME: Duration = Duration::from_millis(250);
+
+type MonitorValue = fn(&MonitorPoint) -> Option<f64>;
+const UPCOMING_LEADERS: usize = 20;
+const SAMPLE_BLOCKS: usize = 4;
+const REPLAY_FRAME: Duration = Duration::from_millis(100);
+const REPLAY_SEEK_MS: f64 = 60_000.0;
+const MIN_REPLAY_SPEED: f64 = 0.125;
+const MAX_REPLAY_SPEED: f64 = 256.0;
+
+impl App {
+    fn new(mode: AppMode, url: &str, config: &Config) -> Self {
+        App {
+            mode,
+            node_health: NodeHealth::default(),
+            network_performance: NetworkPerformance::default(),
+            troubleshoot_results: TroubleshootResults::default(),
+            block_production: BlockProductionReport::default(),
+            skip_rate_sort: SkipRateSort::default(),
+            block_production_scroll: 0,
+            leader_schedule: None,
+            upcoming_leaders: Vec::new(),
+            watched_validators: config.validators.clone(),
+            fee_market: FeeMarket::default(),
+            fee_accounts: config.fee_accounts.clone(),
+            alerts: AlertManager::new(url, &config.alerts),
+            alert_error: None,
+            history: None,
+            history_error: None,
+            history_metrics: probe_samples(&NodeHealth::default(), &NetworkPerformance::default(), &TroubleshootResults::default())
+                .iter()
+                .map(|sample| sample.name)
+                .collect(),
+            history_metric: 0,
+            history_window: HistoryWindow::default(),
+            history_snapshots: Vec::new(),
+            history_loaded_secs: 0,
+            history_series: Vec::new(),
+            monitor_series: MonitorSeries::new(config.update_interval),
+            monitor_window: MonitorWindow::default(),
+            recorder: None,
+            rpc_stats: Arc::new(RpcStats::default()),
+            slot_stream: None,
+            live_slots: None,
+            replay_status: None,
+            replay_time: None,
+            export_status: None,
+            account_input: None,
+            account_lookup: None,
+            selected_tab: 0,
+        }
+    }
+
+    /// Wall-clock time, or the session time while replaying.
+    fn now(&self) -> f64 {
+        self.replay_time
+            .unwrap_or_else(|| chrono::Utc::now().timestamp_millis() as f64 / 1000.0)
+    }
+}
+
+pub async fn run_app(mode: AppMode, url: &str, config: &Config, record: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    let mut app = App::new(mode, url, config);
+
+    if config.history.enabled {
+        match HistoryStore::open(&config.history) {
+            Ok(store) => app.history = Some(store),
+            Err(error) => app.history_error = Some(error.to_string()),
+        }
+        load_history(&mut app, url);
+    }
+
+    let mut observers: Vec<Arc<dyn RpcObserver>> = vec![app.rpc_stats.clone()];
+    if let Some(path) = record {
+        let recorder = Arc::new(SessionRecorder::create(path, url)?);
+        app.recorder = Some(recorder.clone());
+        observers.push(recorder);
+    }
+    let client = interactive_client(url, observers)?;
+
+    app.slot_stream = SlotStream::spawn(url).unwrap_or_else(|e| {
+        log::warn!("Slot streaming unavailable, polling instead: {}", e);
+        None
+    });
+
+    with_terminal(|terminal| run_ui(terminal, &mut app, &client, url, config))
+}
+
+/// Drives the dashboard from a session recorded with `monitor --record`.
+/// History recording and alert notifications are disabled while replaying.
+pub async fn run_replay(path: &Path, speed: f64, config: &Config) -> Result<(), Box<dyn Error>> {
+    let session = Arc::new(Session::load(path)?);
+    let url = session.endpoint.clone();
+    let (client, handle) = replay_client(session.clone());
+
+    let mut app = App::new(AppMode::Monitor, &url, config);
+    let alerts = AlertConfig {
+        notifiers: Vec::new(),
+        ..config.alerts.clone()
+    };
+    app.alerts = AlertManager::new(&url, &alerts);
+    app.selected_tab = MONITOR_TAB;
+
+    with_terminal(|terminal| run_replay_ui(terminal, &mut app, &client, &handle, &session, speed))
+}
+
+fn with_terminal<F>(run: F) -> Result<(), Box<dyn Error>>
+where
+    F: FnOnce(&mut Terminal<CrosstermBackend<io::Stdout>>) -> io::Result<()>,
+{
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = App {
-        mode,
-        node_health: NodeHealth::default(),
-        network_performance: NetworkPerformance::default(),
-        troubleshoot_results: TroubleshootResults::default(),
-        selected_tab: 0,
-    };
-
-    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
-
-    let res = run_ui(&mut terminal, &mut app, &client, config);
+    let res = run(&mut terminal);
 
     disable_raw_mode()?;
     execute!(
@@ -74,37 +252,211 @@ fn run_ui<B: tui::backend::Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
     client: &RpcClient,
+    url: &str,
     config: &Config,
 ) -> io::Result<()> {
     let mut last_update = Instant::now();
     loop {
+        apply_live_slots(app);
         terminal.draw(|f| ui(f, app))?;
 
         if last_update.elapsed() >= Duration::from_secs(config.update_interval) {
-            update_data(app, client);
+            update_data(app, client, url);
             last_update = Instant::now();
         }
 
+        let mut timeout = Duration::from_secs(config.update_interval).saturating_sub(last_update.elapsed());
+        if app.slot_stream.is_some() {
+            timeout = timeout.min(LIVE_FRAME);
+        }
+        if !event::poll(timeout)? {
+            continue;
+        }
         if let Event::Key(key) = event::read()? {
+            if app.account_input.is_some() {
+                edit_account_input(app, client, key.code);
+                continue;
+            }
             match key.code {
                 KeyCode::Char('q') => return Ok(()),
                 KeyCode::Char('r') => {
-                    update_data(app, client);
+                    update_data(app, client, url);
                 }
-                KeyCode::Left => {
-                    app.selected_tab = app.selected_tab.saturating_sub(1);
+                code => handle_navigation(app, code, url),
+            }
+        }
+    }
+}
+
+fn run_replay_ui<B: tui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    client: &RpcClient,
+    handle: &ReplayHandle,
+    session: &Session,
+    mut speed: f64,
+) -> io::Result<()> {
+    let url = session.endpoint.clone();
+    let first = session.refreshes[0].timestamp;
+    let last = session.refreshes[session.refreshes.len() - 1].timestamp;
+    let mut position = first as f64;
+    let mut paused = false;
+    let mut current: Option<usize> = None;
+    let mut last_tick = Instant::now();
+
+    loop {
+        if !paused {
+            position += last_tick.elapsed().as_secs_f64() * 1000.0 * speed;
+        }
+        last_tick = Instant::now();
+        position = position.clamp(first as f64, last as f64);
+
+        // Step through every refresh when playing forward so alert state
+        // evolves as it did live; seeking backwards jumps straight there.
+        let target = session.refresh_at(position as i64);
+        let steps = match current {
+            Some(index) if target > index => (index + 1)..(target + 1),
+            Some(index) if target == index => target..target,
+            _ => target..(target + 1),
+        };
+        for index in steps {
+            handle.load_refresh(index);
+            app.replay_time = Some(session.refreshes[index].timestamp as f64 / 1000.0);
+            update_data(app, client, &url);
+            current = Some(index);
+        }
+
+        app.replay_status = Some(format!(
+            "Replay {} | refresh {}/{} | {}x{} | space pause, +/- speed, ,/. step, [/] seek 1m",
+            format_timestamp(position as i64 / 1000),
+            target + 1,
+            session.refreshes.len(),
+            speed,
+            if paused { " | paused" } else { "" }
+        ));
+        terminal.draw(|f| ui(f, app))?;
+
+        if !event::poll(REPLAY_FRAME)? {
+            continue;
+        }
+        if let Event::Key(key) = event::read()? {
+            if app.account_input.is_some() {
+                edit_account_input(app, client, key.code);
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') => return Ok(()),
+                KeyCode::Char(' ') => paused = !paused,
+                KeyCode::Char('+') => speed = (speed * 2.0).min(MAX_REPLAY_SPEED),
+                KeyCode::Char('-') => speed = (speed / 2.0).max(MIN_REPLAY_SPEED),
+                KeyCode::Char('.') => {
+                    let next = (target + 1).min(session.refreshes.len() - 1);
+                    position = session.refreshes[next].timestamp as f64;
                 }
-                KeyCode::Right => {
-                    if app.selected_tab < 3 {
-                        app.selected_tab += 1;
-                    }
+                KeyCode::Char(',') => {
+                    position = session.refreshes[target.saturating_sub(1)].timestamp as f64;
                 }
-                _ => {}
+                KeyCode::Char(']') => position += REPLAY_SEEK_MS,
+                KeyCode::Char('[') => position -= REPLAY_SEEK_MS,
+                code => handle_navigation(app, code, &url),
             }
         }
     }
 }
 
+/// Tab switching and per-tab keys shared by the live and replay loops.
+fn handle_navigation(app: &mut App, code: KeyCode, url: &str) {
+    app.export_status = None;
+    match code {
+        KeyCode::Char('e') => {
+            app.export_status = Some(match export_tab(app) {
+                Ok(path) => format!("Exported {}", path.display()),
+                Err(e) => format!("Export failed: {}", e),
+            });
+        }
+        KeyCode::Left => {
+            app.selected_tab = app.selected_tab.saturating_sub(1);
+        }
+        KeyCode::Right if app.selected_tab < TAB_TITLES.len() - 1 => {
+            app.selected_tab += 1;
+        }
+        KeyCode::Char('s') if app.selected_tab == BLOCK_PRODUCTION_TAB => {
+            app.skip_rate_sort = app.skip_rate_sort.next();
+            app.block_production.sort_by(app.skip_rate_sort);
+            app.block_production_scroll = 0;
+        }
+        KeyCode::Char('w') if app.selected_tab == MONITOR_TAB => {
+            app.monitor_window = app.monitor_window.next();
+        }
+        KeyCode::Char('m') if app.selected_tab == HISTORY_TAB => {
+            app.history_metric = (app.history_metric + 1) % app.history_metrics.len();
+            select_history_series(app);
+        }
+        KeyCode::Char('w') if app.selected_tab == HISTORY_TAB => {
+            app.history_window = app.history_window.next();
+            load_history(app, url);
+        }
+        KeyCode::Char('/') | KeyCode::Enter if app.selected_tab == ACCOUNT_TAB => {
+            app.account_input = Some(String::new());
+        }
+        KeyCode::Up if app.selected_tab == BLOCK_PRODUCTION_TAB => {
+            app.block_production_scroll = app.block_production_scroll.saturating_sub(1);
+        }
+        KeyCode::Down
+            if app.selected_tab == BLOCK_PRODUCTION_TAB
+                && app.block_production_scroll + 1 < app.block_production.validators.len() =>
+        {
+            app.block_production_scroll += 1;
+        }
+        _ => {}
+    }
+}
+
+/// Keys while an address is typed on the Account tab; Enter looks it up.
+fn edit_account_input(app: &mut App, client: &RpcClient, code: KeyCode) {
+    let Some(input) = app.account_input.as_mut() else {
+        return;
+    };
+    match code {
+        KeyCode::Char(c) => input.push(c),
+        KeyCode::Backspace => {
+            input.pop();
+        }
+        KeyCode::Esc => app.account_input = None,
+        KeyCode::Enter => {
+            let address = input.trim().to_string();
+            app.account_input = None;
+            if !address.is_empty() {
+                app.account_lookup = Some(fetch_account(client, &address).map_err(|e| e.to_string()));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Writes the table or time series behind the current tab to a CSV file in
+/// the working directory.
+fn export_tab(app: &App) -> Result<PathBuf, Box<dyn Error>> {
+    match app.selected_tab {
+        MONITOR_TAB => {
+            let points = app.monitor_series.points(app.monitor_window, app.now());
+            write_export("monitor", &to_csv_or_header(&points)?)
+        }
+        BLOCK_PRODUCTION_TAB => write_export("skip-rates", &to_csv_or_header(&app.block_production.validators)?),
+        LEADER_SCHEDULE_TAB => write_export("leader-schedule", &to_csv_or_header(&app.upcoming_leaders)?),
+        FEES_TAB => write_export("fees", &fees::format_csv(&app.fee_market)?),
+        RPC_TAB => write_export("rpc", &to_csv_or_header(&csv_rows(&app.rpc_stats.snapshot()))?),
+        HISTORY_TAB => {
+            if app.history.is_none() {
+                return Err("History recording is disabled".into());
+            }
+            let metric = app.history_metrics[app.history_metric].to_string();
+            write_export("history", &format_series_csv(&app.history_snapshots, &[metric])?)
+        }
+        _ => Err("Nothing to export on this tab".into()),
+    }
+}
+
 fn ui<B: tui::backend::Backend>(f: &mut tui::Frame<B>, app: &App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -118,10 +470,9 @@ fn ui<B: tui::backend::Backend>(f: &mut tui::Frame<B>, app: &App) {
         )
         .split(f.size());
 
-    let titles = vec!["Node Health", "Network Performance", "Troubleshoot", "Monitor"];
-    let tabs = Tabs::new(titles.into_iter().map(Spans::from).collect())
+    let tabs = Tabs::new(TAB_TITLES.into_iter().map(Spans::from).collect())
         .select(app.selected_tab)
-        .block(Block::default().borders(Borders::ALL).title("SolProbe"))
+        .block(Block::default().borders(Borders::ALL).title(app.export_status.as_deref().or(app.replay_status.as_deref()).unwrap_or("SolProbe")))
         .style(Style::default().fg(Color::White))
         .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
         .divider(Span::raw("|"));
@@ -132,7 +483,13 @@ fn ui<B: tui::backend::Backend>(f: &mut tui::Frame<B>, app: &App) {
         0 => render_node_health(f, app, chunks[1]),
         1 => render_network_performance(f, app, chunks[1]),
         2 => render_troubleshoot(f, app, chunks[1]),
-        3 => render_monitor(f, app, chunks[1]),
+        MONITOR_TAB => render_monitor(f, app, chunks[1]),
+        BLOCK_PRODUCTION_TAB => render_block_production(f, app, chunks[1]),
+        LEADER_SCHEDULE_TAB => render_leader_schedule(f, app, chunks[1]),
+        FEES_TAB => render_fees(f, app, chunks[1]),
+        HISTORY_TAB => render_history(f, app, chunks[1]),
+        RPC_TAB => render_rpc(f, app, chunks[1]),
+        ACCOUNT_TAB => render_account(f, app, chunks[1]),
         _ => unreachable!(),
     }
 }
@@ -146,33 +503,79 @@ fn render_node_health<B: tui::backend::Backend>(f: &mut tui::Frame<B>, app: &App
                 Constraint::Length(3),
                 Constraint::Length(3),
                 Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Length(3),
                 Constraint::Min(0),
             ]
             .as_ref(),
         )
         .split(area);
 
-    let responsive = create_status_text("Node Status", app.node_health.is_responsive);
+    let responsive = create_node_status("Node Status", app.node_health.is_responsive, app.node_health.rate_limited);
     f.render_widget(responsive, chunks[0]);
 
-    if let Some(slot) = app.node_health.current_slot {
-        let slot = create_paragraph("Current Slot", format!("{}", slot));
-        f.render_widget(slot, chunks[1]);
+    let (title, slot) = slot_summary(app);
+    f.render_widget(create_paragraph(&title, slot), chunks[1]);
+
+    if let Some(websocket) = &app.node_health.websocket {
+        let color = match websocket.status {
+            WebSocketStatus::Healthy => Color::Green,
+            WebSocketStatus::Degraded => Color::Yellow,
+            WebSocketStatus::Down => Color::Red,
+        };
+        let websocket = Paragraph::new(Span::styled(format_websocket(websocket), Style::default().fg(color)))
+            .block(Block::default().title("WebSocket").borders(Borders::ALL));
+        f.render_widget(websocket, chunks[2]);
     }
 
     if let Some(version) = &app.node_health.version {
         let version = create_paragraph("Version", version.to_string());
-        f.render_widget(version, chunks[2]);
+        f.render_widget(version, chunks[3]);
     }
 
-    if let Some(epoch) = app.node_health.current_epoch {
-        let epoch = create_paragraph("Current Epoch", format!("{}", epoch));
-        f.render_widget(epoch, chunks[3]);
+    match (&app.node_health.epoch_progress, app.node_health.current_epoch) {
+        (Some(progress), _) => {
+            let label = format!(
+                "{}/{} slots ({:.2}%), boundary in {}",
+                progress.slot_index,
+                progress.slots_in_epoch,
+                progress.percent_complete,
+                format_duration(progress.eta_secs)
+            );
+            let title = format!("Epoch {}", progress.epoch);
+            let gauge = create_progress_gauge(&title, progress.percent_complete / 100.0, label, Color::Magenta);
+            f.render_widget(gauge, chunks[4]);
+        }
+        (None, Some(epoch)) => {
+            let epoch = create_paragraph("Current Epoch", format!("{}", epoch));
+            f.render_widget(epoch, chunks[4]);
+        }
+        (None, None) => {}
     }
 
     if let Some(total_nodes) = app.node_health.total_nodes {
         let total_nodes = create_paragraph("Total Nodes", format!("{}", total_nodes));
-        f.render_widget(total_nodes, chunks[4]);
+        f.render_widget(total_nodes, chunks[5]);
+    }
+
+    if let Some(progress) = &app.node_health.epoch_progress {
+        let history_epoch = progress
+            .stake_history_epoch
+            .map(|epoch| format!(" (as of epoch {})", epoch))
+            .unwrap_or_default();
+        let details = List::new(vec![
+            ListItem::new(format!("Active Stake: {}", format_sol(progress.active_stake))),
+            ListItem::new(format!("Activating Stake: {}{}", format_sol(progress.activating_stake), history_epoch)),
+            ListItem::new(format!("Deactivating Stake: {}{}", format_sol(progress.deactivating_stake), history_epoch)),
+            ListItem::new(format!("Slots per Epoch: {}", progress.slots_per_epoch)),
+            ListItem::new(format!(
+                "Warmup: {} (first normal epoch {})",
+                if progress.warmup { "Yes" } else { "No" },
+                progress.first_normal_epoch
+            )),
+        ])
+        .block(Block::default().borders(Borders::ALL).title("Epoch Details"));
+        f.render_widget(details, chunks[6]);
     }
 }
 
@@ -211,6 +614,7 @@ fn render_troubleshoot<B: tui::backend::Backend>(f: &mut tui::Frame<B>, app: &Ap
                 Constraint::Length(3),
                 Constraint::Length(3),
                 Constraint::Length(3),
+                Constraint::Length(3),
                 Constraint::Min(0),
             ]
             .as_ref(),
@@ -220,95 +624,691 @@ fn render_troubleshoot<B: tui::backend::Backend>(f: &mut tui::Frame<B>, app: &Ap
     let delinquent = create_gauge("Delinquent Validators", app.troubleshoot_results.delinquent_validators as u16, Color::Red);
     f.render_widget(delinquent, chunks[0]);
 
-    let empty_blocks = create_paragraph("Empty Blocks", format!("{}", app.troubleshoot_results.empty_blocks));
-    f.render_widget(empty_blocks, chunks[1]);
+    let skip_rate = match app.troubleshoot_results.skip_rate {
+        Some(skip_rate) => format!("{:.2}%", skip_rate),
+        None => "N/A".to_string(),
+    };
+    let skip_rate = create_paragraph("Cluster Skip Rate", skip_rate);
+    f.render_widget(skip_rate, chunks[1]);
+
+    let supply = match &app.troubleshoot_results.supply {
+        Some(supply) => format_supply(supply, app.troubleshoot_results.previous_supply.as_ref()),
+        None => "N/A".to_string(),
+    };
+    let supply = create_paragraph("Supply", supply);
+    f.render_widget(supply, chunks[2]);
 
-    let large_accounts = create_paragraph("Large Accounts", format!("{}", app.troubleshoot_results.large_accounts));
-    f.render_widget(large_accounts, chunks[2]);
+    let congestion = &app.troubleshoot_results.congestion;
+    let (score, label) = match congestion {
+        Some(congestion) => (congestion.score, format!("{}/100", congestion.score)),
+        None => (0, "N/A".to_string()),
+    };
+    let color = match score {
+        0..=39 => Color::Green,
+        40..=69 => Color::Yellow,
+        _ => Color::Red,
+    };
+    let congestion_gauge = create_progress_gauge("Network Congestion", score as f64 / 100.0, label, color);
+    f.render_widget(congestion_gauge, chunks[3]);
 
-    let recommendations = List::new(vec![
+    let mut items = vec![
         ListItem::new("Investigate delinquent validators if count is high"),
-        ListItem::new("Check for network congestion if many empty blocks"),
-        ListItem::new("Optimize large accounts to improve performance"),
-    ])
-    .block(Block::default().borders(Borders::ALL).title("Recommendations"));
-    f.render_widget(recommendations, chunks[3]);
+        ListItem::new("Check the Block Production tab for leaders with a high skip rate"),
+        ListItem::new("Run `solprobe supply` for the largest accounts and how they changed"),
+    ];
+    if let Some(congestion) = congestion {
+        items.extend(congestion.evidence.iter().map(|evidence| ListItem::new(format!("Congestion: {}", evidence))));
+    }
+    let recommendations = List::new(items).block(Block::default().borders(Borders::ALL).title("Recommendations"));
+    f.render_widget(recommendations, chunks[4]);
 }
 
 fn render_monitor<B: tui::backend::Backend>(f: &mut tui::Frame<B>, app: &App, area: tui::layout::Rect) {
+    let alert_rows = app.alerts.rules().len().max(1) as u16 + usize::from(app.alert_error.is_some()) as u16;
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints(
             [
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(3),
                 Constraint::Length(3),
                 Constraint::Min(0),
+                Constraint::Length(alert_rows + 2),
             ]
             .as_ref(),
         )
         .split(area);
 
-    let responsive = create_status_text("Node Status", app.node_health.is_responsive);
-    f.render_widget(responsive, chunks[0]);
+    let summary = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            [
+                Constraint::Percentage(20),
+                Constraint::Percentage(20),
+                Constraint::Percentage(20),
+                Constraint::Percentage(20),
+                Constraint::Percentage(20),
+            ]
+            .as_ref(),
+        )
+        .split(chunks[0]);
 
-    let tps = create_gauge("TPS", app.network_performance.tps as u16, Color::Blue);
-    f.render_widget(tps, chunks[1]);
+    let responsive = create_node_status("Node Status", app.node_health.is_responsive, app.node_health.rate_limited);
+    f.render_widget(responsive, summary[0]);
 
-    if let Some(slot) = app.node_health.current_slot {
-        let slot = create_paragraph("Current Slot", format!("{}", slot));
-        f.render_widget(slot, chunks[2]);
-    }
+    let (title, slot) = slot_summary(app);
+    f.render_widget(create_paragraph(&title, slot), summary[1]);
 
     if let Some(block_time) = app.network_performance.avg_block_time {
         let block_time = create_paragraph("Avg Block Time", format!("{:.3}s", block_time));
-        f.render_widget(block_time, chunks[3]);
+        f.render_widget(block_time, summary[2]);
     }
 
     let delinquent = create_paragraph("Delinquent Validators", format!("{}", app.troubleshoot_results.delinquent_validators));
-    f.render_widget(delinquent, chunks[4]);
+    f.render_widget(delinquent, summary[3]);
+
+    let window = create_paragraph("Window ('w', 'e' export)", app.monitor_window.label().to_string());
+    f.render_widget(window, summary[4]);
+
+    render_monitor_charts(f, app, chunks[1]);
+    render_alerts(f, app, chunks[2]);
 }
 
-fn update_data(app: &mut App, client: &RpcClient) {
-    match client.get_version() {
-        Ok(version) => {
-            app.node_health.is_responsive = true;
-            app.node_health.version = Some(version.solana_core);
-        },
-        Err(_) => app.node_health.is_responsive = false,
+fn render_monitor_charts(f: &mut tui::Frame, app: &App, area: tui::layout::Rect) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Percentage(40),
+                Constraint::Percentage(30),
+                Constraint::Percentage(30),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+    let halves = |area| {
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+            .split(area)
+    };
+    let middle = halves(rows[1]);
+    let bottom = halves(rows[2]);
+
+    let window = app.monitor_window;
+    let now = app.now();
+    let labels = || {
+        [
+            format!("-{}", window.label()),
+            chrono::DateTime::from_timestamp(now as i64, 0)
+                .map(|time| time.with_timezone(&chrono::Local).format("%H:%M:%S").to_string())
+                .unwrap_or_default(),
+        ]
+    };
+    let latest = app.monitor_series.last();
+    let current = |value: Option<f64>, unit: &str| {
+        value.map(|value| format!("{:.2}{}", value, unit)).unwrap_or_else(|| "-".to_string())
+    };
+
+    let charts: [(tui::layout::Rect, String, MonitorValue, Color); 5] = [
+        (
+            rows[0],
+            format!("TPS ({})", current(latest.map(|point| point.tps), "")),
+            |point| Some(point.tps),
+            Color::Blue,
+        ),
+        (
+            middle[0],
+            format!("Slot Rate ({})", current(latest.and_then(|point| point.slot_rate), " slots/s")),
+            |point| point.slot_rate,
+            Color::Green,
+        ),
+        (
+            middle[1],
+            format!("RPC Latency ({})", current(latest.and_then(|point| point.rpc_latency_ms), " ms")),
+            |point| point.rpc_latency_ms,
+            Color::Yellow,
+        ),
+        (
+            bottom[0],
+            format!("Delinquent Stake ({})", current(latest.and_then(|point| point.delinquent_stake_percent), "%")),
+            |point| point.delinquent_stake_percent,
+            Color::Red,
+        ),
+        (
+            bottom[1],
+            format!("Slot Lag ({})", current(latest.and_then(|point| point.slot_lag), " slots")),
+            |point| point.slot_lag,
+            Color::Magenta,
+        ),
+    ];
+
+    for (area, title, value, color) in charts {
+        let points = app.monitor_series.window(window, now, MONITOR_CHART_POINTS, value);
+        let chart = create_line_chart(title, &points, window.secs(), labels(), color);
+        f.render_widget(chart, area);
     }
+}
 
-    if let Ok(slot) = client.get_slot() {
-        app.node_health.current_slot = Some(slot);
+fn render_alerts(f: &mut tui::Frame, app: &App, area: tui::layout::Rect) {
+    let mut items: Vec<ListItem> = app
+        .alerts
+        .rules()
+        .iter()
+        .map(|status| {
+            let value = status.value.map(|value| format!("{:.2}", value)).unwrap_or_else(|| "-".to_string());
+            let (state, color) = match &status.since {
+                Some(since) if status.firing => (format!("FIRING since {}", since.format("%H:%M:%S")), Color::Red),
+                _ => ("OK".to_string(), Color::Green),
+            };
+            ListItem::new(format!("{} ({} = {}): {}", status.rule.name, status.rule.metric, value, state))
+                .style(Style::default().fg(color))
+        })
+        .collect();
+    if items.is_empty() {
+        items.push(ListItem::new("No alert rules configured"));
+    }
+    if let Some(error) = &app.alert_error {
+        items.push(ListItem::new(format!("Last delivery error: {}", error)).style(Style::default().fg(Color::Yellow)));
     }
 
-    if let Ok(epoch_info) = client.get_epoch_info() {
-        app.node_health.current_epoch = Some(epoch_info.epoch);
+    let alerts = List::new(items).block(Block::default().borders(Borders::ALL).title("Alerts"));
+    f.render_widget(alerts, area);
+}
+
+fn render_block_production(f: &mut tui::Frame, app: &App, area: tui::layout::Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(3),
+                Constraint::Min(0),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+
+    let report = &app.block_production;
+    let summary = create_paragraph(
+        "Cluster Skip Rate",
+        format!(
+            "{:.2}% ({} of {} leader slots skipped, slots {} - {})",
+            report.skip_rate, report.skipped_slots, report.leader_slots, report.first_slot, report.last_slot
+        ),
+    );
+    f.render_widget(summary, chunks[0]);
+
+    let header = Row::new(vec!["Identity", "Leader Slots", "Produced", "Skipped", "Skip Rate"])
+        .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+    let rows = report.validators.iter().skip(app.block_production_scroll).map(|validator| {
+        let color = if validator.skip_rate > report.skip_rate * 2.0 && validator.skipped_slots > 0 {
+            Color::Red
+        } else {
+            Color::White
+        };
+        Row::new(vec![
+            Cell::from(validator.identity.clone()),
+            Cell::from(validator.leader_slots.to_string()),
+            Cell::from(validator.blocks_produced.to_string()),
+            Cell::from(validator.skipped_slots.to_string()),
+            Cell::from(format!("{:.2}%", validator.skip_rate)),
+        ])
+        .style(Style::default().fg(color))
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(45),
+            Constraint::Length(13),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(10),
+        ],
+    )
+    .header(header)
+    .block(Block::default().borders(Borders::ALL).title(format!(
+        "Validators (sorted by {}, 's' to change, Up/Down to scroll, 'e' to export)",
+        app.skip_rate_sort.label()
+    )));
+    f.render_widget(table, chunks[1]);
+}
+
+fn render_leader_schedule(f: &mut tui::Frame, app: &App, area: tui::layout::Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(3),
+                Constraint::Min(0),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+
+    let slot_time = app.network_performance.avg_block_time.unwrap_or(DEFAULT_SLOT_TIME);
+    let epoch = match &app.leader_schedule {
+        Some(schedule) => format!("Epoch {} (slot time {:.3}s)", schedule.epoch, slot_time),
+        None => "Loading leader schedule...".to_string(),
+    };
+    f.render_widget(create_paragraph("Leader Schedule", epoch), chunks[0]);
+
+    let now = app.now();
+    let header = Row::new(vec!["Slots", "Leader", "Estimated Start"])
+        .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+    let rows = app.upcoming_leaders.iter().map(|leader| {
+        let style = if leader.is_watched {
+            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        Row::new(vec![
+            Cell::from(format!("{} - {}", leader.first_slot, leader.last_slot)),
+            Cell::from(leader.identity.clone()),
+            Cell::from(format_eta(leader.start_time, now)),
+        ])
+        .style(style)
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(24),
+            Constraint::Length(45),
+            Constraint::Length(22),
+        ],
+    )
+    .header(header)
+    .block(Block::default().borders(Borders::ALL).title("Upcoming Leaders"));
+    f.render_widget(table, chunks[1]);
+}
+
+fn render_fees(f: &mut tui::Frame, app: &App, area: tui::layout::Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Min(0),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+
+    let market = &app.fee_market;
+    let recommended = create_paragraph(
+        "Recommended CU Price",
+        format!("{} micro-lamports per CU", market.recommended_cu_price),
+    );
+    f.render_widget(recommended, chunks[0]);
+
+    let sampled = create_paragraph(
+        "Sampled Blocks",
+        format!(
+            "{} blocks, {:.1}% of non-vote transactions set a CU price",
+            market.sampled_blocks,
+            market.priority_tx_ratio * 100.0
+        ),
+    );
+    f.render_widget(sampled, chunks[1]);
+
+    let fee_row = |scope: String, fees: &FeePercentiles| {
+        Row::new(vec![
+            scope,
+            fees.samples.to_string(),
+            fees.p25.to_string(),
+            fees.p50.to_string(),
+            fees.p75.to_string(),
+            fees.p90.to_string(),
+            fees.max.to_string(),
+        ])
+    };
+    let mut rows = vec![fee_row("Global (micro-lamports/CU)".to_string(), &market.global)];
+    rows.extend(
+        market
+            .accounts
+            .iter()
+            .map(|account| fee_row(account.account.clone(), &account.prioritization_fees)),
+    );
+    rows.push(fee_row("Block CU prices (micro-lamports/CU)".to_string(), &market.block_cu_prices));
+    rows.push(fee_row("Block priority fees (lamports)".to_string(), &market.block_priority_fees));
+
+    let header = Row::new(vec!["Scope", "Samples", "P25", "P50", "P75", "P90", "Max"])
+        .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(45),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(12),
+        ],
+    )
+    .header(header)
+    .block(Block::default().borders(Borders::ALL).title("Fee Percentiles"));
+    f.render_widget(table, chunks[2]);
+}
+
+fn render_rpc(f: &mut tui::Frame, app: &App, area: tui::layout::Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(3),
+                Constraint::Min(0),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+
+    let methods = app.rpc_stats.snapshot();
+    let calls: u64 = methods.iter().map(|stats| stats.calls).sum();
+    let errors: u64 = methods.iter().map(|stats| stats.errors).sum();
+    let retries: u64 = methods.iter().map(|stats| stats.retries).sum();
+    let total_ms: f64 = methods.iter().map(|stats| stats.total_ms).sum();
+    let summary = if calls == 0 {
+        "No RPC calls observed yet".to_string()
+    } else {
+        format!(
+            "{} calls, {} errors, {} retries, {:.1}s spent waiting on the node ('e' to export)",
+            calls,
+            errors,
+            retries,
+            total_ms / 1000.0
+        )
+    };
+    f.render_widget(create_paragraph("RPC Calls", summary), chunks[0]);
+
+    let header = Row::new(vec![
+        "Method", "Calls", "Errors", "Retries", "Share", "Mean ms", "P50 ms", "P90 ms", "P99 ms", "Max ms", "HTTP", "Errors",
+    ])
+    .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+    let rows = methods.iter().map(|stats| {
+        let color = if stats.errors > 0 { Color::Red } else { Color::White };
+        Row::new(vec![
+            stats.method.clone(),
+            stats.calls.to_string(),
+            stats.errors.to_string(),
+            stats.retries.to_string(),
+            format!("{:.1}%", if total_ms > 0.0 { stats.total_ms / total_ms * 100.0 } else { 0.0 }),
+            format!("{:.1}", stats.mean_ms),
+            format!("{:.1}", stats.p50_ms),
+            format!("{:.1}", stats.p90_ms),
+            format!("{:.1}", stats.p99_ms),
+            format!("{:.1}", stats.max_ms),
+            format_counts(&stats.http_status),
+            format_counts(&stats.errors_by_code),
+        ])
+        .style(Style::default().fg(color))
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(32),
+            Constraint::Length(7),
+            Constraint::Length(7),
+            Constraint::Length(8),
+            Constraint::Length(7),
+            Constraint::Length(9),
+            Constraint::Length(9),
+            Constraint::Length(9),
+            Constraint::Length(9),
+            Constraint::Length(9),
+            Constraint::Length(14),
+            Constraint::Min(10),
+        ],
+    )
+    .header(header)
+    .block(Block::default().borders(Borders::ALL).title("Methods (by total time)"));
+    f.render_widget(table, chunks[1]);
+}
+
+fn render_account(f: &mut tui::Frame, app: &App, area: tui::layout::Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(3),
+                Constraint::Min(0),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+
+    let lookup = match (&app.account_input, &app.account_lookup) {
+        (Some(input), _) => format!("{}_", input),
+        (None, Some(Ok(details))) => format!("{} ('/' to look up another account)", details.address),
+        (None, _) => "Press '/' and enter an account address".to_string(),
+    };
+    let title = if app.account_input.is_some() {
+        "Account Lookup (Enter to look up, Esc to cancel)"
+    } else {
+        "Account Lookup"
+    };
+    f.render_widget(create_paragraph(title, lookup), chunks[0]);
+
+    match &app.account_lookup {
+        Some(Ok(details)) => {
+            let rows = account_fields(details)
+                .into_iter()
+                .map(|field| Row::new(vec![field.name, field.value]));
+            let table = Table::new(rows, [Constraint::Length(24), Constraint::Percentage(80)])
+                .block(Block::default().borders(Borders::ALL).title(format!("Account at slot {}", details.slot)));
+            f.render_widget(table, chunks[1]);
+        }
+        Some(Err(error)) => {
+            let error = Paragraph::new(Span::styled(error.clone(), Style::default().fg(Color::Red)))
+                .block(Block::default().title("Account").borders(Borders::ALL));
+            f.render_widget(error, chunks[1]);
+        }
+        None => {}
+    }
+}
+
+fn update_data(app: &mut App, client: &RpcClient, url: &str) {
+    if let Some(recorder) = &app.recorder {
+        recorder.mark_refresh();
     }
 
-    if let Ok(cluster_nodes) = client.get_cluster_nodes() {
-        app.node_health.total_nodes = Some(cluster_nodes.len() as u64);
+    let context = probe_cluster(
+        client,
+        &mut app.node_health,
+        &mut app.network_performance,
+        &mut app.troubleshoot_results,
+        &mut app.leader_schedule,
+        SAMPLE_BLOCKS,
+    );
+    if let Some(stream) = &app.slot_stream {
+        app.node_health.websocket = Some(websocket_health(client, &stream.snapshot()));
     }
 
-    if let Ok(recent_performance) = client.get_recent_performance_samples(Some(1)) {
-        if let Some(latest) = recent_performance.first() {
-            app.network_performance.tps = latest.num_transactions as f64 / latest.sample_period_secs as f64;
-            app.network_performance.avg_block_time = Some(latest.sample_period_secs as f64 / latest.num_slots as f64);
+    if let Some(epoch_info) = &context.epoch_info {
+        if let Some(schedule) = &app.leader_schedule {
+            let slot_time = app.network_performance.avg_block_time.unwrap_or(DEFAULT_SLOT_TIME);
+            app.upcoming_leaders = schedule.upcoming(
+                epoch_info.absolute_slot,
+                UPCOMING_LEADERS,
+                slot_time,
+                app.now(),
+                &app.watched_validators,
+            );
         }
     }
 
-    app.network_performance.confirmation_time = Some(0.5);
+    if let Some(mut report) = context.block_production {
+        report.sort_by(app.skip_rate_sort);
+        app.block_production = report;
+    }
+
+    if let Ok(market) = fetch_fee_market(client, &app.fee_accounts, &context.blocks) {
+        app.fee_market = market;
+    }
+
+    apply_live_slots(app);
+    let now = app.now();
+    app.monitor_series
+        .push(now, &app.node_health, &app.network_performance, &app.troubleshoot_results);
 
-    if let Ok(vote_accounts) = client.get_vote_accounts() {
-        app.troubleshoot_results.delinquent_validators = vote_accounts.delinquent.len() as u64;
+    let samples = probe_samples(&app.node_health, &app.network_performance, &app.troubleshoot_results);
+    if let Some(store) = &mut app.history {
+        let snapshot = snapshot(url, &samples);
+        if let Err(error) = store.append(&snapshot) {
+            app.history_error = Some(error.to_string());
+        } else {
+            if let Some(value) = snapshot.values.get(app.history_metrics[app.history_metric]) {
+                app.history_series.push((snapshot.timestamp, *value));
+            }
+            let since = snapshot.timestamp - app.history_window.secs();
+            app.history_series.retain(|(timestamp, _)| *timestamp >= since);
+            app.history_snapshots.retain(|snapshot| snapshot.timestamp >= since);
+            app.history_snapshots.push(snapshot);
+        }
     }
 
-    if let Ok(blocks) = client.get_blocks_with_limit(client.get_slot().unwrap_or(0), 100) {
-        app.troubleshoot_results.empty_blocks = blocks.len() as u64;
+    let events = app.alerts.evaluate(&samples);
+    app.alerts.notify(&events);
+    if let Some(error) = app.alerts.take_error() {
+        app.alert_error = Some(error);
     }
+}
 
-    if let Ok(largest_accounts) = client.get_largest_accounts(None) {
-        app.troubleshoot_results.large_accounts = largest_accounts.value.len() as u64;
+/// Replaces the polled slot, root and root lag with the streamed ones while
+/// the websocket is delivering notifications.
+fn apply_live_slots(app: &mut App) {
+    let Some(stream) = &app.slot_stream else {
+        return;
+    };
+    let live = stream.snapshot();
+    if live.is_live() {
+        if let Some(slot) = live.slot {
+            app.node_health.current_slot = Some(slot);
+        }
+        if let Some(root) = live.root {
+            app.node_health.root_slot = Some(root);
+        }
+        if let Some(lag) = live.root_lag() {
+            app.node_health.root_lag = Some(lag);
+        }
     }
-}
\ No newline at end of file
+    app.live_slots = Some(live);
+}
+
+/// Title and text of the slot panel: the current slot, root lag and slot
+/// rate, and whether they are streamed or polled.
+fn slot_summary(app: &App) -> (String, String) {
+    let live = app.live_slots.as_ref().filter(|live| live.is_live());
+    let source = match (&app.live_slots, live) {
+        (_, Some(_)) => "Current Slot (live)".to_string(),
+        (Some(stream), None) => match &stream.error {
+            Some(error) => format!("Current Slot (polling, websocket: {})", error),
+            None => "Current Slot (polling, websocket connecting)".to_string(),
+        },
+        (None, None) => "Current Slot".to_string(),
+    };
+    let Some(slot) = app.node_health.current_slot else {
+        return (source, "-".to_string());
+    };
+
+    let mut text = slot.to_string();
+    if let (Some(root), Some(lag)) = (app.node_health.root_slot, app.node_health.root_lag) {
+        text.push_str(&format!("   root {} (lag {})", root, lag));
+    }
+    let slot_rate = match live {
+        Some(live) => live.slot_rate,
+        None => app.monitor_series.last().and_then(|point| point.slot_rate),
+    };
+    if let Some(rate) = slot_rate {
+        text.push_str(&format!("   {:.2} slots/s", rate));
+    }
+    (source, text)
+}
+
+/// Brings the cached snapshots in line with the selected window, reading the
+/// history file only when the window grows past what is cached.
+fn load_history(app: &mut App, url: &str) {
+    let Some(store) = &app.history else {
+        return;
+    };
+    let window = app.history_window.secs();
+    let until = chrono::Utc::now().timestamp();
+    if window > app.history_loaded_secs {
+        let endpoint = redact_url(url);
+        app.history_snapshots = store.load(until - window, until).unwrap_or_else(|error| {
+            app.history_error = Some(error.to_string());
+            Vec::new()
+        });
+        app.history_snapshots.retain(|snapshot| snapshot.endpoint == endpoint);
+    } else {
+        app.history_snapshots.retain(|snapshot| snapshot.timestamp >= until - window);
+    }
+    app.history_loaded_secs = window;
+    select_history_series(app);
+}
+
+fn select_history_series(app: &mut App) {
+    let metric = app.history_metrics[app.history_metric];
+    app.history_series = app
+        .history_snapshots
+        .iter()
+        .filter_map(|snapshot| snapshot.values.get(metric).map(|value| (snapshot.timestamp, *value)))
+        .collect();
+}
+
+fn render_history(f: &mut tui::Frame, app: &App, area: tui::layout::Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(3),
+                Constraint::Min(0),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+
+    let metric = app.history_metrics[app.history_metric];
+    let window = app.history_window.secs();
+    let now = chrono::Utc::now().timestamp();
+    let since = now - window;
+
+    let summary = match (app.history.as_ref(), app.history_series.first(), app.history_series.last()) {
+        (None, _, _) => match &app.history_error {
+            Some(error) => format!("History recording is unavailable: {}", error),
+            None => "History recording is disabled".to_string(),
+        },
+        (Some(_), Some((first, _)), Some((_, last))) => {
+            let min = app.history_series.iter().map(|(_, value)| *value).fold(f64::INFINITY, f64::min);
+            let max = app.history_series.iter().map(|(_, value)| *value).fold(f64::NEG_INFINITY, f64::max);
+            format!(
+                "Last {:.3}, min {:.3}, max {:.3} over {} points since {} ('m' metric, 'w' window, 'e' export)",
+                last,
+                min,
+                max,
+                app.history_series.len(),
+                format_timestamp(*first)
+            )
+        }
+        (Some(_), _, _) => "No samples recorded in this window yet ('m' metric, 'w' window, 'e' export)".to_string(),
+    };
+    let summary = match (&app.history, &app.history_error) {
+        (Some(_), Some(error)) => format!("{} | Last history error: {}", summary, error),
+        _ => summary,
+    };
+    let title = format!("{} ({})", metric, app.history_window.label());
+    f.render_widget(create_paragraph(&title, summary), chunks[0]);
+
+    let series: Vec<(f64, f64)> = app
+        .history_series
+        .iter()
+        .map(|(timestamp, value)| (*timestamp as f64, *value))
+        .collect();
+    let points = downsample(&series, since as f64, window as f64, HISTORY_CHART_POINTS);
+    let chart = create_line_chart(
+        "History".to_string(),
+        &points,
+        window as f64,
+        [format_timestamp(since), format_timestamp(now)],
+        Color::Cyan,
+    );
+    f.render_widget(chart, chunks[1]);
+}
@@ -1,21 +1,75 @@
-use tui::widgets::{Block, Borders, Gauge, Paragraph};
+use tui::widgets::{Axis, Block, Borders, Chart, Dataset, Gauge, GraphType, Paragraph};
 use tui::layout::Rect;
 use tui::style::{Color, Style};
+use tui::symbols::Marker;
 use tui::text::Span;
 
-pub fn create_gauge(title: &str, value: u16, color: Color) -> Gauge {
+pub fn create_gauge(title: &str, value: u16, color: Color) -> Gauge<'_> {
     Gauge::default()
         .block(Block::default().title(title).borders(Borders::ALL))
         .gauge_style(Style::default().fg(color))
         .percent(value)
 }
 
-pub fn create_paragraph(title: &str, value: String) -> Paragraph {
+pub fn create_progress_gauge(title: &str, ratio: f64, label: String, color: Color) -> Gauge<'_> {
+    Gauge::default()
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .gauge_style(Style::default().fg(color))
+        .ratio(ratio.clamp(0.0, 1.0))
+        .label(label)
+}
+
+/// Line chart of `points` over `[0, window]` seconds, with the y axis fitted
+/// to the data and labelled at its bounds.
+pub fn create_line_chart<'a>(
+    title: String,
+    points: &'a [(f64, f64)],
+    window: f64,
+    x_labels: [String; 2],
+    color: Color,
+) -> Chart<'a> {
+    let (min, max) = points
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), (_, value)| (min.min(*value), max.max(*value)));
+    let (min, max) = if points.is_empty() {
+        (0.0, 1.0)
+    } else if min == max {
+        (min - 1.0, max + 1.0)
+    } else {
+        (min, max)
+    };
+
+    let dataset = Dataset::default()
+        .marker(Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(color))
+        .data(points);
+    let [start, end] = x_labels;
+    Chart::new(vec![dataset])
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .x_axis(Axis::default().bounds([0.0, window]).labels(vec![Span::raw(start), Span::raw(end)]))
+        .y_axis(
+            Axis::default()
+                .bounds([min, max])
+                .labels(vec![Span::raw(format!("{:.2}", min)), Span::raw(format!("{:.2}", max))]),
+        )
+}
+
+pub fn create_paragraph(title: &str, value: String) -> Paragraph<'_> {
     Paragraph::new(value)
         .block(Block::default().title(title).borders(Borders::ALL))
 }
 
-pub fn create_status_text(title: &str, status: bool) -> Paragraph {
+/// Node status that tells an endpoint throttling us apart from one that is down.
+pub fn create_node_status(title: &str, responsive: bool, rate_limited: bool) -> Paragraph<'_> {
+    if responsive || !rate_limited {
+        return create_status_text(title, responsive);
+    }
+    Paragraph::new(Span::styled("Rate limited", Style::default().fg(Color::Yellow)))
+        .block(Block::default().title(title).borders(Borders::ALL))
+}
+
+pub fn create_status_text(title: &str, status: bool) -> Paragraph<'_> {
     let (text, color) = if status {
         ("Online", Color::Green)
     } else {
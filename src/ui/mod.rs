@@ -1,2 +1,3 @@
 pub mod app;
-pub mod components;
\ No newline at end of file
+pub mod components;
+pub mod series;
\ No newline at end of file
@@ -0,0 +1,204 @@
+use serde::Serialize;
+use std::collections::VecDeque;
+
+use crate::types::{NetworkPerformance, NodeHealth, TroubleshootResults};
+
+/// Time span shown by the Monitor tab charts.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MonitorWindow {
+    #[default]
+    FiveMinutes,
+    Hour,
+    Day,
+}
+
+impl MonitorWindow {
+    pub fn next(self) -> Self {
+        match self {
+            MonitorWindow::FiveMinutes => MonitorWindow::Hour,
+            MonitorWindow::Hour => MonitorWindow::Day,
+            MonitorWindow::Day => MonitorWindow::FiveMinutes,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MonitorWindow::FiveMinutes => "5m",
+            MonitorWindow::Hour => "1h",
+            MonitorWindow::Day => "24h",
+        }
+    }
+
+    pub fn secs(self) -> f64 {
+        match self {
+            MonitorWindow::FiveMinutes => 300.0,
+            MonitorWindow::Hour => 3600.0,
+            MonitorWindow::Day => 86400.0,
+        }
+    }
+}
+
+/// Values charted in the Monitor tab at one refresh.
+#[derive(Debug, Default, Clone, Copy, Serialize)]
+pub struct MonitorPoint {
+    /// Unix time in seconds.
+    pub timestamp: f64,
+    pub slot: Option<u64>,
+    pub tps: f64,
+    /// Slots per second since the previous refresh.
+    pub slot_rate: Option<f64>,
+    pub rpc_latency_ms: Option<f64>,
+    pub delinquent_stake_percent: Option<f64>,
+    pub slot_lag: Option<f64>,
+}
+
+/// Fixed-capacity ring buffer holding enough refreshes for the longest window.
+pub struct MonitorSeries {
+    points: VecDeque<MonitorPoint>,
+    capacity: usize,
+}
+
+impl MonitorSeries {
+    pub fn new(update_interval: u64) -> Self {
+        let capacity = (MonitorWindow::Day.secs() as usize / update_interval.max(1) as usize) + 1;
+        MonitorSeries {
+            points: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn push(
+        &mut self,
+        timestamp: f64,
+        health: &NodeHealth,
+        performance: &NetworkPerformance,
+        results: &TroubleshootResults,
+    ) {
+        let slot_rate = match (self.points.back(), health.current_slot) {
+            (Some(MonitorPoint { timestamp: previous, slot: Some(previous_slot), .. }), Some(slot))
+                if timestamp > *previous =>
+            {
+                Some(slot.saturating_sub(*previous_slot) as f64 / (timestamp - previous))
+            }
+            _ => None,
+        };
+
+        if self.points.len() == self.capacity {
+            self.points.pop_front();
+        }
+        self.points.push_back(MonitorPoint {
+            timestamp,
+            slot: health.current_slot,
+            tps: performance.tps,
+            slot_rate,
+            rpc_latency_ms: health.rpc_latency_ms,
+            delinquent_stake_percent: results.delinquent_stake_percent,
+            slot_lag: health.slot_lag.map(|lag| lag as f64),
+        });
+    }
+
+    pub fn last(&self) -> Option<&MonitorPoint> {
+        self.points.back()
+    }
+
+    /// Raw points inside `window` ending at `now`, oldest first.
+    pub fn points(&self, window: MonitorWindow, now: f64) -> Vec<MonitorPoint> {
+        let since = now - window.secs();
+        self.points.iter().filter(|point| point.timestamp >= since).copied().collect()
+    }
+
+    /// Points inside `window` ending at `now`, as (seconds since the window
+    /// start, value), averaged down to at most `max_points`.
+    pub fn window<F>(&self, window: MonitorWindow, now: f64, max_points: usize, value: F) -> Vec<(f64, f64)>
+    where
+        F: Fn(&MonitorPoint) -> Option<f64>,
+    {
+        let since = now - window.secs();
+        let points: Vec<(f64, f64)> = self
+            .points
+            .iter()
+            .filter(|point| point.timestamp >= since)
+            .filter_map(|point| value(point).map(|value| (point.timestamp, value)))
+            .collect();
+        downsample(&points, since, window.secs(), max_points)
+    }
+}
+
+/// Averages `(timestamp, value)` points into at most `max_points` equal-width
+/// time buckets, returning (seconds since `since`, value).
+pub fn downsample(series: &[(f64, f64)], since: f64, window: f64, max_points: usize) -> Vec<(f64, f64)> {
+    let bucket_secs = (window / max_points as f64).max(1.0);
+    let mut points: Vec<(f64, f64, usize)> = Vec::new();
+    for (timestamp, value) in series {
+        let bucket = ((timestamp - since) / bucket_secs).floor() * bucket_secs;
+        match points.last_mut() {
+            Some((last, sum, count)) if *last == bucket => {
+                *sum += value;
+                *count += 1;
+            }
+            _ => points.push((bucket, *value, 1)),
+        }
+    }
+    points.into_iter().map(|(bucket, sum, count)| (bucket, sum / count as f64)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_slot(series: &mut MonitorSeries, timestamp: f64, slot: Option<u64>) {
+        let health = NodeHealth {
+            current_slot: slot,
+            ..NodeHealth::default()
+        };
+        series.push(timestamp, &health, &NetworkPerformance::default(), &TroubleshootResults::default());
+    }
+
+    #[test]
+    fn push_derives_slot_rate_from_the_previous_point() {
+        let mut series = MonitorSeries::new(2);
+        push_slot(&mut series, 100.0, Some(1_000));
+        assert_eq!(series.last().unwrap().slot_rate, None);
+        push_slot(&mut series, 104.0, Some(1_010));
+        assert_eq!(series.last().unwrap().slot_rate, Some(2.5));
+        push_slot(&mut series, 104.0, Some(1_020));
+        assert_eq!(series.last().unwrap().slot_rate, None);
+        push_slot(&mut series, 106.0, None);
+        assert_eq!(series.last().unwrap().slot_rate, None);
+    }
+
+    #[test]
+    fn push_drops_the_oldest_point_at_capacity() {
+        // A day at 12 hour refreshes holds three points.
+        let mut series = MonitorSeries::new(43_200);
+        for timestamp in 0..5 {
+            push_slot(&mut series, timestamp as f64, None);
+        }
+        let timestamps: Vec<f64> = series.points(MonitorWindow::Day, 4.0).iter().map(|point| point.timestamp).collect();
+        assert_eq!(timestamps, vec![2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn window_keeps_points_inside_the_window_with_values() {
+        let mut series = MonitorSeries::new(1);
+        push_slot(&mut series, 1_000.0, Some(1));
+        push_slot(&mut series, 1_600.0, None);
+        push_slot(&mut series, 1_700.0, Some(2));
+        push_slot(&mut series, 1_900.0, Some(3));
+
+        assert_eq!(series.points(MonitorWindow::FiveMinutes, 1_900.0).len(), 3);
+        assert_eq!(series.points(MonitorWindow::Hour, 1_900.0).len(), 4);
+
+        let slots = series.window(MonitorWindow::FiveMinutes, 1_900.0, 300, |point| point.slot.map(|slot| slot as f64));
+        assert_eq!(slots, vec![(100.0, 2.0), (300.0, 3.0)]);
+    }
+
+    #[test]
+    fn downsample_averages_equal_width_buckets() {
+        let series = [(0.0, 1.0), (5.0, 3.0), (10.0, 5.0), (25.0, 7.0), (29.0, 9.0)];
+        assert_eq!(downsample(&series, 0.0, 30.0, 3), vec![(0.0, 2.0), (10.0, 5.0), (20.0, 8.0)]);
+        // Buckets are never narrower than a second.
+        assert_eq!(downsample(&series, 0.0, 30.0, 300).len(), series.len());
+        assert!(downsample(&[], 0.0, 30.0, 3).is_empty());
+    }
+}
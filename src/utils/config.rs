@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
 use std::fs;
 use std::path::PathBuf;
 
@@ -6,6 +8,242 @@ use std::path::PathBuf;
 pub struct Config {
     pub default_url: String,
     pub update_interval: u64,
+    /// Validator identities to highlight in leader schedules.
+    #[serde(default)]
+    pub validators: Vec<String>,
+    /// Writable accounts whose local fee markets are tracked in the Fees tab.
+    #[serde(default)]
+    pub fee_accounts: Vec<String>,
+    /// Names shown next to addresses in the supply view.
+    #[serde(default)]
+    pub address_labels: BTreeMap<String, String>,
+    #[serde(default)]
+    pub history: HistoryConfig,
+    #[serde(default)]
+    pub alerts: AlertConfig,
+    #[serde(default)]
+    pub rpc: RpcConfig,
+    /// Named RPC endpoints with their auth and proxy settings. A profile
+    /// name can be given anywhere an RPC URL is expected, and a profile also
+    /// applies when its URL is given directly.
+    #[serde(default)]
+    pub profiles: BTreeMap<String, EndpointProfile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryConfig {
+    /// Record every probe snapshot to the history file.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// History file (defaults to `history.jsonl` in the solprobe data directory).
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+    /// Snapshots older than this are dropped from the history file.
+    #[serde(default = "default_retention_hours")]
+    pub retention_hours: u64,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        HistoryConfig {
+            enabled: true,
+            path: None,
+            retention_hours: default_retention_hours(),
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_retention_hours() -> u64 {
+    24 * 7
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertConfig {
+    /// Minimum time between two firing notifications for the same rule.
+    #[serde(default = "default_cooldown_secs")]
+    pub cooldown_secs: u64,
+    #[serde(default)]
+    pub rules: Vec<AlertRule>,
+    #[serde(default)]
+    pub notifiers: Vec<NotifierConfig>,
+}
+
+impl Default for AlertConfig {
+    fn default() -> Self {
+        AlertConfig {
+            cooldown_secs: default_cooldown_secs(),
+            rules: Vec::new(),
+            notifiers: Vec::new(),
+        }
+    }
+}
+
+fn default_cooldown_secs() -> u64 {
+    300
+}
+
+/// Fires while `metric` (a probe sample name such as `skip_rate_percent`) is
+/// above or below `threshold`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertRule {
+    pub name: String,
+    pub metric: String,
+    pub condition: AlertCondition,
+    pub threshold: f64,
+    #[serde(default)]
+    pub severity: AlertSeverity,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertCondition {
+    Above,
+    Below,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertSeverity {
+    Info,
+    #[default]
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum NotifierConfig {
+    /// POSTs the alert event as JSON.
+    Webhook { url: String },
+    Slack { url: String },
+    Discord { url: String },
+    PagerDuty {
+        routing_key: String,
+        #[serde(default = "default_pagerduty_url")]
+        url: String,
+    },
+    /// Runs a local command with the alert passed in `SOLPROBE_ALERT_*` environment variables.
+    Command {
+        command: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
+/// Retry and request budget policy applied to every RPC endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcConfig {
+    /// Retries after a 429, 502-504 or connection error before giving up.
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Backoff before the first retry; doubled on each further attempt.
+    #[serde(default = "default_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+    /// Cap on a single backoff, including delays requested by `Retry-After`.
+    #[serde(default = "default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+    /// Requests per second allowed to each endpoint (unlimited when unset).
+    #[serde(default)]
+    pub requests_per_second: Option<f64>,
+    /// Stream slots over the PubSub websocket in the dashboard, polling
+    /// when it is unavailable.
+    #[serde(default = "default_true")]
+    pub websocket: bool,
+}
+
+impl Default for RpcConfig {
+    fn default() -> Self {
+        RpcConfig {
+            max_retries: default_max_retries(),
+            initial_backoff_ms: default_initial_backoff_ms(),
+            max_backoff_ms: default_max_backoff_ms(),
+            requests_per_second: None,
+            websocket: true,
+        }
+    }
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_initial_backoff_ms() -> u64 {
+    250
+}
+
+fn default_max_backoff_ms() -> u64 {
+    10_000
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndpointProfile {
+    pub url: String,
+    /// PubSub endpoint, when it is not the HTTP URL with a `ws`/`wss` scheme.
+    #[serde(default)]
+    pub ws_url: Option<String>,
+    /// Extra HTTP headers sent with every request, e.g. `x-api-key`.
+    #[serde(default)]
+    pub headers: BTreeMap<String, Secret>,
+    /// Query parameters appended to the request URL, e.g. `api-key`.
+    #[serde(default)]
+    pub query: BTreeMap<String, Secret>,
+    #[serde(default)]
+    pub bearer_token: Option<Secret>,
+    #[serde(default)]
+    pub basic_auth: Option<BasicAuth>,
+    /// `http://`, `https://`, `socks5://` or `socks5h://` proxy URL.
+    #[serde(default)]
+    pub proxy: Option<Secret>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BasicAuth {
+    pub username: String,
+    #[serde(default)]
+    pub password: Option<Secret>,
+}
+
+/// A credential given inline, or read from an environment variable or a
+/// file so it can stay out of the config file. Never printed by `Debug`.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Secret {
+    Env { env: String },
+    File { file: PathBuf },
+    Value(String),
+}
+
+impl Secret {
+    pub fn resolve(&self) -> Result<String, Box<dyn std::error::Error>> {
+        match self {
+            Secret::Value(value) => Ok(value.clone()),
+            Secret::Env { env } => {
+                std::env::var(env).map_err(|_| format!("environment variable {} is not set", env).into())
+            }
+            Secret::File { file } => match fs::read_to_string(file) {
+                Ok(value) => Ok(value.trim().to_string()),
+                Err(e) => Err(format!("failed to read secret file {}: {}", file.display(), e).into()),
+            },
+        }
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Secret::Env { env } => write!(f, "Secret(env {})", env),
+            Secret::File { file } => write!(f, "Secret(file {})", file.display()),
+            Secret::Value(_) => write!(f, "Secret(***)"),
+        }
+    }
+}
+
+fn default_pagerduty_url() -> String {
+    "https://events.pagerduty.com/v2/enqueue".to_string()
 }
 
 impl Config {
@@ -18,6 +256,13 @@ impl Config {
             let default_config = Config {
                 default_url: "https://api.mainnet-beta.solana.com".to_string(),
                 update_interval: 5,
+                validators: Vec::new(),
+                fee_accounts: Vec::new(),
+                address_labels: BTreeMap::new(),
+                history: HistoryConfig::default(),
+                alerts: AlertConfig::default(),
+                rpc: RpcConfig::default(),
+                profiles: BTreeMap::new(),
             };
             let toml_str = toml::to_string(&default_config)?;
             fs::write(config_path, toml_str)?;
@@ -13,6 +13,14 @@ pub fn get_url_input() -> Result<String, io::Error> {
     }
 }
 
+/// Uses the URL given on the command line, prompting for one otherwise.
+pub fn resolve_url(url: Option<String>) -> Result<String, io::Error> {
+    match url {
+        Some(url) => Ok(url),
+        None => get_url_input(),
+    }
+}
+
 pub fn get_interval_input() -> Result<u64, io::Error> {
     print!("Enter update interval in seconds (default is 5): ");
     io::stdout().flush()?;